@@ -0,0 +1,371 @@
+use std::collections::BTreeMap;
+
+use namada_sdk::address::{Address, InternalAddress};
+use namada_sdk::borsh::BorshDeserialize;
+use namada_sdk::dec::Dec;
+use namada_sdk::parameters::storage::get_epochs_per_year_key;
+use namada_sdk::proof_of_stake::storage_key::last_pos_inflation_amount_key;
+use namada_sdk::queries::RPC;
+use namada_sdk::storage::{self, BlockHeight, KeySeg};
+use namada_sdk::token::storage_key::{
+    is_any_minted_balance_key, is_any_token_balance_key, masp_total_rewards,
+};
+use namada_sdk::token::Amount;
+use namada_sdk::{decode, rpc};
+use tendermint_rpc::HttpClient;
+
+use crate::sdk::namada::Sdk;
+use crate::state::State;
+
+use super::DoCheck;
+
+/// Balances of a token grouped by the kind of the owner
+#[derive(Clone, Debug, Default)]
+struct Holdings {
+    minted: Amount,
+    transparent: Amount,
+    masp: Amount,
+    pos: Amount,
+    governance: Amount,
+    pgf: Amount,
+    ibc: Amount,
+}
+
+impl Holdings {
+    fn add(&mut self, owner: &Address, amount: Amount) -> Result<(), String> {
+        let pool = match owner {
+            Address::Internal(InternalAddress::Masp) => &mut self.masp,
+            Address::Internal(InternalAddress::PoS)
+            | Address::Internal(InternalAddress::PosSlashPool) => &mut self.pos,
+            Address::Internal(InternalAddress::Governance) => &mut self.governance,
+            Address::Internal(InternalAddress::Pgf) => &mut self.pgf,
+            Address::Internal(InternalAddress::Ibc) => &mut self.ibc,
+            _ => &mut self.transparent,
+        };
+        *pool = pool
+            .checked_add(amount)
+            .ok_or_else(|| format!("Balance overflow for {owner}"))?;
+        Ok(())
+    }
+
+    fn total(&self) -> Option<Amount> {
+        self.transparent
+            .checked_add(self.masp)?
+            .checked_add(self.pos)?
+            .checked_add(self.governance)?
+            .checked_add(self.pgf)?
+            .checked_add(self.ibc)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ConservationCheck;
+
+impl DoCheck for ConservationCheck {
+    async fn check(&self, sdk: &Sdk, state: &mut State) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let prefix = storage::Key::from(Address::Internal(InternalAddress::Multitoken).to_db_key());
+        // All the balances are read from the same block
+        let response = RPC
+            .shell()
+            .storage_prefix(client, None, None, false, &prefix)
+            .await
+            .map_err(|e| format!("Failed to query the balances: {e}"))?;
+        let height = response.height;
+
+        let mut holdings = BTreeMap::<Address, Holdings>::new();
+        for value in response.data {
+            if let Some(token) = is_any_minted_balance_key(&value.key) {
+                let amount: Amount = decode(&value.value).map_err(|e| e.to_string())?;
+                holdings.entry(token.clone()).or_default().minted = amount;
+            } else if let Some([token, owner]) = is_any_token_balance_key(&value.key) {
+                let amount: Amount = decode(&value.value).map_err(|e| e.to_string())?;
+                holdings
+                    .entry(token.clone())
+                    .or_default()
+                    .add(owner, amount)?;
+            }
+        }
+
+        for (token, holding) in &holdings {
+            let total = holding
+                .total()
+                .ok_or_else(|| format!("Total balance overflow for {token}"))?;
+            tracing::info!(
+                "Token {token} at height {height}: minted {}, transparent {}, masp {}, pos {}, governance {}, pgf {}, ibc {}",
+                holding.minted,
+                holding.transparent,
+                holding.masp,
+                holding.pos,
+                holding.governance,
+                holding.pgf,
+                holding.ibc
+            );
+            if total != holding.minted {
+                return Err(format!(
+                    "Token {token} isn't conserved at height {height}: minted {} != balances {total}",
+                    holding.minted
+                ));
+            }
+        }
+
+        self.check_native_supply(sdk, state, &holdings, height)
+            .await
+    }
+
+    fn timing(&self) -> u32 {
+        30
+    }
+
+    fn name(&self) -> String {
+        "ConservationCheck".to_string()
+    }
+}
+
+impl ConservationCheck {
+    /// The native token is minted and burned only when a new epoch starts
+    async fn check_native_supply(
+        &self,
+        sdk: &Sdk,
+        state: &mut State,
+        holdings: &BTreeMap<Address, Holdings>,
+        height: BlockHeight,
+    ) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let native_token = rpc::query_native_token(client)
+            .await
+            .map_err(|e| e.to_string())?;
+        let minted = holdings
+            .get(&native_token)
+            .map(|holding| holding.minted)
+            .unwrap_or_default();
+        let epoch: u64 = rpc::query_epoch_at_height(client, height)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No epoch found".to_string())?
+            .into();
+
+        let governance = holdings
+            .get(&native_token)
+            .map(|holding| holding.governance)
+            .unwrap_or_default();
+        let masp_rewards: Amount = read_at(client, &masp_total_rewards(), height)
+            .await?
+            .unwrap_or_default();
+
+        if let Some((last_epoch, last_minted)) = state.last_native_supply {
+            if epoch == last_epoch && minted != last_minted {
+                return Err(format!(
+                    "Native token supply changed within epoch {epoch}: {last_minted} -> {minted}"
+                ));
+            }
+            if epoch > last_epoch {
+                let increased = minted >= last_minted;
+                let (change, sign) = if increased {
+                    (minted.checked_sub(last_minted).unwrap_or_default(), "+")
+                } else {
+                    (last_minted.checked_sub(minted).unwrap_or_default(), "-")
+                };
+                tracing::info!(
+                    "Native token supply from epoch {last_epoch} to {epoch}: {sign}{change}"
+                );
+
+                if let (Some(last_masp_rewards), Some(last_governance)) =
+                    (state.last_masp_total_rewards, state.last_native_governance)
+                {
+                    let epochs = epoch - last_epoch;
+                    let inflation = InflationParams::query(client, height)
+                        .await?
+                        .max_inflation(minted.max(last_minted), epochs)?;
+                    let masp_rewards = masp_rewards
+                        .checked_sub(last_masp_rewards)
+                        .ok_or_else(|| {
+                            format!(
+                                "MASP total rewards decreased: {last_masp_rewards} -> {masp_rewards}"
+                            )
+                        })?;
+                    SupplyBounds {
+                        inflation,
+                        masp_rewards,
+                        governance: last_governance,
+                    }
+                    .check(last_minted, minted)
+                    .map_err(|e| format!("{e} from epoch {last_epoch} to {epoch}"))?;
+                }
+            }
+        }
+        state.last_native_supply = Some((epoch, minted));
+        state.last_masp_total_rewards = Some(masp_rewards);
+        state.last_native_governance = Some(governance);
+
+        Ok(())
+    }
+}
+
+/// How much the native supply can change from an epoch to a later one
+#[derive(Clone, Debug)]
+struct SupplyBounds {
+    inflation: Amount,
+    masp_rewards: Amount,
+    governance: Amount,
+}
+
+impl SupplyBounds {
+    fn check(&self, last_minted: Amount, minted: Amount) -> Result<(), String> {
+        if minted >= last_minted {
+            let change = minted.checked_sub(last_minted).unwrap_or_default();
+            let max_increase = self
+                .inflation
+                .checked_add(self.masp_rewards)
+                .ok_or("Expected inflation overflow".to_string())?;
+            if change > max_increase {
+                return Err(format!(
+                    "Native token supply increased by {change}, more than PoS and PGF inflation {} + MASP rewards {}",
+                    self.inflation, self.masp_rewards
+                ));
+            }
+        } else {
+            let change = last_minted.checked_sub(minted).unwrap_or_default();
+            // Only the deposits of rejected proposals are burned
+            if change > self.governance {
+                return Err(format!(
+                    "Native token supply decreased by {change}, more than the governance funds {}",
+                    self.governance
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct InflationParams {
+    epochs_per_year: u64,
+    pos_rate: Dec,
+    last_pos_inflation: Amount,
+    pgf_rate: Dec,
+    stewards_rate: Dec,
+}
+
+impl InflationParams {
+    async fn query(client: &HttpClient, height: BlockHeight) -> Result<Self, String> {
+        let epochs_per_year: u64 = read_at(client, &get_epochs_per_year_key(), height)
+            .await?
+            .ok_or("No epochs per year found".to_string())?;
+        let pos_params = rpc::get_pos_params(client)
+            .await
+            .map_err(|e| format!("Failed to query PoS parameters: {e}"))?;
+        let pgf_params = RPC
+            .vp()
+            .pgf()
+            .parameters(client)
+            .await
+            .map_err(|e| format!("Failed to query PGF parameters: {e}"))?;
+        let last_pos_inflation: Amount = read_at(client, &last_pos_inflation_amount_key(), height)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Self {
+            epochs_per_year,
+            pos_rate: pos_params.max_inflation_rate,
+            last_pos_inflation,
+            pgf_rate: pgf_params.pgf_inflation_rate,
+            stewards_rate: pgf_params.stewards_inflation_rate,
+        })
+    }
+
+    /// Upper bound of the PoS and PGF inflation minted over the epochs
+    fn max_inflation(&self, supply: Amount, epochs: u64) -> Result<Amount, String> {
+        let per_epoch = |rate| {
+            supply
+                .mul_ceil(rate)
+                .map(|amount| {
+                    amount
+                        .checked_div_u64(self.epochs_per_year)
+                        .unwrap_or_default()
+                })
+                .map_err(|e| e.to_string())
+        };
+        let pos = per_epoch(self.pos_rate)?.max(self.last_pos_inflation);
+        let pgf = per_epoch(self.pgf_rate)?;
+        let stewards = per_epoch(self.stewards_rate)?;
+
+        pos.checked_add(pgf)
+            .and_then(|amount| amount.checked_add(stewards))
+            .and_then(|amount| amount.checked_mul(epochs))
+            .ok_or("Expected inflation overflow".to_string())
+    }
+}
+
+async fn read_at<T: BorshDeserialize>(
+    client: &HttpClient,
+    key: &storage::Key,
+    height: BlockHeight,
+) -> Result<Option<T>, String> {
+    let (bytes, _) = rpc::query_storage_value_bytes(client, key, Some(height), false)
+        .await
+        .map_err(|e| format!("Failed to query {key}: {e}"))?;
+    bytes
+        .map(|bytes| T::try_from_slice(&bytes).map_err(|e| e.to_string()))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn params() -> InflationParams {
+        InflationParams {
+            epochs_per_year: 100,
+            pos_rate: Dec::from_str("0.1").expect("Rate should be parsed"),
+            last_pos_inflation: Amount::zero(),
+            pgf_rate: Dec::from_str("0.05").expect("Rate should be parsed"),
+            stewards_rate: Dec::from_str("0.01").expect("Rate should be parsed"),
+        }
+    }
+
+    #[test]
+    fn test_max_inflation_per_epoch() {
+        let supply = Amount::from_u64(1_000_000);
+
+        // (100_000 + 50_000 + 10_000) / 100 per epoch
+        assert_eq!(
+            params().max_inflation(supply, 1),
+            Ok(Amount::from_u64(1_600))
+        );
+        assert_eq!(
+            params().max_inflation(supply, 3),
+            Ok(Amount::from_u64(4_800))
+        );
+
+        let params = InflationParams {
+            last_pos_inflation: Amount::from_u64(5_000),
+            ..params()
+        };
+        assert_eq!(params.max_inflation(supply, 1), Ok(Amount::from_u64(5_600)));
+    }
+
+    #[test]
+    fn test_supply_bounds() {
+        let bounds = SupplyBounds {
+            inflation: Amount::from_u64(1_600),
+            masp_rewards: Amount::from_u64(400),
+            governance: Amount::from_u64(500),
+        };
+        let last_minted = Amount::from_u64(1_000_000);
+
+        assert!(bounds.check(last_minted, last_minted).is_ok());
+        assert!(bounds
+            .check(last_minted, Amount::from_u64(1_002_000))
+            .is_ok());
+        assert!(bounds
+            .check(last_minted, Amount::from_u64(1_002_001))
+            .is_err());
+        assert!(bounds.check(last_minted, Amount::from_u64(999_500)).is_ok());
+        assert!(bounds
+            .check(last_minted, Amount::from_u64(999_499))
+            .is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use namada_sdk::storage::BlockHeight;
+use namada_sdk::{rpc, token};
+use tendermint_rpc::{Client, HttpClient};
+
+use crate::faults::FailureKind;
+use crate::sdk::namada::Sdk;
+
+use super::DoCheck;
+
+/// What every node should agree on at a height
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct NodeView {
+    block_hash: String,
+    app_hash: String,
+    epoch: u64,
+    total_supply: Option<Vec<u8>>,
+    validators: Vec<(String, u64)>,
+}
+
+impl NodeView {
+    fn diffs(&self, other: &Self) -> Vec<String> {
+        let mut diffs = vec![];
+        if self.block_hash != other.block_hash {
+            diffs.push(format!(
+                "block hash {} != {}",
+                self.block_hash, other.block_hash
+            ));
+        }
+        if self.app_hash != other.app_hash {
+            diffs.push(format!("app hash {} != {}", self.app_hash, other.app_hash));
+        }
+        if self.epoch != other.epoch {
+            diffs.push(format!("epoch {} != {}", self.epoch, other.epoch));
+        }
+        if self.total_supply != other.total_supply {
+            diffs.push("total supply".to_string());
+        }
+        if self.validators != other.validators {
+            diffs.push(format!(
+                "validator set {:?} != {:?}",
+                self.validators, other.validators
+            ));
+        }
+        diffs
+    }
+}
+
+/// Nodes more than `max_lag` blocks behind the highest one
+fn lagging_nodes(heights: &BTreeMap<String, u64>, max_lag: u64) -> Vec<String> {
+    let Some(max_height) = heights.values().max() else {
+        return vec![];
+    };
+    heights
+        .iter()
+        .filter(|(_, height)| max_height - **height > max_lag)
+        .map(|(name, height)| format!("{name} at {height}"))
+        .collect()
+}
+
+/// Compare the views of all nodes with the first one
+fn check_divergence(views: &BTreeMap<String, NodeView>, height: u64) -> Result<(), String> {
+    let Some((first_name, first_view)) = views.iter().next() else {
+        return Ok(());
+    };
+    for (name, view) in views.iter().skip(1) {
+        let diffs = view.diffs(first_view);
+        if !diffs.is_empty() {
+            return Err(format!(
+                "Node {name} diverged from {first_name} at height {height}: {}",
+                diffs.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ConsistencyCheck {
+    pub max_lag: u64,
+}
+
+impl DoCheck for ConsistencyCheck {
+    async fn check(&self, sdk: &Sdk, _state: &mut crate::state::State) -> Result<(), String> {
+        if sdk.nodes.len() < 2 {
+            return Ok(());
+        }
+
+        // Nodes can be stopped by the faults
+        let mut heights = BTreeMap::new();
+        let mut unreachable = vec![];
+        for node in &sdk.nodes {
+            match node.client.status().await {
+                Ok(status) => {
+                    heights.insert(
+                        node.name.clone(),
+                        status.sync_info.latest_block_height.value(),
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!("Node {} is unreachable: {e}", node.name);
+                    unreachable.push(node.name.clone());
+                }
+            }
+        }
+        if heights.len() < 2 {
+            return Err(FailureKind::Unreachable.tag(format!(
+                "Only {} of {} nodes are reachable, unreachable: {}",
+                heights.len(),
+                sdk.nodes.len(),
+                unreachable.join(", ")
+            )));
+        }
+        let min_height = heights
+            .values()
+            .min()
+            .cloned()
+            .expect("A node should exist");
+        let max_height = heights
+            .values()
+            .max()
+            .cloned()
+            .expect("A node should exist");
+
+        let stuck = lagging_nodes(&heights, self.max_lag);
+        if !stuck.is_empty() {
+            return Err(FailureKind::Lag.tag(format!(
+                "Nodes are more than {} blocks behind {max_height}: {}",
+                self.max_lag,
+                stuck.join(", ")
+            )));
+        }
+
+        let native_token = rpc::query_native_token(&sdk.namada.client)
+            .await
+            .map_err(|e| e.to_string())?;
+        let mut views = BTreeMap::new();
+        for node in &sdk.nodes {
+            if !heights.contains_key(&node.name) {
+                continue;
+            }
+            let view = node_view(&node.client, min_height, &native_token)
+                .await
+                .map_err(|e| {
+                    FailureKind::Unreachable.tag(format!(
+                        "Failed to query {} at {min_height}: {e}",
+                        node.name
+                    ))
+                })?;
+            views.insert(node.name.clone(), view);
+        }
+
+        check_divergence(&views, min_height)?;
+
+        tracing::info!(
+            "{} nodes are consistent at height {min_height} (max {max_height})",
+            views.len()
+        );
+        Ok(())
+    }
+
+    fn timing(&self) -> u32 {
+        30
+    }
+
+    fn tolerates(&self) -> Vec<FailureKind> {
+        vec![FailureKind::Lag, FailureKind::Unreachable]
+    }
+
+    fn name(&self) -> String {
+        "ConsistencyCheck".to_string()
+    }
+}
+
+async fn node_view(
+    client: &HttpClient,
+    height: u64,
+    native_token: &namada_sdk::address::Address,
+) -> Result<NodeView, String> {
+    let tm_height =
+        namada_sdk::tendermint::block::Height::try_from(height).map_err(|e| e.to_string())?;
+    let block = client.block(tm_height).await.map_err(|e| e.to_string())?;
+
+    let epoch = rpc::query_epoch_at_height(client, BlockHeight(height))
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No epoch found".to_string())?;
+
+    let key = token::storage_key::minted_balance_key(native_token);
+    let (total_supply, _) =
+        rpc::query_storage_value_bytes(client, &key, Some(BlockHeight(height)), false)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let mut validators: Vec<_> = client
+        .validators(tm_height, tendermint_rpc::Paging::All)
+        .await
+        .map_err(|e| e.to_string())?
+        .validators
+        .into_iter()
+        .map(|validator| (validator.address.to_string(), validator.power()))
+        .collect();
+    validators.sort();
+
+    Ok(NodeView {
+        block_hash: block.block_id.hash.to_string(),
+        app_hash: block.block.header.app_hash.to_string(),
+        epoch: epoch.into(),
+        total_supply,
+        validators,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> NodeView {
+        NodeView {
+            block_hash: "AB".to_string(),
+            app_hash: "CD".to_string(),
+            epoch: 3,
+            total_supply: Some(vec![1, 2]),
+            validators: vec![("V0".to_string(), 2), ("V1".to_string(), 15)],
+        }
+    }
+
+    #[test]
+    fn test_lagging_nodes() {
+        let heights = BTreeMap::from([
+            ("fullnode".to_string(), 95),
+            ("validator0".to_string(), 100),
+            ("validator1".to_string(), 89),
+        ]);
+        assert_eq!(lagging_nodes(&heights, 10), vec!["validator1 at 89"]);
+        assert!(lagging_nodes(&heights, 11).is_empty());
+        assert!(lagging_nodes(&BTreeMap::new(), 0).is_empty());
+    }
+
+    #[test]
+    fn test_same_views_are_consistent() {
+        let views = BTreeMap::from([
+            ("fullnode".to_string(), view()),
+            ("validator0".to_string(), view()),
+        ]);
+        assert!(check_divergence(&views, 10).is_ok());
+    }
+
+    #[test]
+    fn test_divergence_names_the_differences() {
+        let mut diverged = view();
+        diverged.app_hash = "EF".to_string();
+        diverged.total_supply = None;
+        let views = BTreeMap::from([
+            ("fullnode".to_string(), view()),
+            ("validator0".to_string(), diverged),
+        ]);
+
+        let error = check_divergence(&views, 10).expect_err("Nodes should diverge");
+        assert_eq!(
+            error,
+            "Node validator0 diverged from fullnode at height 10: app hash EF != CD, total supply"
+        );
+        assert_eq!(FailureKind::of(&error), None);
+    }
+}
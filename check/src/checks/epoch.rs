@@ -1,5 +1,6 @@
 use namada_sdk::rpc;
 
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
 
 use super::DoCheck;
@@ -16,6 +17,7 @@ impl DoCheck for EpochCheck {
 
         if state.last_epoch <= current_epoch {
             state.last_epoch = current_epoch;
+            METRICS.epoch.set(current_epoch as i64);
             tracing::info!("Epoch ok");
             Ok(())
         } else {
@@ -0,0 +1,539 @@
+use namada_sdk::address::{Address, GOV};
+use namada_sdk::events::extend::{ReadFromEventAttributes, UserAccount};
+use namada_sdk::governance::event::types::{PROPOSAL_PASSED, PROPOSAL_REJECTED};
+use namada_sdk::governance::event::{ProposalCodeExitStatus, ProposalId};
+use namada_sdk::governance::storage::keys::{get_funds_key, get_proposal_code_key};
+use namada_sdk::governance::storage::proposal::{
+    AddRemove, PGFAction, ProposalType, StorageProposal,
+};
+use namada_sdk::governance::utils::{
+    compute_proposal_result, ProposalResult, ProposalVotes, TallyResult, TallyType, Vote,
+};
+use namada_sdk::proof_of_stake::types::ValidatorState;
+use namada_sdk::queries::RPC;
+use namada_sdk::rpc;
+use namada_sdk::storage::{BlockHeight, Epoch};
+use namada_sdk::tendermint::abci::Event;
+use namada_sdk::token::event::types::{BURN, TRANSFER};
+use namada_sdk::token::event::{Amount as EventAmount, Descriptor, PostBalances, TargetAccounts};
+use namada_sdk::token::Amount;
+use namada_sdk::uint::Uint;
+use tendermint_rpc::{Client, HttpClient};
+
+use crate::sdk::namada::Sdk;
+use crate::state::{ProposalStage, State, TrackedProposal};
+
+use super::DoCheck;
+
+/// Follow each proposal until its activation and verify the execution
+#[derive(Clone, Debug, Default)]
+pub struct GovernanceCheck;
+
+impl DoCheck for GovernanceCheck {
+    async fn check(&self, sdk: &Sdk, state: &mut State) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let epoch = rpc::query_epoch(client).await.map_err(|e| e.to_string())?;
+
+        // Track new proposals
+        let mut proposal_id = state
+            .last_tracked_proposal_id
+            .map_or(0, |last_id| last_id + 1);
+        while let Some(proposal) = rpc::query_proposal_by_id(client, proposal_id)
+            .await
+            .map_err(|e| e.to_string())?
+        {
+            state.last_tracked_proposal_id = Some(proposal_id);
+            // Proposals executed before the checker started can't be verified
+            if proposal.activation_epoch > epoch {
+                let funds: Amount = rpc::query_storage_value(client, &get_funds_key(proposal_id))
+                    .await
+                    .map_err(|e| e.to_string())?;
+                tracing::info!("Tracking proposal {proposal_id} with funds {funds}");
+                state.tracked_proposals.insert(
+                    proposal_id,
+                    TrackedProposal {
+                        activation_epoch: proposal.activation_epoch.0,
+                        funds,
+                        stage: ProposalStage::Pending,
+                        author: Some(proposal.author.clone()),
+                    },
+                );
+            }
+            proposal_id += 1;
+        }
+
+        let mut executed = vec![];
+        for (proposal_id, tracked) in state.tracked_proposals.iter_mut() {
+            let proposal = rpc::query_proposal_by_id(client, *proposal_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Proposal {proposal_id} disappeared"))?;
+            let stage = if epoch >= proposal.activation_epoch {
+                ProposalStage::Executed
+            } else if epoch >= proposal.voting_end_epoch {
+                ProposalStage::Ended
+            } else if epoch >= proposal.voting_start_epoch {
+                ProposalStage::Voting
+            } else {
+                ProposalStage::Pending
+            };
+            if stage != tracked.stage {
+                tracing::info!(
+                    "Proposal {proposal_id}: {:?} -> {stage:?} at epoch {epoch}",
+                    tracked.stage
+                );
+                tracked.stage = stage;
+            }
+
+            if stage == ProposalStage::Executed {
+                let result = check_tally(sdk, &proposal).await?;
+                let height = first_height_of_epoch(client, proposal.activation_epoch).await?;
+                let events = client
+                    .block_results(
+                        namada_sdk::tendermint::block::Height::try_from(height)
+                            .map_err(|e| e.to_string())?,
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to query block results at {height}: {e}"))?
+                    .finalize_block_events;
+                check_execution(sdk, &proposal, &result, &events).await?;
+                check_deposit(sdk, &proposal, tracked, &result, height, &events).await?;
+                executed.push(*proposal_id);
+            }
+        }
+        state
+            .tracked_proposals
+            .retain(|id, _| !executed.contains(id));
+
+        check_locked_funds(sdk, state).await
+    }
+
+    fn timing(&self) -> u32 {
+        30
+    }
+
+    fn name(&self) -> String {
+        "GovernanceCheck".to_string()
+    }
+}
+
+/// Recompute the tally from the votes at the voting end epoch like the ledger
+async fn check_tally(sdk: &Sdk, proposal: &StorageProposal) -> Result<ProposalResult, String> {
+    let client = &sdk.namada.client;
+    let stored = rpc::query_proposal_result(client, proposal.id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("No result for proposal {}", proposal.id))?;
+
+    let tally_epoch = proposal.voting_end_epoch;
+    let votes = rpc::query_proposal_votes(client, proposal.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut counted_votes = vec![];
+    for vote in votes {
+        let (validator_state, _) =
+            rpc::get_validator_state(client, &vote.validator, Some(tally_epoch))
+                .await
+                .map_err(|e| e.to_string())?;
+        if !is_counted(validator_state) {
+            continue;
+        }
+
+        let stake = if vote.is_validator() {
+            rpc::get_validator_stake(client, tally_epoch, &vote.validator)
+                .await
+                .map_err(|e| e.to_string())?
+        } else {
+            rpc::get_bond_amount_at(client, &vote.delegator, &vote.validator, tally_epoch)
+                .await
+                .map_err(|e| e.to_string())?
+        };
+        counted_votes.push((vote, stake));
+    }
+    let total_voting_power = rpc::get_total_active_voting_power(client, tally_epoch)
+        .await
+        .map_err(|e| e.to_string())?;
+    let expected = tally(counted_votes, total_voting_power, stored.tally_type)?;
+
+    if !is_same_tally(&expected, &stored) {
+        return Err(format!(
+            "Tally of proposal {} doesn't match: expected {expected:?}, stored {stored:?}",
+            proposal.id
+        ));
+    }
+    tracing::info!("Proposal {} was {}", proposal.id, stored.result);
+
+    Ok(stored)
+}
+
+/// The votes of jailed and inactive validators and their delegators aren't counted
+fn is_counted(validator_state: Option<ValidatorState>) -> bool {
+    !matches!(
+        validator_state,
+        None | Some(ValidatorState::Jailed) | Some(ValidatorState::Inactive)
+    )
+}
+
+/// Tally the votes with their stake at the tally epoch
+fn tally(
+    votes: Vec<(Vote, Amount)>,
+    total_voting_power: Amount,
+    tally_type: TallyType,
+) -> Result<ProposalResult, String> {
+    let mut proposal_votes = ProposalVotes::default();
+    for (vote, stake) in votes {
+        if vote.is_validator() {
+            proposal_votes.add_validator(&vote.validator, stake, vote.data);
+        } else {
+            proposal_votes.add_delegator(&vote.delegator, &vote.validator, stake, vote.data);
+        }
+    }
+    compute_proposal_result(proposal_votes, total_voting_power, tally_type)
+        .map_err(|e| e.to_string())
+}
+
+fn is_same_tally(expected: &ProposalResult, stored: &ProposalResult) -> bool {
+    matches!(
+        (expected.result, stored.result),
+        (TallyResult::Passed, TallyResult::Passed) | (TallyResult::Rejected, TallyResult::Rejected)
+    ) && expected.total_voting_power == stored.total_voting_power
+        && expected.total_yay_power == stored.total_yay_power
+        && expected.total_nay_power == stored.total_nay_power
+        && expected.total_abstain_power == stored.total_abstain_power
+}
+
+/// Verify the state changes of the executed proposal
+async fn check_execution(
+    sdk: &Sdk,
+    proposal: &StorageProposal,
+    result: &ProposalResult,
+    events: &[Event],
+) -> Result<(), String> {
+    let client = &sdk.namada.client;
+    let passed = matches!(result.result, TallyResult::Passed);
+
+    let event_type = if passed {
+        &*PROPOSAL_PASSED
+    } else {
+        &*PROPOSAL_REJECTED
+    };
+    let event = events
+        .iter()
+        .find(|event| {
+            event.kind == event_type
+                && ProposalId::read_from_event_attributes(&event.attributes).ok()
+                    == Some(proposal.id)
+        })
+        .ok_or_else(|| format!("No {event_type} event for proposal {}", proposal.id))?;
+
+    match &proposal.r#type {
+        ProposalType::Default => {}
+        ProposalType::DefaultWithWasm(_) => {
+            let (code, _) = rpc::query_storage_value_bytes(
+                client,
+                &get_proposal_code_key(proposal.id),
+                None,
+                false,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            if code.is_none() {
+                return Err(format!("No wasm code for proposal {}", proposal.id));
+            }
+            let is_successful =
+                ProposalCodeExitStatus::read_from_event_attributes(&event.attributes)
+                    .map_err(|e| e.to_string())?;
+            if passed && !is_successful {
+                return Err(format!(
+                    "Wasm code of the passed proposal {} failed",
+                    proposal.id
+                ));
+            }
+        }
+        ProposalType::PGFSteward(actions) if passed => {
+            let stewards: Vec<_> = rpc::query_pgf_stewards(client)
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|steward| steward.address)
+                .collect();
+            let max_stewards = rpc::query_pgf_parameters(client)
+                .await
+                .maximum_number_of_stewards;
+            for action in actions {
+                match action {
+                    AddRemove::Add(address)
+                        if !stewards.contains(address)
+                            && (stewards.len() as u64) < max_stewards =>
+                    {
+                        return Err(format!(
+                            "Steward {address} wasn't added by proposal {}",
+                            proposal.id
+                        ));
+                    }
+                    AddRemove::Remove(address)
+                        if stewards.contains(address)
+                            && !actions.contains(&AddRemove::Add(address.clone())) =>
+                    {
+                        return Err(format!(
+                            "Steward {address} wasn't removed by proposal {}",
+                            proposal.id
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ProposalType::PGFPayment(actions) if passed => {
+            let fundings = rpc::query_pgf_fundings(client)
+                .await
+                .map_err(|e| e.to_string())?;
+            for action in actions {
+                let PGFAction::Continuous(action) = action else {
+                    continue;
+                };
+                match action {
+                    AddRemove::Add(target)
+                        if !fundings.iter().any(|funding| {
+                            funding.detail.target() == target.target() && funding.id >= proposal.id
+                        }) =>
+                    {
+                        return Err(format!(
+                            "Continuous funding to {} wasn't added by proposal {}",
+                            target.target(),
+                            proposal.id
+                        ));
+                    }
+                    AddRemove::Remove(target)
+                        if fundings.iter().any(|funding| {
+                            funding.detail.target() == target.target() && funding.id <= proposal.id
+                        }) =>
+                    {
+                        return Err(format!(
+                            "Continuous funding to {} wasn't removed by proposal {}",
+                            target.target(),
+                            proposal.id
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ProposalType::PGFPayment(_) if result.two_thirds_nay_over_two_thirds_total() => {
+            if rpc::is_steward(client, &proposal.author).await {
+                return Err(format!(
+                    "Steward {} wasn't removed after the rejection of proposal {}",
+                    proposal.author, proposal.id
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// The deposit is refunded to the author when the proposal passes, otherwise it's burned
+async fn check_deposit(
+    sdk: &Sdk,
+    proposal: &StorageProposal,
+    tracked: &TrackedProposal,
+    result: &ProposalResult,
+    height: u64,
+    events: &[Event],
+) -> Result<(), String> {
+    let client = &sdk.namada.client;
+    let native_token = rpc::query_native_token(client)
+        .await
+        .map_err(|e| e.to_string())?;
+    let funds = tracked.funds.raw_amount();
+    let is_deposit_event = |event: &&Event, kind: &str, descriptor: &str| {
+        event.kind == kind
+            && Descriptor::read_from_event_attributes(&event.attributes).ok()
+                == Some(descriptor.to_string())
+    };
+
+    if !matches!(result.result, TallyResult::Passed) {
+        let burned = events
+            .iter()
+            .filter(|event| is_deposit_event(event, &BURN, "governance-locked-funds-burn"))
+            .any(|event| {
+                EventAmount::read_from_event_attributes(&event.attributes).ok() == Some(funds)
+            });
+        if !burned {
+            return Err(format!(
+                "Deposit {} of the rejected proposal {} wasn't burned",
+                tracked.funds, proposal.id
+            ));
+        }
+        return Ok(());
+    }
+
+    let Some(author) = &tracked.author else {
+        return Ok(());
+    };
+    let account = (UserAccount::Internal(author.clone()), native_token.clone());
+    let pre_balance =
+        rpc::get_token_balance(client, &native_token, author, Some(BlockHeight(height - 1)))
+            .await
+            .map_err(|e| e.to_string())?;
+
+    // The author can get the deposits of several proposals in the block
+    let mut expected = pre_balance.raw_amount();
+    for event in events
+        .iter()
+        .filter(|event| is_deposit_event(event, &TRANSFER, "governance-locked-funds-refund"))
+    {
+        let targets = TargetAccounts::read_from_event_attributes(&event.attributes)
+            .map_err(|e| e.to_string())?;
+        let Some(amount) = find_account(&targets.0, &account) else {
+            continue;
+        };
+        expected = expected
+            .checked_add(amount)
+            .ok_or("Refund overflow".to_string())?;
+        if amount != funds {
+            continue;
+        }
+
+        let post_balances = PostBalances::read_from_event_attributes(&event.attributes)
+            .map_err(|e| e.to_string())?;
+        let post_balance = find_account(&post_balances.0, &account)
+            .ok_or_else(|| format!("No post balance of {author} in the refund"))?;
+        if post_balance != expected {
+            return Err(format!(
+                "Author {author} of proposal {} has {post_balance} after the refund of {}, expected {expected}",
+                proposal.id, tracked.funds
+            ));
+        }
+        return Ok(());
+    }
+
+    Err(format!(
+        "Deposit {} of the passed proposal {} wasn't refunded to {author}",
+        tracked.funds, proposal.id
+    ))
+}
+
+fn find_account(
+    entries: &[((UserAccount, Address), Uint)],
+    account: &(UserAccount, Address),
+) -> Option<Uint> {
+    entries
+        .iter()
+        .find(|(entry, _)| entry == account)
+        .map(|(_, amount)| *amount)
+}
+
+/// Proposals are executed in the first block of their activation epoch
+async fn first_height_of_epoch(client: &HttpClient, epoch: Epoch) -> Result<u64, String> {
+    let mut high = RPC
+        .shell()
+        .first_block_height_of_current_epoch(client)
+        .await
+        .map_err(|e| e.to_string())?
+        .0;
+    let mut low = 1;
+    while low < high {
+        let mid = (low + high) / 2;
+        let mid_epoch = rpc::query_epoch_at_height(client, BlockHeight(mid))
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("No epoch at height {mid}"))?;
+        if mid_epoch >= epoch {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Governance should hold only the funds of the proposals which haven't been executed
+async fn check_locked_funds(sdk: &Sdk, state: &State) -> Result<(), String> {
+    let client = &sdk.namada.client;
+    let native_token = rpc::query_native_token(client)
+        .await
+        .map_err(|e| e.to_string())?;
+    let balance = rpc::get_token_balance(client, &native_token, &GOV, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let locked = state
+        .tracked_proposals
+        .values()
+        .try_fold(Amount::zero(), |sum, tracked| {
+            sum.checked_add(tracked.funds)
+        })
+        .ok_or("Locked funds overflow".to_string())?;
+
+    // A proposal submitted or executed meanwhile is caught up by the retry
+    if balance != locked {
+        return Err(format!(
+            "Governance balance {balance} doesn't match the locked funds {locked}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_sdk::address::EstablishedAddress;
+    use namada_sdk::governance::ProposalVote;
+
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::Established(EstablishedAddress::from([byte; 20]))
+    }
+
+    fn vote(validator: u8, delegator: u8, data: ProposalVote, stake: u64) -> (Vote, Amount) {
+        let vote = Vote {
+            validator: address(validator),
+            delegator: address(delegator),
+            data,
+        };
+        (vote, Amount::from_u64(stake))
+    }
+
+    #[test]
+    fn test_counted_validator_states() {
+        assert!(is_counted(Some(ValidatorState::Consensus)));
+        assert!(is_counted(Some(ValidatorState::BelowCapacity)));
+        assert!(!is_counted(Some(ValidatorState::Jailed)));
+        assert!(!is_counted(Some(ValidatorState::Inactive)));
+        assert!(!is_counted(None));
+    }
+
+    #[test]
+    fn test_tally_two_fifths() {
+        let total = Amount::from_u64(100);
+        let votes = vec![
+            vote(1, 1, ProposalVote::Yay, 50),
+            vote(2, 2, ProposalVote::Nay, 20),
+        ];
+        let result =
+            tally(votes.clone(), total, TallyType::TwoFifths).expect("Tally should be computed");
+        assert!(matches!(result.result, TallyResult::Passed));
+        assert_eq!(result.total_yay_power, Amount::from_u64(50));
+        assert_eq!(result.total_nay_power, Amount::from_u64(20));
+
+        // The delegator overrides the vote of its validator with its bond
+        let mut votes = votes;
+        votes.push(vote(1, 3, ProposalVote::Nay, 10));
+        let overridden =
+            tally(votes, total, TallyType::TwoFifths).expect("Tally should be computed");
+        assert!(matches!(overridden.result, TallyResult::Rejected));
+        assert_eq!(overridden.total_yay_power, Amount::from_u64(40));
+        assert_eq!(overridden.total_nay_power, Amount::from_u64(30));
+        assert!(!is_same_tally(&result, &overridden));
+        assert!(is_same_tally(&result, &result));
+    }
+
+    #[test]
+    fn test_tally_without_enough_turnout() {
+        let votes = vec![vote(1, 1, ProposalVote::Yay, 30)];
+        let result = tally(votes, Amount::from_u64(100), TallyType::OneHalfOverOneThird)
+            .expect("Tally should be computed");
+        assert!(matches!(result.result, TallyResult::Rejected));
+    }
+}
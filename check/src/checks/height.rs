@@ -1,5 +1,6 @@
 use namada_sdk::rpc;
 
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
 
 use super::DoCheck;
@@ -22,6 +23,7 @@ impl DoCheck for HeightCheck {
                 current_block_height
             );
             state.last_block_height = current_block_height;
+            METRICS.block_height.set(current_block_height as i64);
             Ok(())
         } else {
             Err(format!(
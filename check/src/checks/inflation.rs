@@ -4,6 +4,7 @@ use namada_sdk::governance::utils::TallyResult;
 use namada_sdk::rpc;
 use namada_sdk::token::Amount;
 
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
 use crate::state::State;
 
@@ -36,6 +37,12 @@ impl DoCheck for InflationCheck {
                 state.last_total_supply
             );
             state.last_total_supply = current_total_supply;
+            METRICS.total_supply.set(
+                current_total_supply
+                    .to_string()
+                    .parse()
+                    .expect("Amount should be a number"),
+            );
             Ok(())
         } else {
             Err(format!(
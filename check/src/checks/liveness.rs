@@ -0,0 +1,320 @@
+use chrono::{DateTime, Utc};
+use tendermint_rpc::Client;
+
+use crate::faults::{Expectation, Timeline};
+use crate::metrics::METRICS;
+use crate::sdk::namada::Sdk;
+use crate::state::{percentile, Halt, State, MAX_BLOCK_TIMES};
+
+use super::DoCheck;
+
+/// Maximum number of block headers returned by a `blockchain` query
+const BLOCKCHAIN_PAGE: u64 = 20;
+/// Number of the latest blocks for the block time percentile metrics
+const METRICS_WINDOW: usize = 100;
+
+#[derive(Clone, Debug, Default)]
+pub struct LivenessCheck {
+    pub halt_threshold_sec: u64,
+    pub recovery_timeout_sec: u64,
+    pub timeline: Timeline,
+}
+
+impl DoCheck for LivenessCheck {
+    async fn check(&self, sdk: &Sdk, state: &mut State) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let now = Utc::now();
+        let latest_height = client
+            .status()
+            .await
+            .map_err(|e| format!("Failed to query status: {e}"))?
+            .sync_info
+            .latest_block_height
+            .value();
+
+        // Only keep the latest blocks after a long downtime of the checker
+        let last_recorded = state.block_times.back().map(|(height, _)| *height);
+        let mut from = last_recorded
+            .map(|height| height + 1)
+            .unwrap_or(latest_height)
+            .max(latest_height.saturating_sub(MAX_BLOCK_TIMES as u64) + 1);
+        while from <= latest_height {
+            let to = (from + BLOCKCHAIN_PAGE - 1).min(latest_height);
+            let mut metas = client
+                .blockchain(from as u32, to as u32)
+                .await
+                .map_err(|e| format!("Failed to query blocks {from}..={to}: {e}"))?
+                .block_metas;
+            metas.sort_by_key(|meta| meta.header.height);
+            for meta in metas {
+                let time = to_utc(meta.header.time);
+                if let Some((height, last_time)) = state.block_times.back() {
+                    if *height + 1 == meta.header.height.value() {
+                        let block_time = (time - *last_time).num_milliseconds() as f64 / 1000.0;
+                        METRICS.block_time.set(block_time);
+                    }
+                }
+                state
+                    .block_times
+                    .push_back((meta.header.height.value(), time));
+            }
+            from = to + 1;
+        }
+        while state.block_times.len() > MAX_BLOCK_TIMES {
+            state.block_times.pop_front();
+        }
+        let block_times = state.sorted_block_times(METRICS_WINDOW);
+        if let (Some(p50), Some(p95)) = (percentile(&block_times, 50), percentile(&block_times, 95))
+        {
+            METRICS.block_time_p50.set(p50);
+            METRICS.block_time_p95.set(p95);
+        }
+
+        self.track_halts(state, now)
+    }
+
+    fn timing(&self) -> u32 {
+        3
+    }
+
+    fn max_retries(&self) -> u64 {
+        0
+    }
+
+    fn name(&self) -> String {
+        "LivenessCheck".to_string()
+    }
+}
+
+impl LivenessCheck {
+    /// Record the recovery of the last halt and detect a new one from the latest block time
+    fn track_halts(&self, state: &mut State, now: DateTime<Utc>) -> Result<(), String> {
+        let Some((height, last_block_time)) = state.block_times.back().cloned() else {
+            return Ok(());
+        };
+
+        if let Some(halt) = state.halts.last_mut() {
+            if halt.recovered_at.is_none() && height > halt.height {
+                let recovery_sec = (last_block_time - halt.started_at).num_seconds();
+                tracing::info!(
+                    "Chain recovered at height {height} after halting {recovery_sec}s at {}",
+                    halt.height
+                );
+                halt.recovered_at = Some(last_block_time);
+                halt.recovery_sec = Some(recovery_sec);
+                halt.recovery_after_fault_sec = halt
+                    .faults_ended_at
+                    .map(|ended_at| (last_block_time - ended_at).num_seconds().max(0));
+            }
+        }
+
+        let stalled_sec = (now - last_block_time).num_seconds();
+        let halted = stalled_sec > self.halt_threshold_sec as i64;
+        METRICS.halted.set(halted as i64);
+        if !halted {
+            return Ok(());
+        }
+
+        let is_new_halt = state
+            .halts
+            .last()
+            .is_none_or(|halt| halt.recovered_at.is_some());
+        if is_new_halt {
+            state.halts.push(Halt {
+                height,
+                started_at: last_block_time,
+                detected_at: now,
+                two_nodes_have_two_third: state.two_nodes_have_two_third,
+                recovered_at: None,
+                recovery_sec: None,
+                faults: vec![],
+                faults_ended_at: None,
+                recovery_after_fault_sec: None,
+                reported: false,
+            });
+        }
+        let halt = state.halts.last_mut().expect("Halt should exist");
+
+        let faults = self
+            .timeline
+            .expecting(Expectation::Halt, halt.started_at, now);
+        if !faults.is_empty() {
+            halt.faults = faults.iter().map(|fault| fault.label()).collect();
+            halt.faults_ended_at = faults
+                .iter()
+                .map(|fault| fault.ended_at)
+                .collect::<Option<Vec<_>>>()
+                .and_then(|ended_at| ended_at.into_iter().max());
+            let Some(ended_at) = halt.faults_ended_at else {
+                tracing::warn!(
+                    "Chain halted at height {height} for {stalled_sec}s during {}",
+                    halt.faults.join(", ")
+                );
+                return Ok(());
+            };
+            let since_end_sec = (now - ended_at).num_seconds();
+            if since_end_sec > self.recovery_timeout_sec as i64 {
+                return report(
+                    halt,
+                    format!(
+                        "Chain didn't recover within {since_end_sec}s after {} ended",
+                        halt.faults.join(", ")
+                    ),
+                );
+            }
+            tracing::warn!(
+                "Chain halted at height {height} for {stalled_sec}s, recovering from {}",
+                halt.faults.join(", ")
+            );
+            return Ok(());
+        }
+
+        // Halting is expected when a single node fault can take more than 1/3 of the voting power
+        if halt.two_nodes_have_two_third {
+            report(
+                halt,
+                format!(
+                    "Chain halted at height {height} for {stalled_sec}s while two nodes have 2/3 of the voting power"
+                ),
+            )
+        } else {
+            tracing::warn!(
+                "Chain halted at height {height} for {stalled_sec}s, a single fault can halt the chain"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// An ongoing halt is a single violation
+fn report(halt: &mut Halt, error: String) -> Result<(), String> {
+    if halt.reported {
+        tracing::warn!("{error}");
+        return Ok(());
+    }
+    halt.reported = true;
+    Err(error)
+}
+
+fn to_utc(time: namada_sdk::tendermint::Time) -> DateTime<Utc> {
+    DateTime::from_timestamp_nanos(time.unix_timestamp_nanos() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::faults::FaultRecord;
+
+    fn check(timeline: Timeline) -> LivenessCheck {
+        LivenessCheck {
+            halt_threshold_sec: 30,
+            recovery_timeout_sec: 60,
+            timeline,
+        }
+    }
+
+    /// Blocks from height 1 with the times in seconds between them
+    fn state(start: DateTime<Utc>, block_times: &[i64]) -> State {
+        let mut state = State::from_height(0);
+        let mut time = start;
+        state.block_times.push_back((1, time));
+        for (i, block_time) in block_times.iter().enumerate() {
+            time += Duration::seconds(*block_time);
+            state.block_times.push_back((i as u64 + 2, time));
+        }
+        state
+    }
+
+    fn halt_fault(started_at: DateTime<Utc>, ended_at: DateTime<Utc>) -> FaultRecord {
+        FaultRecord {
+            id: 0,
+            fault: serde_json::json!({"kind": "partition", "scenario": "split-validators"}),
+            targets: vec![],
+            expectations: vec![Expectation::Halt],
+            started_at,
+            ended_at: Some(ended_at),
+        }
+    }
+
+    #[test]
+    fn test_block_time_percentiles() {
+        let start = Utc::now();
+        let mut state = state(start, &[5, 1, 3, 2, 4]);
+        // the blocks in between are missing
+        state
+            .block_times
+            .push_back((10, start + Duration::seconds(100)));
+
+        let block_times = state.sorted_block_times(100);
+        assert_eq!(block_times, vec![1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(percentile(&block_times, 50), Some(3.0));
+        assert_eq!(percentile(&block_times, 95), Some(5.0));
+        assert_eq!(state.sorted_block_times(3), vec![4.0]);
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_unexpected_halt_is_reported_once() {
+        let start = Utc::now();
+        let mut state = state(start, &[5, 5]);
+        let check = check(Timeline::default());
+
+        assert!(check
+            .track_halts(&mut state, start + Duration::seconds(30))
+            .is_ok());
+        assert!(state.halts.is_empty());
+
+        let now = start + Duration::seconds(60);
+        assert!(check.track_halts(&mut state, now).is_err());
+        assert!(check.track_halts(&mut state, now).is_ok());
+        assert_eq!(state.halts.len(), 1);
+        assert_eq!(state.halts[0].height, 3);
+
+        // a single fault can halt the chain
+        state.two_nodes_have_two_third = false;
+        state.halts.clear();
+        assert!(check.track_halts(&mut state, now).is_ok());
+    }
+
+    #[test]
+    fn test_halt_recovers_after_fault() {
+        let start = Utc::now();
+        let fault_end = start + Duration::seconds(100);
+        let check = check(Timeline {
+            faults: vec![halt_fault(start, fault_end)],
+        });
+        let mut state = state(start, &[5]);
+
+        assert!(check
+            .track_halts(&mut state, start + Duration::seconds(90))
+            .is_ok());
+        assert_eq!(state.halts[0].faults.len(), 1);
+        assert!(check
+            .track_halts(&mut state, fault_end + Duration::seconds(60))
+            .is_ok());
+
+        let recovered_at = fault_end + Duration::seconds(20);
+        state.block_times.push_back((3, recovered_at));
+        assert!(check.track_halts(&mut state, recovered_at).is_ok());
+        let halt = &state.halts[0];
+        assert_eq!(halt.recovered_at, Some(recovered_at));
+        assert_eq!(halt.recovery_sec, Some(115));
+        assert_eq!(halt.recovery_after_fault_sec, Some(20));
+    }
+
+    #[test]
+    fn test_halt_not_recovered_after_fault() {
+        let start = Utc::now();
+        let fault_end = start + Duration::seconds(100);
+        let check = check(Timeline {
+            faults: vec![halt_fault(start, fault_end)],
+        });
+        let mut state = state(start, &[5]);
+
+        assert!(check
+            .track_halts(&mut state, fault_end + Duration::seconds(61))
+            .is_err());
+    }
+}
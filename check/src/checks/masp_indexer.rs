@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::faults::FailureKind;
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
 
 use super::DoCheck;
@@ -15,15 +17,17 @@ pub struct MaspIndexerHeightCheck;
 impl DoCheck for MaspIndexerHeightCheck {
     async fn check(&self, sdk: &Sdk, state: &mut crate::state::State) -> Result<(), String> {
         let url = format!("{}/api/v1/height", sdk.masp_indexer_url);
-        let response = reqwest::get(&url)
-            .await
-            .map_err(|e| format!("Error while requesting height from masp indexer: {e}"))?;
+        let response = reqwest::get(&url).await.map_err(|e| {
+            FailureKind::Unreachable.tag(format!(
+                "Error while requesting height from masp indexer: {e}"
+            ))
+        })?;
 
         if response.status() != reqwest::StatusCode::OK {
-            return Err(format!(
+            return Err(FailureKind::Unreachable.tag(format!(
                 "Error while requesting height from masp indexer: status code was {}",
                 response.status()
-            ));
+            )));
         }
 
         let parsed = response
@@ -39,6 +43,10 @@ impl DoCheck for MaspIndexerHeightCheck {
                 current_block_height
             );
             state.last_block_height_masp_indexer = current_block_height;
+            METRICS.masp_indexer_height.set(current_block_height as i64);
+            METRICS
+                .masp_indexer_lag
+                .set(state.last_block_height.saturating_sub(current_block_height) as i64);
             Ok(())
         } else {
             Err(format!(
@@ -52,6 +60,10 @@ impl DoCheck for MaspIndexerHeightCheck {
         12
     }
 
+    fn tolerates(&self) -> Vec<FailureKind> {
+        vec![FailureKind::Unreachable]
+    }
+
     fn name(&self) -> String {
         "MaspIndexerHeightCheck".to_string()
     }
@@ -0,0 +1,226 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use namada_sdk::borsh::BorshDeserialize;
+use namada_sdk::masp::utils::{IndexedNoteEntry, MaspClient};
+use namada_sdk::masp::{IndexerMaspClient, LedgerMaspClient};
+use namada_sdk::masp_primitives::merkle_tree::CommitmentTree;
+use namada_sdk::masp_primitives::sapling::Node;
+use namada_sdk::rpc;
+use namada_sdk::storage::BlockHeight;
+use namada_sdk::token::storage_key::masp_commitment_tree_key;
+
+use crate::faults::FailureKind;
+use crate::sdk::namada::Sdk;
+
+use super::DoCheck;
+
+/// Maximum number of blocks compared with the node at each check, the rest is compared next
+const MAX_WINDOW: u64 = 50;
+
+#[derive(Clone, Debug, Default)]
+pub struct MaspIndexerContentCheck;
+
+impl DoCheck for MaspIndexerContentCheck {
+    async fn check(&self, sdk: &Sdk, state: &mut crate::state::State) -> Result<(), String> {
+        let indexer_api = reqwest::Url::parse(&format!("{}/api/v1", sdk.masp_indexer_url))
+            .map_err(|e| format!("Invalid masp indexer url: {e}"))?;
+        let indexer =
+            IndexerMaspClient::new(reqwest::Client::new(), indexer_api.clone(), false, 10);
+        let block_indexer = IndexerMaspClient::new(reqwest::Client::new(), indexer_api, true, 10);
+        let ledger = LedgerMaspClient::new(sdk.namada.client.clone(), 10, Duration::from_secs(1));
+
+        let indexer_height = indexer
+            .last_block_height()
+            .await
+            .map_err(|e| {
+                FailureKind::Unreachable.tag(format!("Failed to query masp indexer height: {e}"))
+            })?
+            .ok_or_else(|| FailureKind::Stalled.tag("Masp indexer has no block yet"))?;
+        let node_height = ledger
+            .last_block_height()
+            .await
+            .map_err(|e| FailureKind::Unreachable.tag(format!("Failed to query node height: {e}")))?
+            .ok_or("No block found".to_string())?;
+
+        // Page through the blocks from the last compared one not to skip any
+        let latest = indexer_height.0.min(node_height.0);
+        let from = state.last_masp_content_height + 1;
+        if from > latest {
+            return Ok(());
+        }
+        let to = latest.min(from + MAX_WINDOW - 1);
+        if to < latest {
+            tracing::info!(
+                "Masp indexer content is compared up to {to}, {} blocks left",
+                latest - to
+            );
+        }
+        let (from, to) = (BlockHeight(from), BlockHeight(to));
+
+        let expected = ledger
+            .fetch_shielded_transfers(from, to)
+            .await
+            .map_err(|e| {
+                FailureKind::Unreachable.tag(format!("Failed to fetch masp txs from the node: {e}"))
+            })?;
+        let indexed = indexer
+            .fetch_shielded_transfers(from, to)
+            .await
+            .map_err(|e| {
+                FailureKind::Unreachable
+                    .tag(format!("Failed to fetch masp txs from the indexer: {e}"))
+            })?;
+        compare_txs("indexer", &expected, &indexed)?;
+
+        // The block index lets wallets skip blocks, it must not skip a block with masp txs
+        let block_indexed = block_indexer
+            .fetch_shielded_transfers(from, to)
+            .await
+            .map_err(|e| {
+                FailureKind::Unreachable.tag(format!(
+                    "Failed to fetch masp txs with the block index: {e}"
+                ))
+            })?;
+        compare_txs("block index", &expected, &block_indexed)?;
+
+        let note_index = indexer.fetch_note_index(to).await.map_err(|e| {
+            FailureKind::Unreachable.tag(format!("Failed to fetch the note index at {to}: {e}"))
+        })?;
+        check_note_index(&expected, &note_index, from)?;
+
+        let indexer_tree = indexer.fetch_commitment_tree(to).await.map_err(|e| {
+            FailureKind::Unreachable
+                .tag(format!("Failed to fetch the commitment tree at {to}: {e}"))
+        })?;
+        let node_tree = node_commitment_tree(sdk, to).await?;
+        if indexer_tree.root() != node_tree.root() {
+            return Err(format!(
+                "Commitment tree root at height {to} differs: indexer {:?} (size {}), node {:?} (size {})",
+                indexer_tree.root(),
+                indexer_tree.size(),
+                node_tree.root(),
+                node_tree.size()
+            ));
+        }
+
+        tracing::info!(
+            "Masp indexer content ok for blocks {from}..={to} ({} masp txs)",
+            expected.len()
+        );
+        state.last_masp_content_height = to.0;
+
+        Ok(())
+    }
+
+    fn timing(&self) -> u32 {
+        30
+    }
+
+    fn tolerates(&self) -> Vec<FailureKind> {
+        vec![FailureKind::Unreachable, FailureKind::Stalled]
+    }
+
+    fn name(&self) -> String {
+        "MaspIndexerContentCheck".to_string()
+    }
+}
+
+/// Both lists should contain the same txs in the same order
+fn compare_txs(
+    source: &str,
+    expected: &[IndexedNoteEntry],
+    indexed: &[IndexedNoteEntry],
+) -> Result<(), String> {
+    let expected_ids: Vec<_> = expected
+        .iter()
+        .map(|(indexed_tx, tx)| (*indexed_tx, tx.txid()))
+        .collect();
+    let indexed_ids: Vec<_> = indexed
+        .iter()
+        .map(|(indexed_tx, tx)| (*indexed_tx, tx.txid()))
+        .collect();
+    if expected_ids == indexed_ids {
+        return Ok(());
+    }
+
+    let expected_set: BTreeSet<_> = expected_ids.iter().collect();
+    let indexed_set: BTreeSet<_> = indexed_ids.iter().collect();
+    let missing: Vec<_> = expected_set.difference(&indexed_set).collect();
+    let unexpected: Vec<_> = indexed_set.difference(&expected_set).collect();
+    let duplicated = indexed_ids.len() - indexed_set.len();
+
+    let mut errors = vec![];
+    if !missing.is_empty() {
+        errors.push(format!("missing {missing:?}"));
+    }
+    if !unexpected.is_empty() {
+        errors.push(format!("unexpected {unexpected:?}"));
+    }
+    if duplicated > 0 {
+        errors.push(format!("{duplicated} duplicated txs"));
+    }
+    if errors.is_empty() {
+        errors.push(format!(
+            "reordered {indexed_ids:?}, expected {expected_ids:?}"
+        ));
+    }
+    Err(format!(
+        "Masp txs from the {source} differ from the node: {}",
+        errors.join(", ")
+    ))
+}
+
+/// Every masp tx of the window should have a note position following the tx order
+fn check_note_index(
+    expected: &[IndexedNoteEntry],
+    note_index: &BTreeMap<namada_sdk::masp::utils::MaspIndexedTx, usize>,
+    from: BlockHeight,
+) -> Result<(), String> {
+    let missing: Vec<_> = expected
+        .iter()
+        .filter(|(indexed_tx, _)| !note_index.contains_key(indexed_tx))
+        .map(|(indexed_tx, _)| indexed_tx)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!(
+            "Masp txs are missing from the note index: {missing:?}"
+        ));
+    }
+
+    let expected_txs: BTreeSet<_> = expected.iter().map(|(indexed_tx, _)| indexed_tx).collect();
+    let unexpected: Vec<_> = note_index
+        .keys()
+        .filter(|indexed_tx| indexed_tx.indexed_tx.block_height >= from)
+        .filter(|indexed_tx| !expected_txs.contains(indexed_tx))
+        .collect();
+    if !unexpected.is_empty() {
+        return Err(format!("Note index has unknown masp txs: {unexpected:?}"));
+    }
+
+    let positions: Vec<_> = note_index.values().collect();
+    if positions.windows(2).any(|pair| pair[0] > pair[1]) {
+        return Err(format!(
+            "Note positions aren't in the tx order: {note_index:?}"
+        ));
+    }
+
+    Ok(())
+}
+
+async fn node_commitment_tree(
+    sdk: &Sdk,
+    height: BlockHeight,
+) -> Result<CommitmentTree<Node>, String> {
+    let (bytes, _) = rpc::query_storage_value_bytes(
+        &sdk.namada.client,
+        &masp_commitment_tree_key(),
+        Some(height),
+        false,
+    )
+    .await
+    .map_err(|e| format!("Failed to query the commitment tree at {height}: {e}"))?;
+    let bytes = bytes.ok_or(format!("No commitment tree at {height}"))?;
+    CommitmentTree::try_from_slice(&bytes)
+        .map_err(|e| format!("Failed to decode the commitment tree at {height}: {e}"))
+}
@@ -4,19 +4,36 @@ use chrono::{DateTime, Timelike, Utc};
 use enum_dispatch::enum_dispatch;
 use tokio::time::sleep;
 
+use crate::config::AppConfig;
+use crate::faults::{FailureKind, Timeline};
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
+use crate::state::State;
+use crate::verdict::{Ledger, Violation};
 
+pub mod conservation;
+pub mod consistency;
 pub mod epoch;
+pub mod governance;
 pub mod height;
 pub mod inflation;
+pub mod liveness;
 pub mod masp_indexer;
+pub mod masp_indexer_content;
+pub mod pos;
 pub mod status;
 pub mod voting_power;
 
+use conservation::ConservationCheck;
+use consistency::ConsistencyCheck;
 use epoch::EpochCheck;
+use governance::GovernanceCheck;
 use height::HeightCheck;
 use inflation::InflationCheck;
+use liveness::LivenessCheck;
 use masp_indexer::MaspIndexerHeightCheck;
+use masp_indexer_content::MaspIndexerContentCheck;
+use pos::PosCheck;
 use status::StatusCheck;
 use voting_power::VotingPowerCheck;
 
@@ -31,10 +48,23 @@ enum Checker {
     Inflation(InflationCheck),
     Status(StatusCheck),
     MaspIndexerHeight(MaspIndexerHeightCheck),
+    MaspIndexerContent(MaspIndexerContentCheck),
+    Consistency(ConsistencyCheck),
+    Conservation(ConservationCheck),
+    Pos(PosCheck),
+    Governance(GovernanceCheck),
+    Liveness(LivenessCheck),
 }
 
-pub async fn try_checks(sdk: &Sdk, state: &mut crate::state::State) {
+pub async fn try_checks(sdk: &Sdk, config: &AppConfig, state: &mut State, ledger: &mut Ledger) {
     let now = chrono::offset::Utc::now();
+    let timeline = match &config.fault_timeline {
+        Some(path) => Timeline::load(path).unwrap_or_else(|e| {
+            tracing::warn!("Failed to load the fault timeline: {e}");
+            Timeline::default()
+        }),
+        None => Timeline::default(),
+    };
 
     let check_list = vec![
         Checker::VotingPower(VotingPowerCheck),
@@ -43,10 +73,27 @@ pub async fn try_checks(sdk: &Sdk, state: &mut crate::state::State) {
         Checker::Inflation(InflationCheck),
         Checker::Status(StatusCheck),
         Checker::MaspIndexerHeight(MaspIndexerHeightCheck),
+        Checker::MaspIndexerContent(MaspIndexerContentCheck),
+        Checker::Consistency(ConsistencyCheck {
+            max_lag: config.max_node_lag,
+        }),
+        Checker::Conservation(ConservationCheck),
+        Checker::Pos(PosCheck),
+        Checker::Governance(GovernanceCheck),
+        Checker::Liveness(LivenessCheck {
+            halt_threshold_sec: config.halt_threshold_sec,
+            recovery_timeout_sec: config.recovery_timeout_sec,
+            timeline: timeline.clone(),
+        }),
     ];
     for checker in check_list {
+        if !checker.is_due(now) {
+            continue;
+        }
         let vp_check_res = checker.do_check(sdk, state, now).await;
-        is_successful(checker, vp_check_res);
+        let faults = expected_faults(&checker, &vp_check_res, &timeline, now);
+        record(&checker, &vp_check_res, &faults, ledger, state, now);
+        is_successful(checker, vp_check_res, &faults);
     }
 }
 
@@ -60,17 +107,18 @@ trait DoCheck {
         state: &mut crate::state::State,
         now: DateTime<Utc>,
     ) -> Result<(), String> {
-        if now.second().rem_euclid(self.timing()).ne(&0) {
+        if !self.is_due(now) {
             return Ok(());
         }
 
+        let max_retries = self.max_retries();
         let mut times = 0;
-        while times <= MAX_RETRY_COUNT {
+        while times <= max_retries {
             let result = self.check(sdk, state).await;
             if result.is_ok() {
                 return result;
             } else {
-                if times == MAX_RETRY_COUNT {
+                if times == max_retries {
                     tracing::error!(
                         "Check {} failed {} times, returning error",
                         self.name(),
@@ -83,7 +131,7 @@ trait DoCheck {
                     self.name(),
                     result.err().unwrap().to_string(),
                     times,
-                    MAX_RETRY_COUNT,
+                    max_retries,
                 );
                 times += 1;
                 sleep(Duration::from_secs(RETRY_INTERVAL_SEC)).await
@@ -92,17 +140,81 @@ trait DoCheck {
         Err(format!("Failed {} check (end)", self.name()))
     }
 
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        now.second().rem_euclid(self.timing()).eq(&0)
+    }
+
     fn timing(&self) -> u32;
 
+    fn max_retries(&self) -> u64 {
+        MAX_RETRY_COUNT
+    }
+
+    /// Failures of the check which are expected while a fault causing them is active
+    fn tolerates(&self) -> Vec<FailureKind> {
+        vec![]
+    }
+
     fn name(&self) -> String;
 }
 
-fn is_successful(checker: Checker, res: Result<(), String>) {
+/// Timeouts and closed connections are caused by the faults, not by the chain
+fn is_transient(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("timed out") || error.contains("connection closed before")
+}
+
+/// Faults causing the failure of the check, active at some point since the check started
+fn expected_faults(
+    checker: &Checker,
+    res: &Result<(), String>,
+    timeline: &Timeline,
+    now: DateTime<Utc>,
+) -> Vec<String> {
+    let Err(error) = res else {
+        return vec![];
+    };
+    let Some(kind) = FailureKind::of(error).filter(|kind| checker.tolerates().contains(kind))
+    else {
+        return vec![];
+    };
+    timeline
+        .causing(kind, now, Utc::now())
+        .into_iter()
+        .map(|fault| fault.label())
+        .collect()
+}
+
+fn record(
+    checker: &Checker,
+    res: &Result<(), String>,
+    faults: &[String],
+    ledger: &mut Ledger,
+    state: &State,
+    now: DateTime<Utc>,
+) {
+    match res {
+        Ok(_) => ledger.record_success(&checker.name()),
+        Err(e) if is_transient(e) => ledger.record_transient(&checker.name()),
+        Err(_) if !faults.is_empty() => ledger.record_expected(&checker.name()),
+        Err(e) => ledger.record_violation(
+            &checker.name(),
+            Violation {
+                time: now,
+                block_height: state.last_block_height,
+                error: e.clone(),
+            },
+        ),
+    }
+}
+
+fn is_successful(checker: Checker, res: Result<(), String>, faults: &[String]) {
     if let Err(ref e) = res {
         let is_timeout = e.to_lowercase().contains("timed out");
         let is_connection_closed = e.to_lowercase().contains("connection closed before");
         if is_timeout {
             tracing::warn!("Check {} has timedout", checker.name());
+            METRICS.record_outcome(&checker.name(), "transient");
             return;
         }
         if is_connection_closed {
@@ -110,10 +222,23 @@ fn is_successful(checker: Checker, res: Result<(), String>) {
                 "Check {} has failed due to connection closed before message completed",
                 checker.name()
             );
+            METRICS.record_outcome(&checker.name(), "transient");
+            return;
+        }
+        if !faults.is_empty() {
+            tracing::warn!(
+                "Check {} has failed as expected during {}: {e}",
+                checker.name(),
+                faults.join(", ")
+            );
+            METRICS.record_outcome(&checker.name(), "expected");
             return;
         }
     }
 
+    let outcome = if res.is_ok() { "success" } else { "failure" };
+    METRICS.record_outcome(&checker.name(), outcome);
+
     match checker {
         Checker::VotingPower(_) => match res {
             Ok(_) => tracing::info!("Voting power is checked"),
@@ -139,5 +264,29 @@ fn is_successful(checker: Checker, res: Result<(), String>) {
             Ok(_) => tracing::info!("Masp indexer block height increased"),
             Err(e) => tracing::error!("Masp indexer block height was not increased: {e}"),
         },
+        Checker::MaspIndexerContent(_) => match res {
+            Ok(_) => tracing::info!("Masp indexer content is checked"),
+            Err(e) => tracing::error!("Masp indexer content is wrong: {e}"),
+        },
+        Checker::Consistency(_) => match res {
+            Ok(_) => tracing::info!("Nodes are consistent"),
+            Err(e) => tracing::error!("Nodes are inconsistent: {e}"),
+        },
+        Checker::Conservation(_) => match res {
+            Ok(_) => tracing::info!("Tokens are conserved"),
+            Err(e) => tracing::error!("Tokens are not conserved: {e}"),
+        },
+        Checker::Pos(_) => match res {
+            Ok(_) => tracing::info!("PoS accounting is checked"),
+            Err(e) => tracing::error!("PoS accounting is wrong: {e}"),
+        },
+        Checker::Governance(_) => match res {
+            Ok(_) => tracing::info!("Governance proposals are checked"),
+            Err(e) => tracing::error!("Governance proposal execution is wrong: {e}"),
+        },
+        Checker::Liveness(_) => match res {
+            Ok(_) => tracing::info!("Chain is live"),
+            Err(e) => tracing::error!("Chain is not live: {e}"),
+        },
     }
 }
@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+
+use namada_sdk::address::Address;
+use namada_sdk::collections::HashMap;
+use namada_sdk::key::tm_consensus_key_raw_hash;
+use namada_sdk::proof_of_stake::types::{into_tm_voting_power, BondsAndUnbondsDetails, Slash};
+use namada_sdk::queries::RPC;
+use namada_sdk::rpc;
+use namada_sdk::token::Amount;
+use tendermint_rpc::Client;
+
+use crate::sdk::namada::Sdk;
+use crate::state::State;
+
+use super::DoCheck;
+
+#[derive(Clone, Debug, Default)]
+pub struct PosCheck;
+
+impl DoCheck for PosCheck {
+    async fn check(&self, sdk: &Sdk, state: &mut State) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let epoch = rpc::query_epoch(client)
+            .await
+            .map_err(|e| format!("Failed to query last epoch: {e}"))?;
+        // Reconcile once per epoch
+        if state.last_pos_check_epoch == Some(epoch.0) {
+            return Ok(());
+        }
+
+        let params = rpc::get_pos_params(client)
+            .await
+            .map_err(|e| format!("Failed to query PoS params: {e}"))?;
+        let bonds = rpc::bonds_and_unbonds(client, &None, &None)
+            .await
+            .map_err(|e| format!("Failed to query bonds: {e}"))?;
+        let slashes = RPC
+            .vp()
+            .pos()
+            .slashes(client)
+            .await
+            .map_err(|e| format!("Failed to query slashes: {e}"))?;
+
+        check_stake(sdk, epoch, &bonds, &slashes).await?;
+        check_slashes(&bonds, &slashes)?;
+        check_consensus_set(sdk, epoch, params.tm_votes_per_token).await?;
+
+        tracing::info!("PoS accounting ok at epoch {epoch}");
+        state.last_pos_check_epoch = Some(epoch.0);
+        Ok(())
+    }
+
+    fn timing(&self) -> u32 {
+        30
+    }
+
+    fn name(&self) -> String {
+        "PosCheck".to_string()
+    }
+}
+
+/// The stake of each validator should be the sum of its bonds net of slashes
+async fn check_stake(
+    sdk: &Sdk,
+    epoch: namada_sdk::chain::Epoch,
+    bonds: &BondsAndUnbondsDetails,
+    slashes: &HashMap<Address, Vec<Slash>>,
+) -> Result<(), String> {
+    let client = &sdk.namada.client;
+    let bonded = bonded_stake(bonds, epoch)?;
+
+    let validators = rpc::get_all_validators(client, epoch)
+        .await
+        .map_err(|e| format!("Failed to query validators: {e}"))?;
+    for validator in validators {
+        let stake = rpc::get_validator_stake(client, epoch, &validator)
+            .await
+            .map_err(|e| format!("Failed to query stake of {validator}: {e}"))?;
+        let (sum, count) = bonded.get(&validator).cloned().unwrap_or_default();
+        let is_slashed = slashes.get(&validator).is_some_and(|s| !s.is_empty());
+        reconcile_stake(stake, sum, count, is_slashed)
+            .map_err(|e| format!("Stake of {validator} at epoch {epoch} is {stake}, but {e}"))?;
+    }
+    Ok(())
+}
+
+/// Sum and count of the bonds net of slashes contributing to each validator stake at the epoch
+fn bonded_stake(
+    bonds: &BondsAndUnbondsDetails,
+    epoch: namada_sdk::chain::Epoch,
+) -> Result<BTreeMap<Address, (Amount, u64)>, String> {
+    let mut bonded = BTreeMap::<Address, (Amount, u64)>::new();
+    for (bond_id, detail) in bonds {
+        let (sum, count) = bonded.entry(bond_id.validator.clone()).or_default();
+        for bond in detail.bonds.iter().filter(|bond| bond.start <= epoch) {
+            let amount = bond
+                .amount
+                .checked_sub(bond.slashed_amount.unwrap_or_default())
+                .unwrap_or_default();
+            *sum = sum
+                .checked_add(amount)
+                .ok_or_else(|| format!("Bond overflow for {}", bond_id.validator))?;
+            *count += 1;
+        }
+    }
+    Ok(bonded)
+}
+
+fn reconcile_stake(stake: Amount, sum: Amount, count: u64, is_slashed: bool) -> Result<(), String> {
+    // Slashing each bond can be rounded
+    let tolerance = if is_slashed {
+        Amount::from_u64(count)
+    } else {
+        Amount::zero()
+    };
+    let diff = if stake >= sum {
+        stake.checked_sub(sum)
+    } else {
+        sum.checked_sub(stake)
+    }
+    .unwrap_or_default();
+    if diff > tolerance {
+        return Err(format!("the bonds sum up to {sum}"));
+    }
+    Ok(())
+}
+
+/// Slashed amounts should be reflected only when the validator has been slashed
+fn check_slashes(
+    bonds: &BondsAndUnbondsDetails,
+    slashes: &HashMap<Address, Vec<Slash>>,
+) -> Result<(), String> {
+    for (bond_id, detail) in bonds {
+        let validator_slashes = slashes.get(&bond_id.validator).cloned().unwrap_or_default();
+        for bond in &detail.bonds {
+            let slashed = bond.slashed_amount.unwrap_or_default();
+            let should_be_slashed = !bond.amount.is_zero()
+                && validator_slashes
+                    .iter()
+                    .any(|slash| slash.epoch >= bond.start && !slash.rate.is_zero());
+            if should_be_slashed && slashed.is_zero() {
+                return Err(format!(
+                    "Bond {bond_id} from epoch {} isn't slashed by {validator_slashes:?}",
+                    bond.start
+                ));
+            }
+            if validator_slashes.is_empty() && !slashed.is_zero() {
+                return Err(format!(
+                    "Bond {bond_id} from epoch {} is slashed by {slashed} without any slash",
+                    bond.start
+                ));
+            }
+        }
+        for unbond in &detail.unbonds {
+            let slashed = unbond.slashed_amount.unwrap_or_default();
+            if validator_slashes.is_empty() && !slashed.is_zero() {
+                return Err(format!(
+                    "Unbond {bond_id} withdrawable at epoch {} is slashed by {slashed} without any slash",
+                    unbond.withdraw
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The consensus validator set should be the one CometBFT uses
+async fn check_consensus_set(
+    sdk: &Sdk,
+    epoch: namada_sdk::chain::Epoch,
+    votes_per_token: namada_sdk::dec::Dec,
+) -> Result<(), String> {
+    let client = &sdk.namada.client;
+    let consensus_validators = rpc::get_all_consensus_validators(client, epoch)
+        .await
+        .map_err(|e| format!("Failed to query consensus validators: {e}"))?;
+    let mut expected = BTreeMap::new();
+    for validator in consensus_validators {
+        let power = into_tm_voting_power(votes_per_token, validator.bonded_stake);
+        if power == 0 {
+            continue;
+        }
+        let consensus_key = rpc::query_validator_consensus_keys(client, &validator.address)
+            .await
+            .map_err(|e| format!("Failed to query consensus key: {e}"))?
+            .ok_or_else(|| format!("No consensus key for {}", validator.address))?;
+        expected.insert(
+            tm_consensus_key_raw_hash(&consensus_key).to_uppercase(),
+            power as u64,
+        );
+    }
+
+    let status = client
+        .status()
+        .await
+        .map_err(|e| format!("Failed to query status: {e}"))?;
+    let actual: BTreeMap<_, _> = client
+        .validators(
+            status.sync_info.latest_block_height,
+            tendermint_rpc::Paging::All,
+        )
+        .await
+        .map_err(|e| format!("Failed to query validators: {e}"))?
+        .validators
+        .into_iter()
+        .map(|validator| {
+            (
+                validator.address.to_string().to_uppercase(),
+                validator.power(),
+            )
+        })
+        .collect();
+
+    if expected != actual {
+        return Err(format!(
+            "Consensus validator set at epoch {epoch} {expected:?} doesn't match CometBFT {actual:?}"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_sdk::address::EstablishedAddress;
+    use namada_sdk::chain::Epoch;
+    use namada_sdk::dec::Dec;
+    use namada_sdk::proof_of_stake::types::{
+        BondDetails, BondId, BondsAndUnbondsDetail, SlashType,
+    };
+
+    use super::*;
+
+    fn address(byte: u8) -> Address {
+        Address::Established(EstablishedAddress::from([byte; 20]))
+    }
+
+    fn bond(start: u64, amount: u64, slashed: Option<u64>) -> BondDetails {
+        BondDetails {
+            start: Epoch(start),
+            amount: Amount::from_u64(amount),
+            slashed_amount: slashed.map(Amount::from_u64),
+        }
+    }
+
+    fn bonds(validator: &Address, bonds: Vec<BondDetails>) -> BondsAndUnbondsDetails {
+        let mut details = BondsAndUnbondsDetails::default();
+        details.insert(
+            BondId {
+                source: address(9),
+                validator: validator.clone(),
+            },
+            BondsAndUnbondsDetail {
+                bonds,
+                unbonds: vec![],
+                slashes: vec![],
+            },
+        );
+        details
+    }
+
+    #[test]
+    fn test_bonded_stake_at_epoch() {
+        let validator = address(1);
+        let bonds = bonds(
+            &validator,
+            vec![bond(0, 100, None), bond(2, 50, Some(5)), bond(5, 70, None)],
+        );
+
+        let bonded = bonded_stake(&bonds, Epoch(3)).expect("Bonds should be summed");
+        assert_eq!(bonded[&validator], (Amount::from_u64(145), 2));
+    }
+
+    #[test]
+    fn test_reconcile_stake() {
+        let sum = Amount::from_u64(145);
+        assert!(reconcile_stake(sum, sum, 2, false).is_ok());
+        assert!(reconcile_stake(Amount::from_u64(146), sum, 2, false).is_err());
+        // each slashed bond can be rounded by one
+        assert!(reconcile_stake(Amount::from_u64(143), sum, 2, true).is_ok());
+        assert!(reconcile_stake(Amount::from_u64(142), sum, 2, true).is_err());
+    }
+
+    #[test]
+    fn test_slashed_bonds_match_slashes() {
+        let validator = address(1);
+        let slash = Slash {
+            epoch: Epoch(3),
+            block_height: 30,
+            r#type: SlashType::DuplicateVote,
+            rate: Dec::one(),
+        };
+        let mut slashes = HashMap::new();
+
+        assert!(check_slashes(&bonds(&validator, vec![bond(2, 50, None)]), &slashes).is_ok());
+        assert!(check_slashes(&bonds(&validator, vec![bond(2, 50, Some(50))]), &slashes).is_err());
+
+        slashes.insert(validator.clone(), vec![slash]);
+        assert!(check_slashes(&bonds(&validator, vec![bond(2, 50, Some(50))]), &slashes).is_ok());
+        assert!(check_slashes(&bonds(&validator, vec![bond(2, 50, None)]), &slashes).is_err());
+    }
+}
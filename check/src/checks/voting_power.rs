@@ -1,5 +1,6 @@
 use tendermint_rpc::Client;
 
+use crate::metrics::METRICS;
 use crate::sdk::namada::Sdk;
 
 use super::DoCheck;
@@ -38,6 +39,9 @@ impl DoCheck for VotingPowerCheck {
             .any(|validator| validator.power() + max_validator_vp < two_third);
 
         state.two_nodes_have_two_third = !can_halt;
+        METRICS.total_voting_power.set(total_vp as i64);
+        METRICS.can_halt.set(can_halt as i64);
+        METRICS.voting_power.reset();
 
         tracing::info!("Total vp: {}", total_vp);
         tracing::info!("Can halt: {}", can_halt);
@@ -50,6 +54,10 @@ impl DoCheck for VotingPowerCheck {
                 vp,
                 percentage_vp
             );
+            METRICS
+                .voting_power
+                .with_label_values(&[&validator.address.to_string()])
+                .set(percentage_vp as f64);
         }
         Ok(())
     }
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 #[derive(clap::Parser, Clone, Debug)]
 pub struct AppConfig {
     #[clap(long, env)]
@@ -6,4 +8,34 @@ pub struct AppConfig {
     #[clap(long, env)]
     #[arg(required = true)]
     pub masp_indexer_url: String,
+    /// Nodes to compare, e.g. `validator0=http://30.0.0.12:27658,fullnode=http://30.0.0.15:26657`
+    #[clap(long, env, value_delimiter = ',')]
+    pub nodes: Vec<String>,
+    /// Number of blocks a node can be behind the others
+    #[clap(long, env, default_value_t = 10)]
+    pub max_node_lag: u64,
+    /// Seconds without a new block to consider the chain halted
+    #[clap(long, env, default_value_t = 30)]
+    pub halt_threshold_sec: u64,
+    /// Seconds for the chain to recover after a fault expected to halt it
+    #[clap(long, env, default_value_t = 120)]
+    pub recovery_timeout_sec: u64,
+    /// Fault timeline written by the fault orchestrator, failures predicted by its faults are expected
+    #[clap(long, env)]
+    pub fault_timeline: Option<PathBuf>,
+    /// Address to serve the Prometheus metrics
+    #[clap(long, env)]
+    pub metrics_addr: Option<std::net::SocketAddr>,
+    /// Stop checking after the duration in seconds
+    #[clap(long, env)]
+    pub duration: Option<u64>,
+    /// Stop checking when the chain reaches the block height
+    #[clap(long, env)]
+    pub until_height: Option<u64>,
+    /// Directory to checkpoint the state to resume after a restart
+    #[clap(long, env)]
+    pub state_dir: Option<PathBuf>,
+    /// Path to write the final verdict
+    #[clap(long, env, default_value = "verdict.json")]
+    pub verdict: PathBuf,
 }
@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// What the checker should tolerate while a fault is active
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Expectation {
+    Halt,
+    NodeLag,
+    IbcTimeout,
+    MaspIndexerStall,
+}
+
+impl Expectation {
+    /// The failures a fault with the expectation can cause
+    pub fn failures(&self) -> &'static [FailureKind] {
+        match self {
+            Expectation::Halt => &[FailureKind::Stalled],
+            Expectation::NodeLag => &[FailureKind::Lag, FailureKind::Unreachable],
+            // The IBC transfers are checked by the workload
+            Expectation::IbcTimeout => &[],
+            Expectation::MaspIndexerStall => &[FailureKind::Stalled, FailureKind::Unreachable],
+        }
+    }
+}
+
+/// The liveness failures of the checks, any other failure is never expected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    Lag,
+    Unreachable,
+    Stalled,
+}
+
+impl FailureKind {
+    const ALL: [FailureKind; 3] = [
+        FailureKind::Lag,
+        FailureKind::Unreachable,
+        FailureKind::Stalled,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FailureKind::Lag => "lag",
+            FailureKind::Unreachable => "unreachable",
+            FailureKind::Stalled => "stalled",
+        }
+    }
+
+    /// Prefix the error of a check with the kind
+    pub fn tag(self, error: impl Display) -> String {
+        format!("[{}] {error}", self.as_str())
+    }
+
+    pub fn of(error: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| error.starts_with(&format!("[{}]", kind.as_str())))
+    }
+}
+
+/// A fault recorded by the fault orchestrator to the timeline
+#[derive(Clone, Debug, Deserialize)]
+pub struct FaultRecord {
+    pub id: u64,
+    pub fault: serde_json::Value,
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub expectations: Vec<Expectation>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl FaultRecord {
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.started_at <= end && self.ended_at.is_none_or(|ended_at| start <= ended_at)
+    }
+
+    pub fn label(&self) -> String {
+        let kind = self.fault["kind"].as_str().unwrap_or("unknown");
+        match self.fault["scenario"].as_str() {
+            Some(scenario) => format!("#{} {kind} {scenario}", self.id),
+            None => format!("#{} {kind} {}", self.id, self.targets.join(",")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Timeline {
+    pub faults: Vec<FaultRecord>,
+}
+
+impl Timeline {
+    /// No fault when the orchestrator hasn't written the timeline yet
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut faults = BTreeMap::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let record: FaultRecord = serde_json::from_str(line).map_err(|e| e.to_string())?;
+            // The record at the end replaces the one at the start
+            faults.insert(record.id, record);
+        }
+        Ok(Self {
+            faults: faults.into_values().collect(),
+        })
+    }
+
+    /// Faults expecting the behavior which were active between the start and the end
+    pub fn expecting(
+        &self,
+        expectation: Expectation,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<&FaultRecord> {
+        self.faults
+            .iter()
+            .filter(|record| record.expectations.contains(&expectation))
+            .filter(|record| record.overlaps(start, end))
+            .collect()
+    }
+
+    /// Faults which can cause the failure and were active between the start and the end
+    pub fn causing(
+        &self,
+        kind: FailureKind,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Vec<&FaultRecord> {
+        self.faults
+            .iter()
+            .filter(|record| {
+                record
+                    .expectations
+                    .iter()
+                    .any(|expectation| expectation.failures().contains(&kind))
+            })
+            .filter(|record| record.overlaps(start, end))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn record(id: u64, expectations: Vec<Expectation>, start: DateTime<Utc>) -> FaultRecord {
+        FaultRecord {
+            id,
+            fault: serde_json::json!({"kind": "partition", "scenario": "test"}),
+            targets: vec![],
+            expectations,
+            started_at: start,
+            ended_at: Some(start + Duration::seconds(60)),
+        }
+    }
+
+    #[test]
+    fn test_failure_kind_of_tagged_error() {
+        for kind in FailureKind::ALL {
+            assert_eq!(FailureKind::of(&kind.tag("error")), Some(kind));
+        }
+        assert_eq!(FailureKind::of("error [lag]"), None);
+        assert_eq!(FailureKind::of("Supply is wrong"), None);
+    }
+
+    #[test]
+    fn test_timeline_causing_failures() {
+        let now = Utc::now();
+        let timeline = Timeline {
+            faults: vec![
+                record(0, vec![Expectation::Halt, Expectation::NodeLag], now),
+                record(1, vec![Expectation::IbcTimeout], now),
+                record(
+                    2,
+                    vec![Expectation::MaspIndexerStall],
+                    now + Duration::seconds(120),
+                ),
+            ],
+        };
+        let ids = |records: Vec<&FaultRecord>| records.iter().map(|r| r.id).collect::<Vec<_>>();
+        let during = now + Duration::seconds(30);
+
+        assert_eq!(
+            ids(timeline.causing(FailureKind::Stalled, during, during)),
+            vec![0]
+        );
+        assert_eq!(
+            ids(timeline.causing(FailureKind::Unreachable, during, during)),
+            vec![0]
+        );
+        assert_eq!(
+            ids(timeline.causing(
+                FailureKind::Unreachable,
+                during,
+                now + Duration::seconds(150)
+            )),
+            vec![0, 2]
+        );
+        let after = now + Duration::seconds(90);
+        assert!(timeline.causing(FailureKind::Lag, after, after).is_empty());
+        assert_eq!(
+            ids(timeline.expecting(Expectation::IbcTimeout, during, during)),
+            vec![1]
+        );
+    }
+}
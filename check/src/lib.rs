@@ -1,4 +1,7 @@
 pub mod checks;
 pub mod config;
+pub mod faults;
+pub mod metrics;
 pub mod sdk;
 pub mod state;
+pub mod verdict;
@@ -1,13 +1,27 @@
-use std::{str::FromStr, thread, time::Duration};
+use std::{
+    path::Path,
+    str::FromStr,
+    thread,
+    time::{Duration, Instant},
+};
 
 use clap::Parser;
-use namada_chain_check::{checks::try_checks, config::AppConfig, sdk::namada::Sdk, state::State};
+use namada_chain_check::{
+    checks::try_checks,
+    config::AppConfig,
+    metrics::serve_metrics,
+    sdk::namada::{Node, Sdk},
+    state::State,
+    verdict::{Ledger, Verdict},
+};
 use namada_sdk::{io::NullIo, masp::fs::FsShieldedUtils, wallet::fs::FsWalletUtils};
 use tempfile::tempdir;
 use tendermint_rpc::{Client, HttpClient, Url};
 use tracing::level_filters::LevelFilter;
 use tracing_subscriber::EnvFilter;
 
+const CHECKPOINT_INTERVAL_SEC: u64 = 30;
+
 #[tokio::main]
 async fn main() {
     let filter = EnvFilter::builder()
@@ -25,6 +39,12 @@ async fn main() {
     let config = AppConfig::parse();
     tracing::info!("Using config: {:#?}", config);
 
+    if let Some(addr) = config.metrics_addr {
+        serve_metrics(addr)
+            .await
+            .expect("Serving metrics should succeed");
+    }
+
     let base_dir = tempdir().unwrap().path().to_path_buf();
 
     let url = Url::from_str(&config.rpc).expect("invalid RPC address");
@@ -40,7 +60,8 @@ async fn main() {
 
     let io = NullIo;
 
-    let mut state = State::from_height(2);
+    let started_at = chrono::offset::Utc::now();
+    let started = Instant::now();
 
     // Wait for the first 2 blocks
     loop {
@@ -80,19 +101,125 @@ async fn main() {
         }
     }
 
+    let nodes = config
+        .nodes
+        .iter()
+        .map(|node| {
+            let (name, rpc) = node.split_once('=').unwrap_or((node, node));
+            let url = Url::from_str(rpc).expect("invalid node RPC address");
+            Node {
+                name: name.to_string(),
+                client: HttpClient::new(url).unwrap(),
+            }
+        })
+        .collect();
+
     let sdk = Sdk::new(
         &base_dir,
         http_client.clone(),
         wallet,
         shielded_ctx,
         io,
-        config.masp_indexer_url,
+        config.masp_indexer_url.clone(),
+        nodes,
     )
     .await;
 
+    let (mut state, mut ledger) = match &config.state_dir {
+        Some(dir) => resume(&sdk, dir).await,
+        None => (State::from_height(2), Ledger::default()),
+    };
+    let mut last_checkpoint = Instant::now();
+
     loop {
-        try_checks(&sdk, &mut state).await;
+        if state.chain_id.is_none() {
+            if let Err(e) = state.identify(&sdk).await {
+                tracing::warn!("Failed to identify the chain: {e}");
+            }
+        }
+        try_checks(&sdk, &config, &mut state, &mut ledger).await;
+
+        if let Some(dir) = &config.state_dir {
+            if last_checkpoint.elapsed() >= Duration::from_secs(CHECKPOINT_INTERVAL_SEC) {
+                checkpoint(dir, &state, &ledger);
+                last_checkpoint = Instant::now();
+            }
+        }
+
+        if config
+            .duration
+            .is_some_and(|duration| started.elapsed() >= Duration::from_secs(duration))
+        {
+            tracing::info!("Reached the duration");
+            break;
+        }
+        if config
+            .until_height
+            .is_some_and(|height| state.last_block_height >= height)
+        {
+            tracing::info!("Reached the block height {}", state.last_block_height);
+            break;
+        }
 
         tokio::time::sleep(Duration::from_secs(1)).await;
     }
+
+    if let Some(dir) = &config.state_dir {
+        checkpoint(dir, &state, &ledger);
+    }
+
+    let verdict = Verdict::new(ledger, started_at, &state);
+    for halt in &verdict.liveness.halts {
+        match halt.recovery_sec {
+            Some(recovery_sec) => tracing::info!(
+                "Chain halted at height {} from {} and recovered within {recovery_sec}s",
+                halt.height,
+                halt.started_at
+            ),
+            None => tracing::warn!(
+                "Chain halted at height {} from {} and didn't recover",
+                halt.height,
+                halt.started_at
+            ),
+        }
+    }
+    if let Err(e) = verdict.save(&config.verdict) {
+        tracing::error!("Writing the verdict failed: {e}");
+    }
+    if verdict.is_successful {
+        tracing::info!("Verdict: {}", verdict.summary);
+    } else {
+        tracing::error!("Verdict: {}", verdict.summary);
+        std::process::exit(verdict.exit_code());
+    }
+}
+
+async fn resume(sdk: &Sdk, dir: &Path) -> (State, Ledger) {
+    std::fs::create_dir_all(dir).expect("Creating the state directory should succeed");
+
+    let state = match State::load(dir) {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::info!("No checkpoint to resume from ({e}), starting from scratch");
+            return (State::from_height(2), Ledger::default());
+        }
+    };
+    if let Err(e) = state.validate(sdk).await {
+        tracing::warn!("Discarding the checkpoint: {e}");
+        return (State::from_height(2), Ledger::default());
+    }
+    let ledger = Ledger::load(dir).unwrap_or_default();
+
+    tracing::info!(
+        "Resuming from block height {}, epoch {}",
+        state.last_block_height,
+        state.last_epoch
+    );
+    (state, ledger)
+}
+
+fn checkpoint(dir: &Path, state: &State, ledger: &Ledger) {
+    if let Err(e) = state.save(dir).and_then(|_| ledger.save(dir)) {
+        tracing::warn!("Checkpointing the state failed: {e}");
+    }
 }
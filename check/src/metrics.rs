@@ -0,0 +1,178 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use prometheus::{Encoder, Gauge, GaugeVec, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use tokio::net::TcpListener;
+
+/// Idle scrapes are dropped after the timeout
+const METRICS_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    pub check_outcomes: IntCounterVec,
+    pub block_height: IntGauge,
+    pub epoch: IntGauge,
+    pub masp_indexer_height: IntGauge,
+    pub masp_indexer_lag: IntGauge,
+    pub voting_power: GaugeVec,
+    pub total_voting_power: IntGauge,
+    pub can_halt: IntGauge,
+    pub total_supply: Gauge,
+    pub block_time: Gauge,
+    pub block_time_p50: Gauge,
+    pub block_time_p95: Gauge,
+    pub halted: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("check".to_string()), None)
+            .expect("Registry should be created");
+        let check_outcomes = IntCounterVec::new(
+            Opts::new("outcomes_total", "Check outcomes"),
+            &["check", "outcome"],
+        )
+        .expect("Metric should be valid");
+        let block_height =
+            IntGauge::new("block_height", "Latest block height").expect("Metric should be valid");
+        let epoch = IntGauge::new("epoch", "Latest epoch").expect("Metric should be valid");
+        let masp_indexer_height = IntGauge::new(
+            "masp_indexer_height",
+            "Latest block height of the masp indexer",
+        )
+        .expect("Metric should be valid");
+        let masp_indexer_lag = IntGauge::new(
+            "masp_indexer_lag",
+            "Blocks the masp indexer is behind the chain",
+        )
+        .expect("Metric should be valid");
+        let voting_power = GaugeVec::new(
+            Opts::new("voting_power_ratio", "Voting power ratio of each validator"),
+            &["validator"],
+        )
+        .expect("Metric should be valid");
+        let total_voting_power = IntGauge::new("total_voting_power", "Total voting power")
+            .expect("Metric should be valid");
+        let can_halt = IntGauge::new(
+            "can_halt",
+            "1 if two validators don't have 2/3 of the voting power",
+        )
+        .expect("Metric should be valid");
+        let total_supply = Gauge::new("total_supply", "Total supply of the native token")
+            .expect("Metric should be valid");
+
+        let block_time = Gauge::new("block_time_seconds", "Time between the latest blocks")
+            .expect("Metric should be valid");
+        let block_time_p50 = Gauge::new(
+            "block_time_p50_seconds",
+            "Median time between the latest blocks",
+        )
+        .expect("Metric should be valid");
+        let block_time_p95 = Gauge::new(
+            "block_time_p95_seconds",
+            "95th percentile of the time between the latest blocks",
+        )
+        .expect("Metric should be valid");
+        let halted =
+            IntGauge::new("halted", "1 if the chain is halted").expect("Metric should be valid");
+
+        registry
+            .register(Box::new(check_outcomes.clone()))
+            .and_then(|_| registry.register(Box::new(block_height.clone())))
+            .and_then(|_| registry.register(Box::new(epoch.clone())))
+            .and_then(|_| registry.register(Box::new(masp_indexer_height.clone())))
+            .and_then(|_| registry.register(Box::new(masp_indexer_lag.clone())))
+            .and_then(|_| registry.register(Box::new(voting_power.clone())))
+            .and_then(|_| registry.register(Box::new(total_voting_power.clone())))
+            .and_then(|_| registry.register(Box::new(can_halt.clone())))
+            .and_then(|_| registry.register(Box::new(total_supply.clone())))
+            .and_then(|_| registry.register(Box::new(block_time.clone())))
+            .and_then(|_| registry.register(Box::new(block_time_p50.clone())))
+            .and_then(|_| registry.register(Box::new(block_time_p95.clone())))
+            .and_then(|_| registry.register(Box::new(halted.clone())))
+            .expect("Metrics should be registered");
+
+        Self {
+            registry,
+            check_outcomes,
+            block_height,
+            epoch,
+            masp_indexer_height,
+            masp_indexer_lag,
+            voting_power,
+            total_voting_power,
+            can_halt,
+            total_supply,
+            block_time,
+            block_time_p50,
+            block_time_p95,
+            halted,
+        }
+    }
+
+    pub fn record_outcome(&self, check: &str, outcome: &str) {
+        self.check_outcomes
+            .with_label_values(&[check, outcome])
+            .inc();
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buffer = vec![];
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Metrics should be encoded");
+        buffer
+    }
+}
+
+/// Serve the metrics at `/metrics` on the address in the background
+pub async fn serve_metrics(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Serving metrics on http://{addr}/metrics");
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!("Accepting a metrics connection failed: {e}");
+                    continue;
+                }
+            };
+            // each connection is served separately not to block the other scrapes
+            tokio::spawn(async move {
+                let connection = http1::Builder::new()
+                    .timer(TokioTimer::new())
+                    .header_read_timeout(METRICS_READ_TIMEOUT)
+                    .serve_connection(TokioIo::new(stream), service_fn(respond));
+                if let Err(e) = connection.await {
+                    tracing::warn!("Serving metrics failed: {e}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn respond(request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let response = if request.uri().path() == "/metrics" {
+        Response::builder()
+            .header(CONTENT_TYPE, prometheus::TEXT_FORMAT)
+            .body(Full::new(Bytes::from(METRICS.encode())))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::from_static(b"Not Found")))
+    };
+    Ok(response.expect("Response should be valid"))
+}
@@ -8,10 +8,16 @@ use namada_sdk::{
 };
 use tendermint_rpc::HttpClient;
 
+pub struct Node {
+    pub name: String,
+    pub client: HttpClient,
+}
+
 pub struct Sdk {
     pub base_dir: PathBuf,
     pub namada: NamadaImpl<HttpClient, FsWalletUtils, FsShieldedUtils, NullIo>,
     pub masp_indexer_url: String,
+    pub nodes: Vec<Node>,
 }
 
 impl Sdk {
@@ -22,6 +28,7 @@ impl Sdk {
         shielded_ctx: ShieldedWallet<FsShieldedUtils>,
         io: NullIo,
         masp_indexer_url: String,
+        nodes: Vec<Node>,
     ) -> Sdk {
         let namada = NamadaImpl::new(http_client, wallet, shielded_ctx, io)
             .await
@@ -31,6 +38,7 @@ impl Sdk {
             base_dir: base_dir.to_owned(),
             namada,
             masp_indexer_url,
+            nodes,
         }
     }
 }
@@ -1,7 +1,64 @@
-use namada_sdk::token;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
 
-#[derive(Clone, Debug, Default)]
+use chrono::{DateTime, Utc};
+use namada_sdk::address::Address;
+use namada_sdk::{rpc, token};
+use serde::{Deserialize, Serialize};
+use tendermint_rpc::Client;
+
+use crate::sdk::namada::Sdk;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum ProposalStage {
+    Pending,
+    Voting,
+    Ended,
+    Executed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TrackedProposal {
+    pub activation_epoch: u64,
+    pub funds: token::Amount,
+    pub stage: ProposalStage,
+    /// The deposit is refunded to the author when the proposal passes
+    #[serde(default)]
+    pub author: Option<Address>,
+}
+
+/// Number of the latest block times kept to compute the block time percentiles
+pub const MAX_BLOCK_TIMES: usize = 1000;
+
+/// A period without a new block longer than the halt threshold
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Halt {
+    pub height: u64,
+    pub started_at: DateTime<Utc>,
+    pub detected_at: DateTime<Utc>,
+    pub two_nodes_have_two_third: bool,
+    pub recovered_at: Option<DateTime<Utc>>,
+    pub recovery_sec: Option<i64>,
+    /// Faults expected to halt the chain
+    #[serde(default)]
+    pub faults: Vec<String>,
+    #[serde(default)]
+    pub faults_ended_at: Option<DateTime<Utc>>,
+    /// Seconds to recover after the end of the faults
+    #[serde(default)]
+    pub recovery_after_fault_sec: Option<i64>,
+    /// The halt is reported as a violation only once
+    #[serde(default)]
+    pub reported: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct State {
+    /// Chain the checkpoint was taken on
+    #[serde(default)]
+    pub chain_id: Option<String>,
+    #[serde(default)]
+    pub genesis_hash: Option<String>,
     pub last_block_height: u64,
     pub last_block_height_masp_indexer: u64,
     pub last_epoch: u64,
@@ -9,11 +66,31 @@ pub struct State {
     pub two_nodes_have_two_third: bool,
     pub last_proposal_id: Option<u64>,
     pub on_going_proposals: Vec<u64>,
+    #[serde(default)]
+    pub last_native_supply: Option<(u64, token::Amount)>,
+    #[serde(default)]
+    pub last_masp_total_rewards: Option<token::Amount>,
+    #[serde(default)]
+    pub last_native_governance: Option<token::Amount>,
+    #[serde(default)]
+    pub last_pos_check_epoch: Option<u64>,
+    #[serde(default)]
+    pub last_tracked_proposal_id: Option<u64>,
+    #[serde(default)]
+    pub tracked_proposals: BTreeMap<u64, TrackedProposal>,
+    #[serde(default)]
+    pub last_masp_content_height: u64,
+    #[serde(default)]
+    pub block_times: VecDeque<(u64, DateTime<Utc>)>,
+    #[serde(default)]
+    pub halts: Vec<Halt>,
 }
 
 impl State {
     pub fn from_height(height: u64) -> Self {
         Self {
+            chain_id: None,
+            genesis_hash: None,
             last_block_height: height,
             last_block_height_masp_indexer: 0,
             last_epoch: 0,
@@ -21,6 +98,144 @@ impl State {
             two_nodes_have_two_third: true,
             last_proposal_id: None,
             on_going_proposals: Default::default(),
+            last_native_supply: None,
+            last_masp_total_rewards: None,
+            last_native_governance: None,
+            last_pos_check_epoch: None,
+            last_tracked_proposal_id: None,
+            tracked_proposals: Default::default(),
+            last_masp_content_height: 0,
+            block_times: Default::default(),
+            halts: Default::default(),
         }
     }
+
+    /// Sorted times between the consecutive blocks among the latest `window` blocks
+    pub fn sorted_block_times(&self, window: usize) -> Vec<f64> {
+        let skip = self.block_times.len().saturating_sub(window);
+        let mut block_times: Vec<_> = self
+            .block_times
+            .iter()
+            .skip(skip)
+            .zip(self.block_times.iter().skip(skip + 1))
+            .filter(|((height, _), (next_height, _))| height + 1 == *next_height)
+            .map(|((_, time), (_, next_time))| {
+                (*next_time - *time).num_milliseconds() as f64 / 1000.0
+            })
+            .collect();
+        block_times.sort_by(f64::total_cmp);
+        block_times
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        save_json(&dir.join("state.json"), self)
+    }
+
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        load_json(&dir.join("state.json"))
+    }
+
+    /// Record the chain the state belongs to
+    pub async fn identify(&mut self, sdk: &Sdk) -> Result<(), String> {
+        let (chain_id, genesis_hash) = query_chain_identity(sdk).await?;
+        self.chain_id = Some(chain_id);
+        self.genesis_hash = Some(genesis_hash);
+        Ok(())
+    }
+
+    /// Check the checkpoint doesn't come from another chain or from the future
+    pub async fn validate(&self, sdk: &Sdk) -> Result<(), String> {
+        let client = &sdk.namada.client;
+        let (chain_id, genesis_hash) = query_chain_identity(sdk).await?;
+        if self.chain_id.as_ref() != Some(&chain_id) {
+            return Err(format!(
+                "Chain ID of the checkpoint {:?} is not the chain {chain_id}",
+                self.chain_id
+            ));
+        }
+        if self.genesis_hash.as_ref() != Some(&genesis_hash) {
+            return Err(format!(
+                "First block hash of the checkpoint {:?} differs from the chain {genesis_hash}",
+                self.genesis_hash
+            ));
+        }
+
+        let block = rpc::query_block(client)
+            .await
+            .map_err(|e| format!("Failed to query last block: {e}"))?
+            .ok_or("No block found".to_string())?;
+        let height: u64 = block.height.into();
+        if self.last_block_height > height {
+            return Err(format!(
+                "Block height of the checkpoint {} is ahead of the chain {height}",
+                self.last_block_height
+            ));
+        }
+
+        let epoch: u64 = rpc::query_epoch(client)
+            .await
+            .map_err(|e| format!("Failed to query last epoch: {e}"))?
+            .into();
+        if self.last_epoch > epoch {
+            return Err(format!(
+                "Epoch of the checkpoint {} is ahead of the chain {epoch}",
+                self.last_epoch
+            ));
+        }
+
+        for proposal_id in self
+            .last_proposal_id
+            .iter()
+            .chain(&self.on_going_proposals)
+            .chain(&self.last_tracked_proposal_id)
+            .chain(self.tracked_proposals.keys())
+        {
+            if rpc::query_proposal_by_id(client, *proposal_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .is_none()
+            {
+                return Err(format!("Proposal {proposal_id} doesn't exist on the chain"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Chain ID and hash of the first block
+async fn query_chain_identity(sdk: &Sdk) -> Result<(String, String), String> {
+    let client = &sdk.namada.client;
+    let chain_id = client
+        .status()
+        .await
+        .map_err(|e| format!("Failed to query status: {e}"))?
+        .node_info
+        .network
+        .to_string();
+    let genesis_hash = client
+        .block(1u32)
+        .await
+        .map_err(|e| format!("Failed to query the first block: {e}"))?
+        .block_id
+        .hash
+        .to_string();
+    Ok((chain_id, genesis_hash))
+}
+
+pub fn percentile(sorted: &[f64], p: usize) -> Option<f64> {
+    (!sorted.is_empty()).then(|| sorted[(sorted.len() * p / 100).min(sorted.len() - 1)])
+}
+
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    // Write to a temporary file first not to leave a broken checkpoint on a crash
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json).map_err(|e| e.to_string())?;
+    std::fs::rename(tmp_path, path).map_err(|e| e.to_string())
+}
+
+pub fn load_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
 }
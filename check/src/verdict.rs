@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::state::{load_json, percentile, save_json, Halt, State, MAX_BLOCK_TIMES};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Violation {
+    pub time: DateTime<Utc>,
+    pub block_height: u64,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CheckRecord {
+    pub success: u64,
+    pub transient: u64,
+    /// Failures predicted by an active fault
+    #[serde(default)]
+    pub expected: u64,
+    pub violations: Vec<Violation>,
+}
+
+/// Outcomes of every check run, grouped by the checker
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Ledger {
+    pub checks: BTreeMap<String, CheckRecord>,
+}
+
+impl Ledger {
+    pub fn record_success(&mut self, check: &str) {
+        self.record(check).success += 1;
+    }
+
+    pub fn record_transient(&mut self, check: &str) {
+        self.record(check).transient += 1;
+    }
+
+    pub fn record_expected(&mut self, check: &str) {
+        self.record(check).expected += 1;
+    }
+
+    pub fn record_violation(&mut self, check: &str, violation: Violation) {
+        self.record(check).violations.push(violation);
+    }
+
+    pub fn violation_count(&self) -> usize {
+        self.checks
+            .values()
+            .map(|record| record.violations.len())
+            .sum()
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), String> {
+        save_json(&dir.join("ledger.json"), self)
+    }
+
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        load_json(&dir.join("ledger.json"))
+    }
+
+    fn record(&mut self, check: &str) -> &mut CheckRecord {
+        self.checks.entry(check.to_string()).or_default()
+    }
+}
+
+/// Block times and halts to state how long the chain took to recover
+#[derive(Clone, Debug, Serialize)]
+pub struct LivenessReport {
+    pub blocks: usize,
+    pub block_time_p50_sec: Option<f64>,
+    pub block_time_p90_sec: Option<f64>,
+    pub block_time_p99_sec: Option<f64>,
+    pub block_time_max_sec: Option<f64>,
+    pub halts: Vec<Halt>,
+}
+
+impl LivenessReport {
+    pub fn new(state: &State) -> Self {
+        let block_times = state.sorted_block_times(MAX_BLOCK_TIMES);
+        let percentile = |p| percentile(&block_times, p);
+
+        Self {
+            blocks: state.block_times.len(),
+            block_time_p50_sec: percentile(50),
+            block_time_p90_sec: percentile(90),
+            block_time_p99_sec: percentile(99),
+            block_time_max_sec: block_times.last().cloned(),
+            halts: state.halts.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct Verdict {
+    pub is_successful: bool,
+    pub summary: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub last_block_height: u64,
+    pub last_epoch: u64,
+    pub liveness: LivenessReport,
+    pub ledger: Ledger,
+}
+
+impl Verdict {
+    pub fn new(ledger: Ledger, started_at: DateTime<Utc>, state: &State) -> Self {
+        let violations = ledger.violation_count();
+        let summary = if violations == 0 {
+            "No invariant violation".to_string()
+        } else {
+            let checks: Vec<_> = ledger
+                .checks
+                .iter()
+                .filter(|(_, record)| !record.violations.is_empty())
+                .map(|(check, record)| format!("{check} ({})", record.violations.len()))
+                .collect();
+            format!("{violations} invariant violations: {}", checks.join(", "))
+        };
+
+        Self {
+            is_successful: violations == 0,
+            summary,
+            started_at,
+            finished_at: Utc::now(),
+            last_block_height: state.last_block_height,
+            last_epoch: state.last_epoch,
+            liveness: LivenessReport::new(state),
+            ledger,
+        }
+    }
+
+    /// Transient and expected failures don't fail the run, only the violations
+    pub fn exit_code(&self) -> i32 {
+        if self.is_successful {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Verdict should be convertible");
+        std::fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn violation(error: &str) -> Violation {
+        Violation {
+            time: Utc::now(),
+            block_height: 10,
+            error: error.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verdict_without_violation() {
+        let mut ledger = Ledger::default();
+        ledger.record_success("HeightCheck");
+        ledger.record_transient("HeightCheck");
+        ledger.record_expected("ConsistencyCheck");
+
+        let verdict = Verdict::new(ledger, Utc::now(), &State::from_height(10));
+        assert!(verdict.is_successful);
+        assert_eq!(verdict.exit_code(), 0);
+        assert_eq!(verdict.summary, "No invariant violation");
+    }
+
+    #[test]
+    fn test_verdict_with_violations() {
+        let mut ledger = Ledger::default();
+        ledger.record_success("HeightCheck");
+        ledger.record_violation("PosCheck", violation("Stake mismatch"));
+        ledger.record_violation("PosCheck", violation("Stake mismatch"));
+        ledger.record_violation("ConservationCheck", violation("Supply mismatch"));
+
+        let verdict = Verdict::new(ledger, Utc::now(), &State::from_height(10));
+        assert!(!verdict.is_successful);
+        assert_eq!(verdict.exit_code(), 1);
+        assert_eq!(
+            verdict.summary,
+            "3 invariant violations: ConservationCheck (1), PosCheck (2)"
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt::{self, Display};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+mod partition;
+mod runner;
+mod schedule;
+mod timeline;
+
+pub use partition::*;
+pub use runner::*;
+pub use schedule::*;
+pub use timeline::*;
+
+#[derive(Error, Debug)]
+pub enum FaultError {
+    #[error("Schedule error: `{0}`")]
+    Schedule(String),
+    #[error("Command `{0}` failed: `{1}`")]
+    Command(String, String),
+    #[error("Timeline error: `{0}`")]
+    Timeline(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Fault {
+    Kill,
+    Pause,
+    Delay {
+        #[serde(default = "default_delay_ms")]
+        time_ms: u64,
+    },
+    Loss {
+        #[serde(default = "default_loss_percent")]
+        percent: u64,
+    },
+    Rate {
+        #[serde(default = "default_rate")]
+        rate: String,
+    },
+    Duplicate {
+        #[serde(default = "default_duplicate_percent")]
+        percent: u64,
+    },
+    Corrupt {
+        #[serde(default = "default_corrupt_percent")]
+        percent: u64,
+    },
+    /// A partition scenario of the schedule, it's injected into the services of the scenario
+    Partition {
+        scenario: String,
+    },
+}
+
+fn default_delay_ms() -> u64 {
+    1000
+}
+
+fn default_loss_percent() -> u64 {
+    30
+}
+
+fn default_rate() -> String {
+    "128kbit".to_string()
+}
+
+fn default_duplicate_percent() -> u64 {
+    10
+}
+
+fn default_corrupt_percent() -> u64 {
+    5
+}
+
+impl Fault {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Kill,
+            Self::Pause,
+            Self::Delay {
+                time_ms: default_delay_ms(),
+            },
+            Self::Loss {
+                percent: default_loss_percent(),
+            },
+            Self::Rate {
+                rate: default_rate(),
+            },
+            Self::Duplicate {
+                percent: default_duplicate_percent(),
+            },
+            Self::Corrupt {
+                percent: default_corrupt_percent(),
+            },
+        ]
+    }
+
+    /// Commands injecting the fault into the targets for the duration
+    pub fn actions(
+        &self,
+        targets: &[String],
+        duration: Duration,
+        schedule: &FaultSchedule,
+    ) -> Result<Vec<Action>, FaultError> {
+        let duration_arg = format!("{}s", duration.as_secs());
+        let netem = |command: &[&str]| {
+            let args = ["netem", "--duration", &duration_arg]
+                .iter()
+                .chain(command)
+                .map(|arg| arg.to_string())
+                .chain(targets.iter().cloned())
+                .collect();
+            vec![Action::Run {
+                program: Program::Pumba,
+                args,
+                blocks_for: duration,
+            }]
+        };
+
+        let actions = match self {
+            // Containers couldn't restart when `pumba kill`
+            Self::Kill => vec![
+                Action::Run {
+                    program: Program::Pumba,
+                    args: std::iter::once("kill".to_string())
+                        .chain(targets.iter().cloned())
+                        .collect(),
+                    blocks_for: Duration::ZERO,
+                },
+                Action::Sleep(duration),
+                Action::Run {
+                    program: Program::Docker,
+                    args: std::iter::once("restart".to_string())
+                        .chain(targets.iter().cloned())
+                        .collect(),
+                    blocks_for: Duration::ZERO,
+                },
+            ],
+            Self::Pause => vec![Action::Run {
+                program: Program::Pumba,
+                args: ["pause", "--duration", &duration_arg]
+                    .iter()
+                    .map(|arg| arg.to_string())
+                    .chain(targets.iter().cloned())
+                    .collect(),
+                blocks_for: duration,
+            }],
+            Self::Delay { time_ms } => netem(&["delay", "--time", &time_ms.to_string()]),
+            Self::Loss { percent } => netem(&["loss", "--percent", &percent.to_string()]),
+            Self::Rate { rate } => netem(&["rate", "--rate", rate]),
+            Self::Duplicate { percent } => netem(&["duplicate", "--percent", &percent.to_string()]),
+            Self::Corrupt { percent } => netem(&["corrupt", "--percent", &percent.to_string()]),
+            Self::Partition { scenario } => schedule
+                .scenario(scenario)?
+                .actions(&schedule.hosts, duration)?,
+        };
+        Ok(actions)
+    }
+}
+
+impl Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Kill => write!(f, "kill"),
+            Self::Pause => write!(f, "pause"),
+            Self::Delay { time_ms } => write!(f, "delay {time_ms}ms"),
+            Self::Loss { percent } => write!(f, "loss {percent}%"),
+            Self::Rate { rate } => write!(f, "rate {rate}"),
+            Self::Duplicate { percent } => write!(f, "duplicate {percent}%"),
+            Self::Corrupt { percent } => write!(f, "corrupt {percent}%"),
+            Self::Partition { scenario } => write!(f, "partition {scenario}"),
+        }
+    }
+}
+
+/// Inject the faults of the schedule and record them to the timeline
+pub struct Orchestrator<R: Runner> {
+    schedule: FaultSchedule,
+    runner: R,
+    timeline_path: PathBuf,
+    next_id: u64,
+    rng: SmallRng,
+}
+
+impl<R: Runner> Orchestrator<R> {
+    pub fn new(schedule: FaultSchedule, runner: R, timeline_path: PathBuf, seed: u64) -> Self {
+        Self {
+            schedule,
+            runner,
+            timeline_path,
+            next_id: 0,
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn runner(&self) -> &R {
+        &self.runner
+    }
+
+    /// Run until the schedule is exhausted or `max_faults` faults have been injected
+    pub fn run(&mut self, max_faults: Option<u64>) -> Result<(), FaultError> {
+        while max_faults.is_none_or(|max| self.next_id < max) {
+            let Some(planned) = self.schedule.next_fault(self.next_id, &mut self.rng) else {
+                tracing::info!("The fault schedule is exhausted");
+                break;
+            };
+
+            if !planned.wait.is_zero() {
+                tracing::info!("Waiting {}s before the next fault", planned.wait.as_secs());
+                self.runner.sleep(planned.wait);
+            }
+            self.inject(planned)?;
+        }
+        Ok(())
+    }
+
+    fn inject(&mut self, planned: PlannedFault) -> Result<(), FaultError> {
+        let mut record = FaultRecord {
+            id: self.next_id,
+            fault: planned.fault.clone(),
+            targets: planned.targets.clone(),
+            duration_sec: planned.duration.as_secs(),
+            expectations: self.schedule.expectations(&planned.fault),
+            started_at: self.runner.now(),
+            ended_at: None,
+            error: None,
+        };
+        self.next_id += 1;
+        tracing::info!(
+            "Injecting fault '{}' into {:?} for {}s",
+            record.fault,
+            record.targets,
+            record.duration_sec
+        );
+        append_timeline(&self.timeline_path, &record)?;
+
+        let actions = planned
+            .fault
+            .actions(&planned.targets, planned.duration, &self.schedule)?;
+        for action in actions {
+            let result = match action {
+                Action::Run {
+                    program,
+                    args,
+                    blocks_for,
+                } => self.runner.run(program, &args, blocks_for),
+                Action::RunAll {
+                    commands,
+                    blocks_for,
+                } => self.runner.run_all(&commands, blocks_for),
+                Action::Sleep(duration) => {
+                    self.runner.sleep(duration);
+                    Ok(())
+                }
+            };
+            // Keep going to restart the killed containers
+            if let Err(e) = result {
+                tracing::warn!("Injecting fault {} failed: {e}", record.id);
+                record.error = Some(e.to_string());
+            }
+        }
+
+        record.ended_at = Some(self.runner.now());
+        append_timeline(&self.timeline_path, &record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::*;
+
+    fn plan(schedule: &FaultSchedule, seed: u64) -> Vec<PlannedFault> {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        (0..20)
+            .map(|index| {
+                schedule
+                    .next_fault(index, &mut rng)
+                    .expect("Random schedule should be endless")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_seed_gives_same_schedule() {
+        let schedule = FaultSchedule::default();
+        let planned = plan(&schedule, 42);
+
+        assert_eq!(planned, plan(&schedule, 42));
+        assert_ne!(planned, plan(&schedule, 43));
+        assert!(planned[0].wait.is_zero());
+        for fault in &planned[1..] {
+            assert!((61..=180).contains(&fault.wait.as_secs()));
+        }
+    }
+
+    #[test]
+    fn test_dry_run_records_timeline() {
+        let run = |name: &str| {
+            let path = std::env::temp_dir().join(format!(
+                "fault_timeline_{name}_{}.jsonl",
+                std::process::id()
+            ));
+            let _ = std::fs::remove_file(&path);
+            let mut orchestrator = Orchestrator::new(
+                FaultSchedule::default(),
+                DryRunner::new(Utc::now()),
+                path.clone(),
+                42,
+            );
+            orchestrator.run(Some(5)).expect("Dry run should succeed");
+            let timeline = Timeline::load(&path).expect("Timeline should be written");
+            std::fs::remove_file(&path).expect("Timeline should be removed");
+            (timeline, orchestrator.runner().commands.clone())
+        };
+        let (timeline, commands) = run("first");
+
+        assert_eq!(timeline.faults.len(), 5);
+        for (id, record) in timeline.faults.iter().enumerate() {
+            assert_eq!(record.id, id as u64);
+            assert!(record.error.is_none());
+            let ended_at = record.ended_at.expect("Fault should have ended");
+            assert!(ended_at >= record.started_at);
+        }
+        for (prev, next) in timeline.faults.iter().zip(&timeline.faults[1..]) {
+            assert!(prev.ended_at.expect("Fault should have ended") <= next.started_at);
+        }
+
+        let (replayed, replayed_commands) = run("second");
+        assert_eq!(commands, replayed_commands);
+        let faults = |timeline: &Timeline| {
+            timeline
+                .faults
+                .iter()
+                .map(|record| (record.fault.clone(), record.targets.clone()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(faults(&timeline), faults(&replayed));
+    }
+
+    #[test]
+    fn test_builtin_scenario_expectations() {
+        let expectations: BTreeMap<_, _> = PartitionScenario::builtins()
+            .into_iter()
+            .map(|scenario| (scenario.name, scenario.expectations))
+            .collect();
+
+        assert_eq!(
+            expectations["split-validators"],
+            vec![Expectation::Halt, Expectation::NodeLag]
+        );
+        assert_eq!(
+            expectations["isolate-hermes"],
+            vec![Expectation::IbcTimeout]
+        );
+        assert_eq!(
+            expectations["isolate-masp-indexer"],
+            vec![Expectation::MaspIndexerStall]
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use namada_chain_fault::{CommandRunner, DryRunner, FaultSchedule, Orchestrator};
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
+
+#[derive(clap::Parser, Clone, Debug)]
+struct Args {
+    #[clap(long, env)]
+    #[arg(required = true)]
+    seed: u64,
+    /// Fault schedule in TOML, the faults of the former pumba loop are injected without it
+    #[clap(long, env)]
+    schedule: Option<PathBuf>,
+    /// File to record the injected faults to
+    #[clap(long, env, default_value = "fault_timeline.jsonl")]
+    timeline: PathBuf,
+    /// Wait for the file to exist before injecting faults
+    #[clap(long, env)]
+    wait_for: Option<PathBuf>,
+    /// Stop after injecting the number of faults
+    #[clap(long, env)]
+    max_faults: Option<u64>,
+    /// Print the commands instead of running them
+    #[clap(long, env)]
+    #[clap(default_value_t = false)]
+    dry_run: bool,
+    #[clap(long, env, default_value = "pumba")]
+    pumba_bin: String,
+    #[clap(long, env, default_value = "docker")]
+    docker_bin: String,
+}
+
+fn main() {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env()
+        .unwrap();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .compact()
+        .with_ansi(false)
+        .init();
+
+    let args = Args::parse();
+    tracing::info!("Using args: {args:#?}");
+
+    let schedule = match &args.schedule {
+        Some(path) => FaultSchedule::load(path),
+        None => Ok(FaultSchedule::default()),
+    };
+    let schedule = match schedule {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            tracing::error!("Loading the fault schedule failed: {e}");
+            std::process::exit(4);
+        }
+    };
+
+    if let Some(path) = &args.wait_for {
+        while !path.exists() {
+            tracing::info!("Waiting for {}...", path.display());
+            std::thread::sleep(Duration::from_secs(5));
+        }
+    }
+
+    let result = if args.dry_run {
+        Orchestrator::new(
+            schedule,
+            DryRunner::new(chrono::Utc::now()),
+            args.timeline,
+            args.seed,
+        )
+        .run(args.max_faults)
+    } else {
+        let runner = CommandRunner {
+            pumba: args.pumba_bin,
+            docker: args.docker_bin,
+        };
+        Orchestrator::new(schedule, runner, args.timeline, args.seed).run(args.max_faults)
+    };
+    if let Err(e) = result {
+        tracing::error!("Injecting faults failed: {e}");
+        std::process::exit(1);
+    }
+}
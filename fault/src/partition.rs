@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Action, FaultError, Program};
+
+/// Addresses of the services in `config/docker-compose.yml`
+const COMPOSE_HOSTS: [(&str, &str); 12] = [
+    ("validator0", "30.0.0.12"),
+    ("validator1", "30.0.0.13"),
+    ("validator2", "30.0.0.14"),
+    ("fullnode", "30.0.0.15"),
+    ("workload", "30.0.0.16"),
+    ("check", "30.0.0.19"),
+    ("masp-webserver", "30.0.0.20"),
+    ("postgres", "30.0.0.21"),
+    ("masp-chain", "30.0.0.22"),
+    ("masp-block-filter", "30.0.0.23"),
+    ("hermes", "30.0.0.30"),
+    ("gaia", "30.0.0.31"),
+];
+
+/// What the checker should tolerate while the fault is active
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Expectation {
+    /// The chain can halt, it should recover after the fault
+    Halt,
+    /// Nodes can be behind the others
+    NodeLag,
+    /// IBC packets can't be relayed and the transfers time out
+    IbcTimeout,
+    /// The masp indexer can stop following the chain
+    MaspIndexerStall,
+}
+
+/// The services in different groups can't reach each other
+#[derive(Clone, Debug, Deserialize)]
+pub struct PartitionScenario {
+    pub name: String,
+    pub groups: Vec<Vec<String>>,
+    #[serde(default)]
+    pub expectations: Vec<Expectation>,
+}
+
+impl PartitionScenario {
+    pub fn builtins() -> Vec<Self> {
+        let group = |services: &[&str]| services.iter().map(|s| s.to_string()).collect();
+        vec![
+            Self {
+                name: "split-validators".to_string(),
+                groups: vec![group(&["validator2"]), group(&["validator0", "validator1"])],
+                // Neither side keeps more than 2/3 of the voting power
+                expectations: vec![Expectation::Halt, Expectation::NodeLag],
+            },
+            Self {
+                name: "isolate-hermes".to_string(),
+                groups: vec![group(&["hermes"]), group(&["gaia"])],
+                expectations: vec![Expectation::IbcTimeout],
+            },
+            Self {
+                name: "isolate-masp-indexer".to_string(),
+                groups: vec![
+                    group(&["masp-chain", "masp-webserver", "masp-block-filter"]),
+                    group(&["fullnode"]),
+                ],
+                expectations: vec![Expectation::MaspIndexerStall],
+            },
+        ]
+    }
+
+    pub fn services(&self) -> Vec<String> {
+        self.groups.iter().flatten().cloned().collect()
+    }
+
+    /// Every group drops the packets to the other groups for the duration
+    pub fn actions(
+        &self,
+        hosts: &BTreeMap<String, String>,
+        duration: Duration,
+    ) -> Result<Vec<Action>, FaultError> {
+        let mut commands = vec![];
+        for (i, group) in self.groups.iter().enumerate() {
+            let mut args = vec!["netem".to_string(), "--duration".to_string()];
+            args.push(format!("{}s", duration.as_secs()));
+            for service in self
+                .groups
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .flat_map(|(_, other)| other)
+            {
+                let host = hosts.get(service).ok_or_else(|| {
+                    FaultError::Schedule(format!("No host for {service} in {}", self.name))
+                })?;
+                args.push("--target".to_string());
+                args.push(host.clone());
+            }
+            args.extend(["loss", "--percent", "100"].map(String::from));
+            args.extend(group.iter().cloned());
+            commands.push((Program::Pumba, args));
+        }
+
+        Ok(vec![Action::RunAll {
+            commands,
+            blocks_for: duration,
+        }])
+    }
+}
+
+pub fn compose_hosts() -> BTreeMap<String, String> {
+    COMPOSE_HOSTS
+        .iter()
+        .map(|(service, host)| (service.to_string(), host.to_string()))
+        .collect()
+}
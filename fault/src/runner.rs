@@ -0,0 +1,163 @@
+use std::process::Command;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::FaultError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Program {
+    Pumba,
+    Docker,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    Run {
+        program: Program,
+        args: Vec<String>,
+        /// How long the command takes to return, e.g. `pumba pause --duration`
+        blocks_for: Duration,
+    },
+    /// Run the commands concurrently, e.g. both sides of a partition
+    RunAll {
+        commands: Vec<(Program, Vec<String>)>,
+        blocks_for: Duration,
+    },
+    Sleep(Duration),
+}
+
+/// Executes the fault actions, the docker stand-in can replace it to run offline
+pub trait Runner {
+    fn run(
+        &mut self,
+        program: Program,
+        args: &[String],
+        blocks_for: Duration,
+    ) -> Result<(), FaultError>;
+
+    fn run_all(
+        &mut self,
+        commands: &[(Program, Vec<String>)],
+        blocks_for: Duration,
+    ) -> Result<(), FaultError>;
+
+    fn sleep(&mut self, duration: Duration);
+
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// Run pumba and docker as child processes
+pub struct CommandRunner {
+    pub pumba: String,
+    pub docker: String,
+}
+
+impl CommandRunner {
+    fn command(&self, program: Program, args: &[String]) -> (Command, String) {
+        let program = match program {
+            Program::Pumba => &self.pumba,
+            Program::Docker => &self.docker,
+        };
+        let mut command = Command::new(program);
+        command.args(args);
+        (command, format!("{program} {}", args.join(" ")))
+    }
+}
+
+impl Runner for CommandRunner {
+    fn run(
+        &mut self,
+        program: Program,
+        args: &[String],
+        blocks_for: Duration,
+    ) -> Result<(), FaultError> {
+        self.run_all(&[(program, args.to_vec())], blocks_for)
+    }
+
+    fn run_all(
+        &mut self,
+        commands: &[(Program, Vec<String>)],
+        _blocks_for: Duration,
+    ) -> Result<(), FaultError> {
+        let mut children = vec![];
+        for (program, args) in commands {
+            let (mut command, line) = self.command(*program, args);
+            let child = command
+                .spawn()
+                .map_err(|e| FaultError::Command(line.clone(), e.to_string()))?;
+            children.push((child, line));
+        }
+
+        // Wait for all the children not to leave a fault behind
+        let mut result = Ok(());
+        for (mut child, line) in children {
+            match child.wait() {
+                Ok(status) if status.success() => {}
+                Ok(status) => result = Err(FaultError::Command(line, status.to_string())),
+                Err(e) => result = Err(FaultError::Command(line, e.to_string())),
+            }
+        }
+        result
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Record the commands and advance a virtual clock instead of running them
+pub struct DryRunner {
+    clock: DateTime<Utc>,
+    pub commands: Vec<String>,
+}
+
+impl DryRunner {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            clock: start,
+            commands: vec![],
+        }
+    }
+}
+
+impl Runner for DryRunner {
+    fn run(
+        &mut self,
+        program: Program,
+        args: &[String],
+        blocks_for: Duration,
+    ) -> Result<(), FaultError> {
+        self.run_all(&[(program, args.to_vec())], blocks_for)
+    }
+
+    fn run_all(
+        &mut self,
+        commands: &[(Program, Vec<String>)],
+        blocks_for: Duration,
+    ) -> Result<(), FaultError> {
+        for (program, args) in commands {
+            let program = match program {
+                Program::Pumba => "pumba",
+                Program::Docker => "docker",
+            };
+            let command = format!("{program} {}", args.join(" "));
+            tracing::info!("Dry run: {command}");
+            self.commands.push(command);
+        }
+        self.sleep(blocks_for);
+        Ok(())
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.clock += duration;
+    }
+
+    fn now(&self) -> DateTime<Utc> {
+        self.clock
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Deserialize;
+
+use super::{compose_hosts, Expectation, Fault, FaultError, PartitionScenario};
+
+/// Faults are injected into these containers when the schedule doesn't list any
+// TODO: skip fullnode for https://github.com/anoma/namada/issues/4689
+const DEFAULT_TARGETS: [&str; 8] = [
+    "validator0",
+    "validator1",
+    "validator2",
+    "masp-chain",
+    "masp-webserver",
+    "masp-block-filter",
+    "gaia",
+    "hermes",
+];
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct WeightedFault {
+    #[serde(flatten)]
+    pub fault: Fault,
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+}
+
+fn default_weight() -> u64 {
+    1
+}
+
+/// A fault injected after waiting for `after_sec` since the end of the previous one
+#[derive(Clone, Debug, Deserialize)]
+pub struct FaultEvent {
+    #[serde(default)]
+    pub after_sec: u64,
+    #[serde(flatten)]
+    pub fault: Fault,
+    #[serde(default)]
+    pub targets: Vec<String>,
+    pub duration_sec: u64,
+}
+
+/// Declarative fault schedule.
+/// The events are injected in order when they are listed, otherwise random faults are injected
+/// into random targets forever.
+/// The partition scenarios refer to the services of `hosts`, the docker compose services by default.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct FaultSchedule {
+    pub targets: Vec<String>,
+    pub faults: Vec<WeightedFault>,
+    pub min_duration_sec: u64,
+    pub max_duration_sec: u64,
+    pub min_interval_sec: u64,
+    pub max_interval_sec: u64,
+    pub events: Vec<FaultEvent>,
+    pub hosts: BTreeMap<String, String>,
+    pub scenarios: Vec<PartitionScenario>,
+}
+
+impl Default for FaultSchedule {
+    fn default() -> Self {
+        Self {
+            targets: DEFAULT_TARGETS.iter().map(|t| t.to_string()).collect(),
+            faults: Fault::all()
+                .into_iter()
+                .map(|fault| WeightedFault {
+                    fault,
+                    weight: default_weight(),
+                })
+                .collect(),
+            min_duration_sec: 1,
+            max_duration_sec: 10,
+            min_interval_sec: 61,
+            max_interval_sec: 180,
+            events: vec![],
+            hosts: compose_hosts(),
+            scenarios: PartitionScenario::builtins(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedFault {
+    pub wait: Duration,
+    pub fault: Fault,
+    pub targets: Vec<String>,
+    pub duration: Duration,
+}
+
+impl FaultSchedule {
+    pub fn load(path: &Path) -> Result<Self, FaultError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| FaultError::Schedule(e.to_string()))?;
+        let schedule: Self =
+            toml::from_str(&content).map_err(|e| FaultError::Schedule(e.to_string()))?;
+        schedule.validate()?;
+        Ok(schedule)
+    }
+
+    pub fn validate(&self) -> Result<(), FaultError> {
+        for fault in self
+            .events
+            .iter()
+            .map(|event| &event.fault)
+            .chain(self.faults.iter().map(|f| &f.fault))
+        {
+            if let Fault::Partition { scenario } = fault {
+                self.scenario(scenario)?
+                    .actions(&self.hosts, Duration::ZERO)?;
+            }
+        }
+        for (i, event) in self.events.iter().enumerate() {
+            if event.targets.is_empty() && !matches!(event.fault, Fault::Partition { .. }) {
+                return Err(FaultError::Schedule(format!("No target for event {i}")));
+            }
+            if event.duration_sec == 0 {
+                return Err(FaultError::Schedule(format!("No duration for event {i}")));
+            }
+        }
+        if !self.events.is_empty() {
+            return Ok(());
+        }
+
+        if self.targets.is_empty() {
+            return Err(FaultError::Schedule("No target".to_string()));
+        }
+        if self.min_duration_sec > self.max_duration_sec
+            || self.min_interval_sec > self.max_interval_sec
+        {
+            return Err(FaultError::Schedule(
+                "The minimum is greater than the maximum".to_string(),
+            ));
+        }
+        WeightedIndex::new(self.faults.iter().map(|f| f.weight))
+            .map_err(|e| FaultError::Schedule(format!("Invalid fault weights: {e}")))?;
+        Ok(())
+    }
+
+    pub fn scenario(&self, name: &str) -> Result<&PartitionScenario, FaultError> {
+        self.scenarios
+            .iter()
+            .find(|scenario| scenario.name == name)
+            .ok_or_else(|| FaultError::Schedule(format!("Unknown partition scenario: {name}")))
+    }
+
+    pub fn expectations(&self, fault: &Fault) -> Vec<Expectation> {
+        match fault {
+            Fault::Partition { scenario } => self
+                .scenario(scenario)
+                .map(|scenario| scenario.expectations.clone())
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    /// The `index`-th fault to inject, `None` when all the events have been injected
+    pub fn next_fault(&self, index: u64, rng: &mut impl Rng) -> Option<PlannedFault> {
+        if !self.events.is_empty() {
+            return self.events.get(index as usize).map(|event| PlannedFault {
+                wait: Duration::from_secs(event.after_sec),
+                fault: event.fault.clone(),
+                targets: match &event.fault {
+                    Fault::Partition { scenario } => self
+                        .scenario(scenario)
+                        .map(|scenario| scenario.services())
+                        .unwrap_or_default(),
+                    _ => event.targets.clone(),
+                },
+                duration: Duration::from_secs(event.duration_sec),
+            });
+        }
+
+        let weights = WeightedIndex::new(self.faults.iter().map(|f| f.weight))
+            .expect("Weights should be valid");
+        // The interval is waited between the faults, the first one is injected right away
+        let wait = if index == 0 {
+            0
+        } else {
+            rng.gen_range(self.min_interval_sec..=self.max_interval_sec)
+        };
+        let count = rng.gen_range(1..=self.targets.len());
+        let targets = self.targets.choose_multiple(rng, count).cloned().collect();
+        let fault = self.faults[weights.sample(rng)].fault.clone();
+        let targets = match &fault {
+            Fault::Partition { scenario } => self
+                .scenario(scenario)
+                .map(|scenario| scenario.services())
+                .unwrap_or(targets),
+            _ => targets,
+        };
+        let duration = rng.gen_range(self.min_duration_sec..=self.max_duration_sec);
+        Some(PlannedFault {
+            wait: Duration::from_secs(wait),
+            fault,
+            targets,
+            duration: Duration::from_secs(duration),
+        })
+    }
+}
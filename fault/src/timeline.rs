@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Expectation, Fault, FaultError};
+
+/// An injected fault. It's appended to the timeline when it starts and again when it ends.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FaultRecord {
+    pub id: u64,
+    pub fault: Fault,
+    pub targets: Vec<String>,
+    pub duration_sec: u64,
+    #[serde(default)]
+    pub expectations: Vec<Expectation>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
+impl FaultRecord {
+    /// The fault is active from its start to its end, or until now if it hasn't ended yet
+    pub fn is_active_at(&self, time: DateTime<Utc>) -> bool {
+        self.started_at <= time && self.ended_at.is_none_or(|ended_at| time <= ended_at)
+    }
+
+    /// The fault was active at some point between the start and the end
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.started_at <= end && self.ended_at.is_none_or(|ended_at| start <= ended_at)
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} {}", self.fault, self.targets.join(","))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Timeline {
+    pub faults: Vec<FaultRecord>,
+}
+
+impl Timeline {
+    pub fn load(path: &Path) -> Result<Self, FaultError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| FaultError::Timeline(e.to_string()))?;
+        let mut faults = BTreeMap::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let record: FaultRecord =
+                serde_json::from_str(line).map_err(|e| FaultError::Timeline(e.to_string()))?;
+            // The record at the end replaces the one at the start
+            faults.insert(record.id, record);
+        }
+        Ok(Self {
+            faults: faults.into_values().collect(),
+        })
+    }
+
+    pub fn active_at(&self, time: DateTime<Utc>) -> Vec<&FaultRecord> {
+        self.faults
+            .iter()
+            .filter(|record| record.is_active_at(time))
+            .collect()
+    }
+
+    pub fn active_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&FaultRecord> {
+        self.faults
+            .iter()
+            .filter(|record| record.overlaps(start, end))
+            .collect()
+    }
+}
+
+pub fn append_timeline(path: &Path, record: &FaultRecord) -> Result<(), FaultError> {
+    let json = serde_json::to_string(record).map_err(|e| FaultError::Timeline(e.to_string()))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| FaultError::Timeline(e.to_string()))?;
+    writeln!(file, "{json}").map_err(|e| FaultError::Timeline(e.to_string()))
+}
@@ -115,6 +115,8 @@ for ((i = 0; i < len; i++)); do
     # Copy all of the wasm artifacts from the chain into base directory for each validator chain directory
     rm -rf ${base_dirs[i]}/${CHAIN_ID}/wasm
     cp -r ${namada_path}/${CHAIN_ID}/wasm ${base_dirs[i]}/${CHAIN_ID}/
+    # Expose the RPC for the consistency check
+    sed -i 's|laddr = "tcp://127.0.0.1:27658"|laddr = "tcp://0.0.0.0:27658"|' ${base_dirs[i]}/${CHAIN_ID}/config.toml
     
     # Let each validator know it's ready to start 
     touch /container_ready/validator-${i}
@@ -128,6 +130,19 @@ rm -rf /fullnode/${CHAIN_ID}/wasm
 cp -r ${namada_path}/${CHAIN_ID}/wasm /fullnode/${CHAIN_ID}/
 sed -i 's|laddr = "tcp://127.0.0.1:26657"|laddr = "tcp://0.0.0.0:26657"|' /fullnode/${CHAIN_ID}/config.toml
 
+# Provide the extra genesis tokens for the workload, e.g. "btc=tnam1...,eth=tnam1..."
+tokens=()
+for alias in $(grep -o '^\[token\.[A-Za-z0-9_]*\]' ${network_template_path}/tokens.toml | sed 's/\[token\.\(.*\)\]/\1/' | tr '[:upper:]' '[:lower:]'); do
+    if [ "$alias" == "nam" ]; then
+        continue
+    fi
+    address=$(namadaw --base-dir /fullnode find --alias "$alias" | grep -o 'tnam1[0-9a-z]*' | head -n 1)
+    if [ -n "$address" ]; then
+        tokens+=("${alias}=${address}")
+    fi
+done
+(IFS=,; echo "${tokens[*]}") > /container_ready/tokens
+
 # Let each fullnode know it's ready to start 
 touch /container_ready/fullnode
 
@@ -8,7 +8,7 @@ use crate::context::Ctx;
 use crate::error::CheckError;
 use crate::state::State;
 use crate::types::{Alias, Balance, Fee, Height};
-use crate::utils::{is_native_denom, RetryConfig};
+use crate::utils::RetryConfig;
 
 pub mod account_exist;
 pub mod balance_shielded_source;
@@ -17,10 +17,19 @@ pub mod balance_source;
 pub mod balance_target;
 pub mod bond_decrease;
 pub mod bond_increase;
+pub mod bond_slashed;
+pub mod commission_rate;
+pub mod masp_fee;
+pub mod pgf_funding;
+pub mod pgf_steward;
+pub mod proposal_tally;
 pub mod reveal_pk;
+pub mod slash_processed;
 pub mod validator_account;
+pub mod validator_slashed;
 pub mod validator_status;
 pub mod vote_result;
+pub mod wasm_proposal_executed;
 
 #[enum_dispatch]
 pub enum Check {
@@ -29,12 +38,21 @@ pub enum Check {
     BalanceSource(balance_source::BalanceSource),
     BalanceShieldedTarget(balance_shielded_target::BalanceShieldedTarget),
     BalanceShieldedSource(balance_shielded_source::BalanceShieldedSource),
+    MaspFee(masp_fee::MaspFee),
     BondIncrease(bond_increase::BondIncrease),
     BondDecrease(bond_decrease::BondDecrease),
+    BondSlashed(bond_slashed::BondSlashed),
     AccountExist(account_exist::AccountExist),
     IsValidatorAccount(validator_account::ValidatorAccount),
     ValidatorStatus(validator_status::ValidatorStatus),
+    CommissionRate(commission_rate::CommissionRate),
+    ValidatorSlashed(validator_slashed::ValidatorSlashed),
+    SlashProcessed(slash_processed::SlashProcessed),
     VoteResult(vote_result::VoteResult),
+    PgfSteward(pgf_steward::PgfSteward),
+    PgfFunding(pgf_funding::PgfFunding),
+    ProposalTally(proposal_tally::ProposalTally),
+    WasmProposalExecuted(wasm_proposal_executed::WasmProposalExecuted),
 }
 
 impl Display for Check {
@@ -47,11 +65,7 @@ impl Check {
     pub fn check_pre_balance(&self, state: &State) -> Result<(), CheckError> {
         let (matched, details) = match self {
             Check::BalanceSource(bs) => {
-                let expected_pre_balance = if is_native_denom(bs.denom()) {
-                    state.get_balance_for(bs.target())
-                } else {
-                    state.get_ibc_balance_for(bs.target(), bs.denom())
-                };
+                let expected_pre_balance = state.get_balance_for(bs.target(), bs.denom());
                 let matched = bs.pre_balance() == Balance::from_u64(expected_pre_balance);
                 let details = json!({
                     "source_alias": bs.target(),
@@ -62,11 +76,7 @@ impl Check {
                 (matched, details)
             }
             Check::BalanceTarget(bt) => {
-                let expected_pre_balance = if is_native_denom(bt.denom()) {
-                    state.get_balance_for(bt.target())
-                } else {
-                    state.get_ibc_balance_for(bt.target(), bt.denom())
-                };
+                let expected_pre_balance = state.get_balance_for(bt.target(), bt.denom());
                 let matched = bt.pre_balance() == Balance::from_u64(expected_pre_balance);
                 let details = json!({
                     "target_alias": bt.target(),
@@ -77,11 +87,8 @@ impl Check {
                 (matched, details)
             }
             Check::BalanceShieldedSource(bss) => {
-                let expected_pre_balance = if is_native_denom(bss.denom()) {
-                    state.get_shielded_balance_for(bss.target())
-                } else {
-                    state.get_ibc_balance_for(bss.target(), bss.denom())
-                };
+                let expected_pre_balance =
+                    state.get_shielded_balance_for(bss.target(), bss.denom());
                 let matched = bss.pre_balance() == Balance::from_u64(expected_pre_balance);
                 let details = json!({
                     "source_alias": bss.target(),
@@ -91,11 +98,8 @@ impl Check {
                 (matched, details)
             }
             Check::BalanceShieldedTarget(bst) => {
-                let expected_pre_balance = if is_native_denom(bst.denom()) {
-                    state.get_shielded_balance_for(bst.target())
-                } else {
-                    state.get_ibc_balance_for(bst.target(), bst.denom())
-                };
+                let expected_pre_balance =
+                    state.get_shielded_balance_for(bst.target(), bst.denom());
                 let matched = bst.pre_balance() == Balance::from_u64(expected_pre_balance);
                 let details = json!({
                     "target_alias": bst.target(),
@@ -104,6 +108,17 @@ impl Check {
                 });
                 (matched, details)
             }
+            Check::MaspFee(mf) => {
+                let expected_pre_balance =
+                    state.get_shielded_balance_for(mf.target(), &Alias::nam().name);
+                let matched = mf.pre_balance() == Balance::from_u64(expected_pre_balance);
+                let details = json!({
+                    "target_alias": mf.target(),
+                    "expected_pre_balance": expected_pre_balance,
+                    "actual_pre_balance": mf.pre_balance(),
+                });
+                (matched, details)
+            }
             _ => (true, json!({})),
         };
 
@@ -8,7 +8,7 @@ use crate::check::{CheckContext, CheckInfo};
 use crate::context::Ctx;
 use crate::error::CheckError;
 use crate::types::{Alias, Amount, Balance, Fee};
-use crate::utils::{get_shielded_balance, is_native_denom, shielded_sync_with_retry, RetryConfig};
+use crate::utils::{get_shielded_balance, shielded_sync_with_retry, RetryConfig};
 
 #[derive(TypedBuilder)]
 pub struct BalanceShieldedSource {
@@ -74,7 +74,7 @@ impl CheckContext for BalanceShieldedSource {
                 ))
             })?;
 
-        let fee = if is_native_denom(&self.denom) {
+        let fee = if self.denom == Alias::nam().name {
             fees.get(&self.target.spending_key())
                 .cloned()
                 .unwrap_or_default()
@@ -8,7 +8,7 @@ use crate::check::{CheckContext, CheckInfo};
 use crate::context::Ctx;
 use crate::error::CheckError;
 use crate::types::{Alias, Amount, Balance, Fee};
-use crate::utils::{get_balance, is_native_denom, RetryConfig};
+use crate::utils::{get_balance, RetryConfig};
 
 #[derive(TypedBuilder)]
 pub struct BalanceSource {
@@ -51,7 +51,7 @@ impl CheckContext for BalanceSource {
         let (target_address, post_balance) =
             get_balance(ctx, &self.target, &self.denom, retry_config).await?;
 
-        let fee = if is_native_denom(&self.denom) {
+        let fee = if self.denom == Alias::nam().name {
             fees.get(&self.target).cloned().unwrap_or_default()
         } else {
             0u64
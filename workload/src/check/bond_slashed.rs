@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use namada_sdk::dec::Dec;
+use namada_sdk::token;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::constants::SLASH_ROUNDING_TOLERANCE;
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::types::{Alias, Amount, Epoch, Fee, ValidatorAddress};
+use crate::utils::{get_bond, get_unbonds, RetryConfig};
+
+/// The bond and the unbond to the slashed validator shrink by the slash rate. Only the stake
+/// contributing at the infraction is slashable.
+#[derive(TypedBuilder)]
+pub struct BondSlashed {
+    target: Alias,
+    validator: ValidatorAddress,
+    rate: Dec,
+    pre_bond: Amount,
+    slashable_bond: Amount,
+    pre_unbond: Amount,
+    slashable_unbond: Amount,
+    epoch: Epoch,
+}
+
+fn slashed(amount: Amount, rate: Dec) -> Amount {
+    token::Amount::from_u64(amount)
+        .mul_ceil(rate)
+        .expect("Slashed amount shouldn't overflow")
+        .to_string()
+        .parse()
+        .expect("Amount conversion shouldn't fail")
+}
+
+impl CheckContext for BondSlashed {
+    fn summary(&self) -> String {
+        format!("bond/{}/{}/slashed", self.target.name, self.validator)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let post_bond: Amount =
+            get_bond(ctx, &self.target, &self.validator, self.epoch, retry_config)
+                .await?
+                .to_string()
+                .parse()
+                .expect("Amount conversion shouldn't fail");
+        let post_unbond: Amount = get_unbonds(ctx, &self.target, &self.validator, retry_config)
+            .await?
+            .to_string()
+            .parse()
+            .expect("Amount conversion shouldn't fail");
+
+        let expected_bond = self
+            .pre_bond
+            .saturating_sub(slashed(self.slashable_bond, self.rate));
+        // Unbonds withdrawable before the processing epoch aren't slashed
+        let min_unbond = self
+            .pre_unbond
+            .saturating_sub(slashed(self.slashable_unbond, self.rate));
+
+        let details = json!({
+            "target_alias": self.target,
+            "validator": self.validator,
+            "rate": self.rate.to_string(),
+            "pre_bond": self.pre_bond,
+            "slashable_bond": self.slashable_bond,
+            "expected_bond": expected_bond,
+            "post_bond": post_bond,
+            "pre_unbond": self.pre_unbond,
+            "slashable_unbond": self.slashable_unbond,
+            "min_unbond": min_unbond,
+            "post_unbond": post_unbond,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if post_bond.abs_diff(expected_bond) > SLASH_ROUNDING_TOLERANCE {
+            tracing::error!("Slashed bond is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "BondSlashed check error: bond should be {} - {} * {} = {expected_bond}, but {post_bond}",
+                self.pre_bond, self.slashable_bond, self.rate
+            )));
+        }
+        if post_unbond + SLASH_ROUNDING_TOLERANCE < min_unbond
+            || post_unbond > self.pre_unbond + SLASH_ROUNDING_TOLERANCE
+        {
+            tracing::error!("Slashed unbond is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "BondSlashed check error: unbond should be between {min_unbond} and {}, but {post_unbond}",
+                self.pre_unbond
+            )));
+        }
+
+        tracing::info!("Bond slashed: {details}");
+        Ok(())
+    }
+}
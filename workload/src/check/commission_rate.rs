@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::constants::PIPELINE_LEN;
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::types::{Alias, CommissionChangeKind, CommissionRate as Rate, Fee, ValidatorAddress};
+use crate::utils::{get_commission_rate, get_epoch_at_height, RetryConfig};
+
+/// A valid change takes effect at the pipeline epoch and an invalid change is rejected. The pre
+/// rate is the rate at the pipeline epoch before the execution.
+#[derive(TypedBuilder)]
+pub struct CommissionRate {
+    target: Alias,
+    validator: ValidatorAddress,
+    pre_rate: Rate,
+    rate: Rate,
+    kind: CommissionChangeKind,
+}
+
+impl CheckContext for CommissionRate {
+    fn summary(&self) -> String {
+        format!("commission-rate/{}/{}", self.target.name, self.kind)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let epoch = get_epoch_at_height(ctx, check_info.execution_height, retry_config).await?;
+        let pre_pipeline_rate =
+            get_commission_rate(ctx, &self.validator, epoch + PIPELINE_LEN - 1, retry_config)
+                .await?
+                .commission_rate;
+        let pipeline_rate =
+            get_commission_rate(ctx, &self.validator, epoch + PIPELINE_LEN, retry_config)
+                .await?
+                .commission_rate;
+
+        let expected_rate = if self.kind == CommissionChangeKind::Valid {
+            self.rate
+        } else {
+            self.pre_rate
+        };
+
+        let details = json!({
+            "target_alias": self.target,
+            "validator": self.validator,
+            "kind": self.kind.to_string(),
+            "pre_rate": self.pre_rate.to_string(),
+            "rate": self.rate.to_string(),
+            "pre_pipeline_rate": pre_pipeline_rate.map(|rate| rate.to_string()),
+            "pipeline_rate": pipeline_rate.map(|rate| rate.to_string()),
+            "epoch": epoch,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if pipeline_rate != Some(expected_rate) {
+            tracing::error!("Commission rate is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "CommissionRate check error: {} rate at the pipeline epoch should be {expected_rate} for the {} change, but {pipeline_rate:?}",
+                self.target.name, self.kind
+            )));
+        }
+
+        tracing::info!("Commission rate is as expected: {details}");
+        Ok(())
+    }
+}
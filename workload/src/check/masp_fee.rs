@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use namada_sdk::token;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::types::{Alias, Amount, Balance, Fee};
+use crate::utils::{get_shielded_balance, shielded_sync_with_retry, RetryConfig};
+
+/// The NAM balance of the spending key paying the fee via a disposable address
+#[derive(TypedBuilder)]
+pub struct MaspFee {
+    target: Alias,
+    pre_balance: Balance,
+    /// NAM spent by the tx itself when it's applied
+    amount: Amount,
+    /// NAM received by the same spending key, e.g. in a batch
+    #[builder(default)]
+    received: Amount,
+}
+
+impl MaspFee {
+    pub fn target(&self) -> &Alias {
+        &self.target
+    }
+
+    pub fn pre_balance(&self) -> Balance {
+        self.pre_balance
+    }
+
+    /// Only the fee is deducted when the tx has failed
+    pub fn without_transfer(self) -> Self {
+        Self {
+            amount: 0,
+            received: 0,
+            ..self
+        }
+    }
+}
+
+impl CheckContext for MaspFee {
+    fn summary(&self) -> String {
+        format!("masp-fee/{}", self.target.name)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        shielded_sync_with_retry(
+            ctx,
+            &self.target,
+            Some(check_info.execution_height),
+            true,
+            retry_config,
+        )
+        .await?;
+
+        let post_balance =
+            get_shielded_balance(ctx, &self.target, &Alias::nam().name, retry_config)
+                .await?
+                .unwrap_or_default();
+        let fee = fees
+            .get(&self.target.spending_key())
+            .cloned()
+            .unwrap_or_default();
+
+        let check_balance = self
+            .pre_balance
+            .checked_add(token::Amount::from_u64(self.received))
+            .and_then(|balance| balance.checked_sub(token::Amount::from_u64(self.amount + fee)))
+            .ok_or_else(|| {
+                CheckError::State(format!(
+                    "MaspFee check error: {} balance is underflowing",
+                    self.target.name
+                ))
+            })?;
+
+        let details = json!({
+            "target_alias": self.target,
+            "pre_balance": self.pre_balance,
+            "amount": self.amount,
+            "received": self.received,
+            "paid_fee": fee,
+            "post_balance": post_balance,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if post_balance.eq(&check_balance) {
+            tracing::info!("MASP fee deducted: {details}");
+            Ok(())
+        } else {
+            tracing::error!("MASP fee is wrong: {details}");
+            Err(CheckError::State(format!("MaspFee check error: post balance is not equal to pre balance - amount + received - fee: {} - {} + {} - {fee} = {check_balance} != {post_balance}", self.pre_balance, self.amount, self.received)))
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+use namada_sdk::token;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::task::query_pgf::PgfPayment;
+use crate::types::{Alias, Fee};
+use crate::utils::{get_balance, get_epoch, get_pgf_fundings, RetryConfig};
+
+/// The PGF payments land in the balance of the recipient tracked by the state
+#[derive(TypedBuilder)]
+pub struct PgfFunding {
+    payment: PgfPayment,
+}
+
+impl CheckContext for PgfFunding {
+    fn summary(&self) -> String {
+        format!("pgf-funding/{}", self.payment.target.name)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let target = &self.payment.target;
+        let denom = Alias::nam().name;
+
+        // The balance should be read within an epoch since it's paid at every new epoch
+        let (epoch, target_address, post_balance) = loop {
+            let epoch = get_epoch(ctx, retry_config).await?;
+            let (target_address, post_balance) =
+                get_balance(ctx, target, &denom, retry_config).await?;
+            if get_epoch(ctx, retry_config).await? == epoch {
+                break (epoch, target_address, post_balance);
+            }
+        };
+
+        let expected_balance =
+            token::Amount::from_u64(self.payment.pre_balance + self.payment.paid_until(epoch));
+        let funding = get_pgf_fundings(ctx, retry_config)
+            .await?
+            .get(&target_address.to_string())
+            .cloned();
+
+        let details = json!({
+            "target_alias": target,
+            "target": target_address.to_string(),
+            "pre_balance": self.payment.pre_balance,
+            "one_off": self.payment.one_off,
+            "continuous_amount": self.payment.amount,
+            "from_epoch": self.payment.from_epoch,
+            "epoch": epoch,
+            "expected_balance": expected_balance,
+            "post_balance": post_balance,
+            "funding": funding,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if self.payment.amount > 0 && funding != Some(token::Amount::from_u64(self.payment.amount))
+        {
+            tracing::error!("Continuous funding is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "PgfFunding check error: continuous funding of {} should be {}, but {funding:?}",
+                target.name, self.payment.amount
+            )));
+        }
+        if post_balance != expected_balance {
+            tracing::error!("PGF payment is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "PgfFunding check error: {} balance should be {expected_balance}, but {post_balance}",
+                target.name
+            )));
+        }
+
+        tracing::info!("PGF payments landed: {details}");
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::{CheckError, QueryError};
+use crate::types::{Alias, Fee};
+use crate::utils::{get_pgf_stewards, RetryConfig};
+
+/// The steward is added to the stewards set only when the proposal passed
+#[derive(TypedBuilder)]
+pub struct PgfSteward {
+    target: Alias,
+    is_steward: bool,
+}
+
+impl CheckContext for PgfSteward {
+    fn summary(&self) -> String {
+        format!("pgf-steward/{}", self.target.name)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let target_address = ctx
+            .namada
+            .wallet
+            .read()
+            .await
+            .find_address(&self.target.name)
+            .ok_or_else(|| QueryError::Wallet(format!("No target address: {}", self.target.name)))?
+            .into_owned();
+        let stewards = get_pgf_stewards(ctx, retry_config).await?;
+        let is_steward = stewards.contains(&target_address);
+
+        let details = json!({
+            "target_alias": self.target,
+            "target": target_address.to_string(),
+            "expected_steward": self.is_steward,
+            "steward": is_steward,
+            "stewards": stewards.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if is_steward == self.is_steward {
+            tracing::info!("Stewards set is as expected: {details}");
+            Ok(())
+        } else {
+            tracing::error!("Stewards set is wrong: {details}");
+            Err(CheckError::State(format!(
+                "PgfSteward check error: {} should{} be a steward",
+                self.target.name,
+                if self.is_steward { "" } else { " not" }
+            )))
+        }
+    }
+}
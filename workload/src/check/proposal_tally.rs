@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use namada_sdk::token;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::task::query_tally::ExpectedTally;
+use crate::types::{Alias, Fee};
+use crate::utils::{get_proposal_tally, RetryConfig};
+
+/// The tallied voting powers follow the votes and the delegator overrides
+#[derive(TypedBuilder)]
+pub struct ProposalTally {
+    tally: ExpectedTally,
+}
+
+impl CheckContext for ProposalTally {
+    fn summary(&self) -> String {
+        format!("proposal-tally/{}", self.tally.proposal_id)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let result = get_proposal_tally(ctx, self.tally.proposal_id, retry_config)
+            .await?
+            .ok_or_else(|| {
+                CheckError::State(format!(
+                    "ProposalTally check error: proposal {} hasn't been tallied",
+                    self.tally.proposal_id
+                ))
+            })?;
+        let expected =
+            [self.tally.yay, self.tally.nay, self.tally.abstain].map(token::Amount::from_u64);
+        let actual = [
+            result.total_yay_power,
+            result.total_nay_power,
+            result.total_abstain_power,
+        ];
+
+        let details = json!({
+            "proposal_id": self.tally.proposal_id,
+            "expected_yay": expected[0].to_string(),
+            "expected_nay": expected[1].to_string(),
+            "expected_abstain": expected[2].to_string(),
+            "yay": actual[0].to_string(),
+            "nay": actual[1].to_string(),
+            "abstain": actual[2].to_string(),
+            "result": result.result.to_string(),
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if expected == actual {
+            tracing::info!("Proposal tally is as expected: {details}");
+            Ok(())
+        } else {
+            tracing::error!("Proposal tally is wrong: {details}");
+            Err(CheckError::State(format!(
+                "ProposalTally check error: proposal {} tally yay/nay/abstain {}/{}/{} != {}/{}/{}",
+                self.tally.proposal_id,
+                actual[0],
+                actual[1],
+                actual[2],
+                expected[0],
+                expected[1],
+                expected[2]
+            )))
+        }
+    }
+}
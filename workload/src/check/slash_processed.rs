@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use namada_sdk::address::Address;
+use namada_sdk::dec::Dec;
+use namada_sdk::proof_of_stake::types::ValidatorState;
+use namada_sdk::token;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::types::{Alias, Epoch, Fee, ValidatorAddress};
+use crate::utils::{
+    get_consensus_stake, get_epoch, get_pos_params, get_validator_stake, get_validator_state,
+    RetryConfig,
+};
+
+/// The processed slash of the double sign has at least the duplicate vote rate and the cubic
+/// rate from the stake of the validator, and the validator is still jailed
+#[derive(TypedBuilder)]
+pub struct SlashProcessed {
+    target: Alias,
+    validator: ValidatorAddress,
+    infraction_epoch: Epoch,
+    rate: Dec,
+}
+
+/// The cubic rate when the validator is the only one infracting in the window
+fn min_cubic_rate(stake: token::Amount, consensus_stake: token::Amount) -> Dec {
+    let stake = Dec::try_from(stake).expect("Stake should be converted");
+    let consensus_stake = Dec::try_from(consensus_stake).expect("Stake should be converted");
+    stake
+        .checked_div(consensus_stake)
+        .and_then(|fraction| {
+            Dec::from(9_u64)
+                .checked_mul(fraction)
+                .and_then(|rate| rate.checked_mul(fraction))
+        })
+        .unwrap_or_default()
+        .min(Dec::one())
+}
+
+impl CheckContext for SlashProcessed {
+    fn summary(&self) -> String {
+        format!("slash-processed/{}", self.target.name)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let params = get_pos_params(ctx, retry_config).await?;
+        let validator =
+            Address::from_str(&self.validator).expect("ValidatorAddress should be converted");
+        let stake =
+            get_validator_stake(ctx, &validator, self.infraction_epoch, retry_config).await?;
+        let consensus_stake = get_consensus_stake(ctx, self.infraction_epoch, retry_config).await?;
+        let min_rate = params
+            .owned
+            .duplicate_vote_min_slash_rate
+            .max(min_cubic_rate(
+                token::Amount::from_u64(stake),
+                consensus_stake,
+            ));
+
+        let epoch = get_epoch(ctx, retry_config).await?;
+        let (_, (state, _)) = get_validator_state(ctx, &self.target, epoch, retry_config).await?;
+
+        let details = json!({
+            "target_alias": self.target,
+            "validator": self.validator,
+            "infraction_epoch": self.infraction_epoch,
+            "rate": self.rate.to_string(),
+            "min_rate": min_rate.to_string(),
+            "stake": stake,
+            "consensus_stake": consensus_stake.to_string(),
+            "state": format!("{state:?}"),
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if self.rate < min_rate || self.rate > Dec::one() {
+            tracing::error!("Slash rate is wrong: {details}");
+            return Err(CheckError::State(format!(
+                "SlashProcessed check error: slash rate {} should be between {min_rate} and 1",
+                self.rate
+            )));
+        }
+        if !matches!(state, Some(ValidatorState::Jailed)) {
+            tracing::error!("Validator isn't jailed: {details}");
+            return Err(CheckError::State(format!(
+                "SlashProcessed check error: {} state is {state:?}, not jailed",
+                self.target.name
+            )));
+        }
+
+        tracing::info!("Slash processed: {details}");
+        Ok(())
+    }
+}
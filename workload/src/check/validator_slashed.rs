@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use namada_sdk::key::tm_consensus_key_raw_hash;
+use namada_sdk::proof_of_stake::types::SlashType;
+use namada_sdk::rpc;
+use namada_sdk::tendermint::evidence::Evidence;
+use namada_sdk::tendermint_rpc::Client;
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::{CheckError, QueryError};
+use crate::types::{Alias, Epoch, Fee, ValidatorAddress};
+use crate::utils::{get_consensus_key, get_enqueued_slashes, get_pos_params, RetryConfig};
+
+/// The evidence of the double sign is committed and the duplicate vote slash is enqueued. The
+/// rate is set when the slash is processed, it's checked by `SlashProcessed`.
+#[derive(TypedBuilder)]
+pub struct ValidatorSlashed {
+    target: Alias,
+    validator: ValidatorAddress,
+}
+
+impl CheckContext for ValidatorSlashed {
+    fn summary(&self) -> String {
+        format!("validator-slashed/{}", self.target.name)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let client = &ctx.namada.client;
+        let consensus_pk = get_consensus_key(ctx, &self.validator, retry_config)
+            .await?
+            .ok_or_else(|| {
+                CheckError::State(format!(
+                    "ValidatorSlashed check error: no consensus key of {}",
+                    self.target.name
+                ))
+            })?;
+        let tm_address = tm_consensus_key_raw_hash(&consensus_pk);
+
+        let block = client
+            .block(check_info.execution_height as u32)
+            .await
+            .map_err(|e| CheckError::Query(QueryError::CosmosRpc(e)))?
+            .block;
+        let infraction_height = block
+            .evidence
+            .iter()
+            .find_map(|evidence| match evidence {
+                Evidence::DuplicateVote(evidence)
+                    if evidence.vote_a.validator_address.to_string() == tm_address =>
+                {
+                    Some(evidence.vote_a.height.value())
+                }
+                _ => None,
+            })
+            .ok_or_else(|| {
+                CheckError::State(format!(
+                    "ValidatorSlashed check error: no evidence of {} at height {}",
+                    self.target.name, check_info.execution_height
+                ))
+            })?;
+        let infraction_epoch: Epoch = rpc::query_epoch_at_height(client, infraction_height.into())
+            .await
+            .map_err(QueryError::Rpc)?
+            .ok_or_else(|| {
+                CheckError::State(format!(
+                    "ValidatorSlashed check error: no epoch at height {infraction_height}"
+                ))
+            })?
+            .into();
+
+        let params = get_pos_params(ctx, retry_config).await?;
+        let processing_epoch = infraction_epoch + params.owned.slash_processing_epoch_offset();
+        let enqueued_slashes = get_enqueued_slashes(ctx, &self.validator, retry_config).await?;
+        let is_enqueued = enqueued_slashes
+            .get(&processing_epoch)
+            .is_some_and(|slashes| {
+                slashes.iter().any(|slash| {
+                    slash.r#type == SlashType::DuplicateVote
+                        && slash.block_height == infraction_height
+                })
+            });
+
+        let details = json!({
+            "target_alias": self.target,
+            "validator": self.validator,
+            "infraction_height": infraction_height,
+            "infraction_epoch": infraction_epoch,
+            "processing_epoch": processing_epoch,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if !is_enqueued {
+            tracing::error!("Slash isn't enqueued: {details}");
+            return Err(CheckError::State(format!(
+                "ValidatorSlashed check error: no slash of {} is enqueued for epoch {processing_epoch}",
+                self.target.name
+            )));
+        }
+
+        tracing::info!("Slash enqueued: {details}");
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+use typed_builder::TypedBuilder;
+
+use crate::check::{CheckContext, CheckInfo};
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::types::{Alias, Fee, ProposalId};
+use crate::utils::{get_wasm_marker, wasm_marker_key, RetryConfig};
+
+/// The proposal code wrote the marker only when the proposal passed
+#[derive(TypedBuilder)]
+pub struct WasmProposalExecuted {
+    proposal_id: ProposalId,
+    marker: u64,
+    is_passed: bool,
+}
+
+impl CheckContext for WasmProposalExecuted {
+    fn summary(&self) -> String {
+        format!("wasm-proposal-executed/{}", self.proposal_id)
+    }
+
+    async fn do_check(
+        &self,
+        ctx: &Ctx,
+        _fees: &HashMap<Alias, Fee>,
+        check_info: CheckInfo,
+        retry_config: RetryConfig,
+    ) -> Result<(), CheckError> {
+        let value = get_wasm_marker(ctx, self.marker, retry_config).await?;
+        let expected_value = self.is_passed.then(|| self.marker.to_le_bytes().to_vec());
+
+        let details = json!({
+            "proposal_id": self.proposal_id,
+            "marker_key": wasm_marker_key(self.marker).to_string(),
+            "is_passed": self.is_passed,
+            "value": value,
+            "expected_value": expected_value,
+            "execution_height": check_info.execution_height,
+            "check_height": check_info.check_height,
+        });
+
+        if value == expected_value {
+            tracing::info!("Proposal code effect is as expected: {details}");
+            Ok(())
+        } else {
+            tracing::error!("Proposal code effect is wrong: {details}");
+            Err(CheckError::State(format!(
+                "WasmProposalExecuted check error: marker of proposal {} is {value:?}, expected {expected_value:?}",
+                self.proposal_id
+            )))
+        }
+    }
+}
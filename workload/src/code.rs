@@ -25,6 +25,18 @@ pub enum CodeType {
     UnexpectedFailure,
 }
 
+impl CodeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CodeType::Success => "success",
+            CodeType::Fatal => "fatal",
+            CodeType::Skip => "skip",
+            CodeType::AcceptableFailure => "acceptable_failure",
+            CodeType::UnexpectedFailure => "unexpected_failure",
+        }
+    }
+}
+
 impl Code {
     pub fn code(&self) -> i32 {
         match self {
@@ -102,6 +114,7 @@ fn is_acceptable_failure(err: &TaskError) -> bool {
         TaskError::IbcTransfer(_) | TaskError::InvalidShielded { .. } | TaskError::Query(_) => true,
         TaskError::BuildTx(e) if e.to_string().contains(CONNECTION_ERROR_MESSAGE) => true,
         TaskError::Broadcast(e) if e.to_string().contains(CONNECTION_ERROR_MESSAGE) => true,
+        TaskError::Evidence(e) if e.contains(CONNECTION_ERROR_MESSAGE) => true,
         TaskError::CosmosTx(e)
             if e.to_string().contains(CONNECTION_ERROR_MESSAGE)
                 || e.to_string().contains(COSMOS_ACC_SEQ_ERROR_MESSAGE) =>
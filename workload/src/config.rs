@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 use serde::Deserialize;
@@ -24,6 +26,36 @@ pub struct Args {
     #[clap(long, env)]
     #[clap(default_value_t = false)]
     pub init: bool,
+    /// Trace to replay, run with the recorded seed to regenerate the same keys
+    #[clap(long, env)]
+    pub replay: Option<PathBuf>,
+    #[clap(long, env)]
+    pub report: Option<PathBuf>,
+    #[clap(long, env)]
+    pub junit_report: Option<PathBuf>,
+    /// Fault timeline to annotate the failures in the report with the active faults
+    #[clap(long, env)]
+    pub fault_timeline: Option<PathBuf>,
+    /// Address to serve the Prometheus metrics, overrides the config
+    #[clap(long, env)]
+    pub metrics_addr: Option<SocketAddr>,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Clone, Debug)]
+pub enum Command {
+    /// Shrink a trace ending in a fatal failure to the steps reproducing it: the shortest prefix
+    /// of the trace first, then any subset of the prefix with delta debugging
+    Shrink {
+        #[clap(long)]
+        trace: PathBuf,
+        /// Command to reinitialize the chain before each replay
+        #[clap(long)]
+        reset_cmd: String,
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -37,6 +69,15 @@ pub struct AppConfig {
     pub cosmos_base_dir: PathBuf,
     pub namada_channel_id: String,
     pub cosmos_channel_id: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    #[serde(default)]
+    pub profiles: HashMap<String, HashMap<String, u64>>,
+    #[serde(default)]
+    pub metrics_addr: Option<SocketAddr>,
+    /// Extra genesis tokens by the alias, added to the wallet next to `nam`
+    #[serde(default)]
+    pub tokens: HashMap<String, String>,
 }
 
 impl AppConfig {
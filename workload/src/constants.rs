@@ -14,6 +14,12 @@ pub const MAX_BATCH_TX_NUM: u64 = 3;
 pub const PIPELINE_LEN: u64 = 2;
 pub const UNBONDING_LEN: u64 = 3;
 
+// For slashing
+/// Blocks to wait for the evidence to be committed
+pub const EVIDENCE_WAIT_BLOCKS: u64 = 20;
+/// Slashing rounds each bond of the delegator
+pub const SLASH_ROUNDING_TOLERANCE: u64 = 10;
+
 pub const NATIVE_SCALE: u64 = namada_sdk::token::NATIVE_SCALE;
 pub const FAUCET_AMOUNT: u64 = 1_000_000 * NATIVE_SCALE;
 pub const DEFAULT_GAS_PRICE: f64 = 0.000001;
@@ -21,6 +27,8 @@ pub const DEFAULT_GAS_LIMIT: u64 = namada_sdk::DEFAULT_GAS_LIMIT * 3;
 pub const DEFAULT_FEE: u64 = DEFAULT_GAS_LIMIT * (DEFAULT_GAS_PRICE * NATIVE_SCALE as f64) as u64;
 pub const MIN_TRANSFER_BALANCE: u64 = MAX_BATCH_TX_NUM * NATIVE_SCALE + DEFAULT_FEE;
 pub const PROPOSAL_DEPOSIT: u64 = 50 * NATIVE_SCALE;
+/// Small enough for the PGF account to pay every funding
+pub const MAX_PGF_FUNDING: u64 = 1_000;
 
 // For Cosmos
 pub const COSMOS_CHAIN_ID: &str = "gaia-0";
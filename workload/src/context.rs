@@ -1,6 +1,7 @@
 use namada_sdk::ibc::core::host::types::identifiers::ChannelId;
 
 use crate::config::AppConfig;
+use crate::types::Alias;
 
 mod cosmos;
 mod namada;
@@ -11,6 +12,8 @@ pub struct Ctx {
     pub namada_channel_id: ChannelId,
     pub cosmos_channel_id: ChannelId,
     pub masp_indexer_url: String,
+    /// Denoms of the transparent tokens, NAM and the extra tokens
+    pub tokens: Vec<String>,
 }
 
 impl Ctx {
@@ -22,6 +25,9 @@ impl Ctx {
             namada_channel_id: config.namada_channel_id.parse().unwrap(),
             cosmos_channel_id: config.cosmos_channel_id.parse().unwrap(),
             masp_indexer_url: format!("{}/api/v1", config.masp_indexer_url.clone()),
+            tokens: std::iter::once(Alias::nam().name)
+                .chain(config.tokens.keys().cloned())
+                .collect(),
         })
     }
 
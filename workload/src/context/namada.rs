@@ -46,6 +46,15 @@ pub async fn namada_ctx(config: &AppConfig) -> Result<NamadaCtx, String> {
 
         wallet.save().expect("Should be able to save the wallet");
     }
+    // The extra tokens could be configured after the wallet was created
+    if !config.tokens.is_empty() {
+        for (alias, address) in &config.tokens {
+            let address = Address::from_str(address)
+                .map_err(|e| format!("Invalid token address of {alias}: {e}"))?;
+            wallet.insert_address(alias, address, true).unwrap();
+        }
+        wallet.save().expect("Should be able to save the wallet");
+    }
 
     // Setup shielded context storage
     let shielded_ctx_path = base_dir.join(format!("masp-{}", thread_id()));
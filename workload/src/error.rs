@@ -48,6 +48,8 @@ pub enum TaskError {
     CosmosTx(String),
     #[error("IBC transfer wasn't rejected or timed out: `{0}`")]
     IbcTransfer(String),
+    #[error("Submitting evidence failed: `{0}`")]
+    Evidence(String),
 }
 
 #[derive(Error, Debug)]
@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 
 use namada_sdk::rpc;
@@ -9,38 +11,63 @@ use crate::code::Code;
 use crate::config::AppConfig;
 use crate::context::Ctx;
 use crate::error::{CheckError, StepError, TaskError};
+use crate::metrics::METRICS;
+use crate::schedule::Schedule;
 use crate::state::{State, StateError};
 use crate::stats::Stats;
 use crate::step::{StepContext, StepType};
 use crate::task::{Task, TaskContext};
+use crate::trace::{append_trace, TraceEntry};
 use crate::types::{Alias, Epoch, Fee, Height};
 use crate::utils::{
-    base_dir, execute_reveal_pk, get_block_height, get_proposals, is_pk_revealed, retry_config,
-    thread_id,
+    base_dir, execute_reveal_pk, gen_wallet_keys, get_block_height, get_proposals, is_pk_revealed,
+    retry_config, settlement_wait, take_retry_count, take_settlement_wait, thread_id,
 };
 
 pub struct WorkloadExecutor {
     ctx: Ctx,
+    schedule: Arc<Schedule>,
     state: State,
     stats: Stats,
     step_id: u64,
+    trace: Option<PathBuf>,
+    failed_check: Option<String>,
+    started: Instant,
 }
 
 impl WorkloadExecutor {
-    pub fn new(ctx: Ctx) -> Self {
+    pub fn new(ctx: Ctx, schedule: Arc<Schedule>) -> Self {
         Self {
             ctx,
+            schedule,
             state: State::new(),
             stats: Stats::default(),
             step_id: 0u64,
+            trace: None,
+            failed_check: None,
+            started: Instant::now(),
         }
     }
 
+    pub fn record_trace(&mut self, path: PathBuf) {
+        self.trace = Some(path);
+    }
+
     pub fn reconnect(&mut self, config: &AppConfig) {
         self.ctx.reconnect(config);
     }
 
-    pub fn final_report(self) -> Stats {
+    pub fn next_step(&self) -> StepType {
+        self.schedule.next_step_type()
+    }
+
+    /// Summary of the last check which failed
+    pub fn failed_check(&self) -> Option<&str> {
+        self.failed_check.as_deref()
+    }
+
+    pub fn final_report(mut self) -> Stats {
+        self.stats.duration = self.started.elapsed();
         self.stats
     }
 
@@ -126,16 +153,24 @@ impl WorkloadExecutor {
         self.state.save(&dir)
     }
 
+    async fn finish_step(&mut self, code: &Code) {
+        let height = get_block_height(&self.ctx, retry_config()).await.ok();
+        self.stats.update(self.step_id, code, height);
+    }
+
     pub async fn try_step(&mut self, next_step: StepType, no_check: bool) -> Code {
         self.step_id += 1;
         tracing::info!("StepID: {}, StepType: {next_step}", self.step_id);
+        take_retry_count();
+        self.stats.start_step();
+        let now = Instant::now();
 
         match self.is_valid(&next_step).await {
             Ok(true) => {}
             _ => {
                 tracing::warn!("Invalid step: {next_step} -> {:>?}", self.state);
                 let code = Code::Skip(next_step);
-                self.stats.update(self.step_id, &code);
+                self.finish_step(&code).await;
                 code.output_logs();
                 return code;
             }
@@ -145,20 +180,78 @@ impl WorkloadExecutor {
         let tasks = match self.build_tasks(&next_step).await {
             Ok(tasks) if tasks.is_empty() => {
                 let code = Code::NoTask(next_step);
-                self.stats.update(self.step_id, &code);
+                self.finish_step(&code).await;
                 code.output_logs();
                 return code;
             }
             Ok(tasks) => tasks,
             Err(e) => {
                 let code = Code::StepFailure(next_step, e);
-                self.stats.update(self.step_id, &code);
+                self.finish_step(&code).await;
                 code.output_logs();
                 return code;
             }
         };
         tracing::info!("Built tasks for {next_step}");
+        self.stats
+            .step_latency(&next_step)
+            .build
+            .record(now.elapsed());
+        self.record(&next_step, &tasks);
+
+        self.run_tasks(next_step, tasks, no_check).await
+    }
+
+    /// Execute the recorded tasks without building them from the step
+    pub async fn replay_step(
+        &mut self,
+        next_step: StepType,
+        tasks: Vec<Task>,
+        no_check: bool,
+    ) -> Code {
+        self.step_id += 1;
+        tracing::info!("StepID: {}, Replaying StepType: {next_step}", self.step_id);
+        take_retry_count();
+        self.stats.start_step();
+
+        if tasks.is_empty() {
+            let code = Code::NoTask(next_step);
+            self.finish_step(&code).await;
+            code.output_logs();
+            return code;
+        }
+
+        if let Err(e) = self.prepare_wallet(&tasks).await {
+            let code = Code::StepFailure(next_step, e);
+            self.finish_step(&code).await;
+            code.output_logs();
+            return code;
+        }
+
+        self.run_tasks(next_step, tasks, no_check).await
+    }
 
+    /// Replay the recorded steps until a fatal failure happens
+    pub async fn replay(&mut self, entries: &[TraceEntry], no_check: bool) -> Option<Code> {
+        let mut last_code = None;
+        for entry in entries {
+            let step_type = entry.step_type().expect("Step type should be valid");
+            // Initialization is executed without checks
+            let no_check = no_check || matches!(step_type, StepType::Initialize(_));
+            let code = self
+                .replay_step(step_type, entry.tasks.clone(), no_check)
+                .await;
+            if matches!(code, Code::Fatal(..)) {
+                tracing::error!("Reproduced a fatal failure at StepID {}", entry.step_id);
+                return Some(code);
+            }
+            last_code = Some(code);
+        }
+        last_code
+    }
+
+    async fn run_tasks(&mut self, next_step: StepType, tasks: Vec<Task>, no_check: bool) -> Code {
+        let now = Instant::now();
         let checks = if no_check {
             vec![]
         } else {
@@ -166,22 +259,56 @@ impl WorkloadExecutor {
                 Ok(checks) => checks,
                 Err(e) => {
                     let code = Code::TaskFailure(next_step, e);
-                    self.stats.update(self.step_id, &code);
+                    self.finish_step(&code).await;
                     code.output_logs();
                     return code;
                 }
             }
         };
         tracing::info!("Built checks for {next_step}");
+        let mut check_time = now.elapsed();
 
+        let now = Instant::now();
+        take_settlement_wait();
         let (result, fees) = self.execute(&tasks).await;
+        let settlement = take_settlement_wait();
+        let latency = self.stats.step_latency(&next_step);
+        latency
+            .execute
+            .record(now.elapsed().saturating_sub(settlement));
+        latency.settlement.record(settlement);
         self.apply_fee_payments(&fees);
 
         let execution_height = match result {
             Ok(height) => height,
             Err(e) => {
-                let code = Code::TaskFailure(next_step, e);
-                self.stats.update(self.step_id, &code);
+                // the fee could have been paid from the MASP even though the tx failed
+                let fee_checks: Vec<_> = checks
+                    .into_iter()
+                    .filter_map(|check| match check {
+                        Check::MaspFee(inner) => Some(Check::MaspFee(inner.without_transfer())),
+                        _ => None,
+                    })
+                    .collect();
+                let height = get_block_height(&self.ctx, retry_config())
+                    .await
+                    .unwrap_or_default();
+                // The check syncs the shielded context again at the current height. Only an
+                // invalid shielded tx is known not to be applied, the other failed txs could
+                // have been applied after all
+                let code = match self.checks(fee_checks, height, &fees).await {
+                    Err(check_error @ CheckError::State(_))
+                        if matches!(e, TaskError::InvalidShielded { .. }) =>
+                    {
+                        Code::Fatal(next_step, check_error)
+                    }
+                    Err(check_error @ CheckError::State(_)) => {
+                        tracing::warn!("MASP fee mismatch after the task failure: {e}");
+                        Code::CheckFailure(next_step, check_error)
+                    }
+                    _ => Code::TaskFailure(next_step, e),
+                };
+                self.finish_step(&code).await;
                 code.output_logs();
                 return code;
             }
@@ -190,21 +317,73 @@ impl WorkloadExecutor {
         tracing::info!("Execution were successful, updating state...");
         if let Err(e) = self.post_execute(&tasks, execution_height).await {
             let code = Code::TaskFailure(next_step, e);
-            self.stats.update(self.step_id, &code);
+            self.finish_step(&code).await;
             code.output_logs();
             return code;
         }
 
-        let code = match self.checks(checks, execution_height, &fees).await {
+        let now = Instant::now();
+        let result = self.checks(checks, execution_height, &fees).await;
+        if !no_check {
+            check_time += now.elapsed();
+            self.stats.step_latency(&next_step).check.record(check_time);
+        }
+        let code = match result {
             Ok(_) => Code::Success(next_step),
             Err(e) if matches!(e, CheckError::State(_)) => Code::Fatal(next_step, e),
             Err(e) => Code::CheckFailure(next_step, e),
         };
-        self.stats.update(self.step_id, &code);
+        self.finish_step(&code).await;
         code.output_logs();
         code
     }
 
+    fn record(&self, step_type: &StepType, tasks: &[Task]) {
+        let Some(path) = &self.trace else {
+            return;
+        };
+        let entry = TraceEntry {
+            step_id: self.step_id,
+            step_type: step_type.to_string(),
+            tasks: tasks.to_vec(),
+        };
+        if let Err(e) = append_trace(path, &entry) {
+            tracing::warn!("Recording the trace failed: {e}");
+        }
+    }
+
+    /// Generate the keys which were generated when building the recorded tasks
+    async fn prepare_wallet(&self, tasks: &[Task]) -> Result<(), StepError> {
+        for task in tasks {
+            let aliases = match task {
+                Task::NewWalletKeyPair(inner) => vec![inner.source().clone()],
+                Task::Batch(batch) => batch
+                    .tasks()
+                    .into_iter()
+                    .filter_map(|task| match task {
+                        Task::NewWalletKeyPair(inner) => Some(inner.source().clone()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            };
+            for alias in aliases {
+                let exists = self
+                    .ctx
+                    .namada
+                    .wallet
+                    .read()
+                    .await
+                    .find_address(&alias.name)
+                    .is_some();
+                if !exists {
+                    gen_wallet_keys(&self.ctx, &alias).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub async fn is_valid(&self, step_type: &StepType) -> Result<bool, StepError> {
         step_type.is_valid(&self.ctx, &self.state).await
     }
@@ -233,7 +412,7 @@ impl WorkloadExecutor {
     }
 
     pub async fn checks(
-        &self,
+        &mut self,
         checks: Vec<Check>,
         execution_height: Height,
         fees: &HashMap<Alias, Fee>,
@@ -249,7 +428,7 @@ impl WorkloadExecutor {
             .unwrap_or_default();
         for check in checks {
             tracing::info!("Running {check} check...");
-            check
+            let result = check
                 .do_check(
                     &self.ctx,
                     fees,
@@ -259,14 +438,18 @@ impl WorkloadExecutor {
                     },
                     retry_config,
                 )
-                .await?;
+                .await;
+            if let Err(e) = result {
+                self.failed_check = Some(check.summary());
+                return Err(e);
+            }
         }
 
         Ok(())
     }
 
     pub async fn execute(
-        &self,
+        &mut self,
         tasks: &[Task],
     ) -> (Result<Height, TaskError>, HashMap<Alias, Fee>) {
         let mut fees = HashMap::new();
@@ -277,7 +460,15 @@ impl WorkloadExecutor {
         for task in tasks {
             tracing::info!("Executing {task}...");
             let now = Instant::now();
-            execution_height = match task.execute(&self.ctx).await {
+            let settlement_before = settlement_wait();
+            let result = task.execute(&self.ctx).await;
+            let settlement = settlement_wait().saturating_sub(settlement_before);
+            let latency = self.stats.task_latency(&task.name());
+            latency
+                .execute
+                .record(now.elapsed().saturating_sub(settlement));
+            latency.settlement.record(settlement);
+            execution_height = match result {
                 Ok(height) => height,
                 Err(e) => {
                     match e {
@@ -294,6 +485,10 @@ impl WorkloadExecutor {
                 }
             };
             tracing::info!("Execution took {}s...", now.elapsed().as_secs());
+            self.stats.executed_txs += 1;
+            METRICS.executed_txs.inc();
+            METRICS.observe_height(execution_height);
+            self.stats.observe_height(execution_height);
 
             task.aggregate_fees(&mut fees, true);
         }
@@ -313,10 +508,11 @@ impl WorkloadExecutor {
             match task {
                 Task::ClaimRewards(cr) => {
                     // workaround for exact balance update after claim-rewards
+                    let denom = Alias::nam().name;
                     let (_, balance) = crate::utils::get_balance(
                         &self.ctx,
                         cr.source(),
-                        &Alias::nam().name,
+                        &denom,
                         crate::utils::retry_config(),
                     )
                     .await?;
@@ -324,7 +520,7 @@ impl WorkloadExecutor {
                         .to_string()
                         .parse()
                         .expect("Balance conversion shouldn't fail");
-                    self.state.overwrite_balance(cr.source(), balance);
+                    self.state.overwrite_balance(cr.source(), &denom, balance);
 
                     let claimed_epoch = self.fetch_epoch_at_height(execution_height).await;
                     self.state.set_claimed_epoch(cr.source(), claimed_epoch);
@@ -336,7 +532,18 @@ impl WorkloadExecutor {
                         .save()
                         .map_err(|e| TaskError::Wallet(e.to_string()))?;
                 }
-                Task::DefaultProposal(_) | Task::Vote(_) => {
+                Task::DoubleSign(ds) => {
+                    self.state
+                        .add_pending_slash(ds.validator(), ds.address(), execution_height);
+                    let jailed_epoch = self.fetch_epoch_at_height(execution_height).await;
+                    self.state
+                        .set_validator_as_jailed(ds.validator(), jailed_epoch);
+                }
+                Task::DefaultProposal(_)
+                | Task::PgfStewardProposal(_)
+                | Task::PgfFundingProposal(_)
+                | Task::WasmProposal(_)
+                | Task::Vote(_) => {
                     let last_proposal_id = self.state.proposals.keys().max().cloned();
                     let new_proposals = get_proposals(&self.ctx, last_proposal_id).await?;
                     self.state.add_proposals(new_proposals);
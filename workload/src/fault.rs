@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// What the steps should tolerate while a fault is active
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Expectation {
+    Halt,
+    NodeLag,
+    IbcTimeout,
+    MaspIndexerStall,
+}
+
+/// A fault recorded by the fault orchestrator to the timeline
+#[derive(Clone, Debug, Deserialize)]
+pub struct FaultRecord {
+    pub id: u64,
+    pub fault: serde_json::Value,
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub expectations: Vec<Expectation>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl FaultRecord {
+    /// The fault was active at some point between the start and the end
+    pub fn overlaps(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.started_at <= end && self.ended_at.is_none_or(|ended_at| start <= ended_at)
+    }
+
+    /// The failure of the step is caused by the fault
+    pub fn explains(&self, step_type: &str) -> bool {
+        self.expectations.contains(&Expectation::IbcTimeout) && step_type.starts_with("ibc-")
+    }
+
+    fn kind(&self) -> String {
+        let kind = self.fault["kind"].as_str().unwrap_or("unknown");
+        match self.fault["scenario"].as_str() {
+            Some(scenario) => format!("{kind} {scenario}"),
+            None => kind.to_string(),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} {}", self.kind(), self.targets.join(","))
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Timeline {
+    pub faults: Vec<FaultRecord>,
+}
+
+impl Timeline {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut faults = BTreeMap::new();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            let record: FaultRecord = serde_json::from_str(line).map_err(|e| e.to_string())?;
+            // The record at the end replaces the one at the start
+            faults.insert(record.id, record);
+        }
+        Ok(Self {
+            faults: faults.into_values().collect(),
+        })
+    }
+
+    pub fn active_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<&FaultRecord> {
+        self.faults
+            .iter()
+            .filter(|record| record.overlaps(start, end))
+            .collect()
+    }
+}
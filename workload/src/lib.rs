@@ -5,9 +5,15 @@ pub mod constants;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod fault;
+pub mod metrics;
+pub mod report;
+pub mod schedule;
+pub mod shrink;
 pub mod state;
 pub mod stats;
 pub mod step;
 pub mod task;
+pub mod trace;
 pub mod types;
 pub mod utils;
@@ -1,14 +1,20 @@
 use std::sync::Arc;
 use std::thread;
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use clap::Parser;
 use namada_chain_workload::code::Code;
-use namada_chain_workload::config::{AppConfig, Args};
+use namada_chain_workload::config::{AppConfig, Args, Command};
 use namada_chain_workload::context::Ctx;
 use namada_chain_workload::executor::WorkloadExecutor;
+use namada_chain_workload::fault::Timeline;
+use namada_chain_workload::metrics::serve_metrics;
+use namada_chain_workload::report::Report;
+use namada_chain_workload::schedule::Schedule;
+use namada_chain_workload::shrink::{save_trace, Shrinker};
 use namada_chain_workload::stats::summary_stats;
 use namada_chain_workload::step::StepType;
+use namada_chain_workload::trace::{load_trace, trace_path};
 use tokio::runtime::Builder;
 use tokio::time::sleep;
 use tracing::level_filters::LevelFilter;
@@ -43,15 +49,89 @@ async fn main() {
     };
     let config = Arc::new(config);
     tracing::info!("Using config: {config:#?}");
+    let schedule = match Schedule::load(&config) {
+        Ok(schedule) => schedule,
+        Err(e) => {
+            tracing::error!("Loading the schedule failed: {e}");
+            std::process::exit(4);
+        }
+    };
+    let schedule = Arc::new(schedule);
+    tracing::info!("Using profile: {}", schedule.profile);
+
+    if let Some(addr) = args.metrics_addr.or(config.metrics_addr) {
+        if let Err(e) = serve_metrics(addr).await {
+            tracing::error!("Serving metrics on {addr} failed: {e}");
+            std::process::exit(4);
+        }
+    }
 
     namada_chain_workload::utils::GLOBAL_SEED
         .set(args.seed)
         .expect("Seed already set");
+
+    if let Some(Command::Shrink {
+        trace,
+        reset_cmd,
+        output,
+    }) = args.command.clone()
+    {
+        let entries = match load_trace(&trace) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::error!("Loading the trace failed: {e}");
+                std::process::exit(4);
+            }
+        };
+        let handle = thread::spawn(move || {
+            let rt = Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("Failed to build Tokio runtime");
+            rt.block_on(async move {
+                Shrinker::new(config, schedule, reset_cmd)
+                    .shrink(entries)
+                    .await
+            })
+        });
+        let shrunk = match handle.join().expect("No error should happen") {
+            Ok(shrunk) => shrunk,
+            Err(e) => {
+                tracing::error!("Shrinking the trace failed: {e}");
+                std::process::exit(1);
+            }
+        };
+        let output = output.unwrap_or_else(|| trace.with_extension("shrunk.jsonl"));
+        save_trace(&output, &shrunk).expect("Saving the trace failed");
+        println!("==== Shrunk trace: {} steps ====", shrunk.len());
+        for entry in &shrunk {
+            println!("  - {}: {}", entry.step_id, entry.step_type);
+        }
+        println!("Saved to {}", output.display());
+        std::process::exit(0);
+    }
+
+    let started = Instant::now();
     let end_time = SystemTime::now() + Duration::from_secs(args.test_time_sec);
 
+    let trace = match &args.replay {
+        Some(path) => match load_trace(path) {
+            Ok(entries) => Some(Arc::new(entries)),
+            Err(e) => {
+                tracing::error!("Loading the trace failed: {e}");
+                std::process::exit(4);
+            }
+        },
+        None => None,
+    };
+    // Replay the trace in a single thread
+    let concurrency = if trace.is_some() { 1 } else { args.concurrency };
+
     let mut handles = Vec::new();
-    for _ in 0..args.concurrency {
+    for _ in 0..concurrency {
         let config = Arc::clone(&config);
+        let schedule = Arc::clone(&schedule);
+        let trace = trace.clone();
         let handle = thread::spawn(move || {
             let rt = Builder::new_current_thread()
                 .enable_all()
@@ -68,10 +148,18 @@ async fn main() {
                         }
                     }
                 };
-                let mut executor = WorkloadExecutor::new(ctx);
+                let mut executor = WorkloadExecutor::new(ctx, schedule);
                 let thread_id = thread::current().id();
 
-                if args.init {
+                if let Some(entries) = trace {
+                    tracing::info!("Replaying {} steps for {thread_id:?}...", entries.len());
+                    executor
+                        .init_faucet()
+                        .await
+                        .expect("Executor initialization failed");
+                    executor.replay(&entries, args.no_check).await;
+                } else if args.init {
+                    executor.record_trace(trace_path());
                     tracing::info!("Initializing accounts for {thread_id:?}...");
                     executor
                         .init_faucet()
@@ -93,9 +181,10 @@ async fn main() {
                     tracing::info!("Initialization for {thread_id:?} has been completed");
                 } else {
                     executor.load_state().expect("Loading state file failed");
+                    executor.record_trace(trace_path());
 
                     while end_time > SystemTime::now() {
-                        let next_step = StepType::random_step_type();
+                        let next_step = executor.next_step();
                         executor.reconnect(&config);
                         executor.try_step(next_step, args.no_check).await;
                     }
@@ -115,7 +204,56 @@ async fn main() {
         .into_iter()
         .map(|h| h.join().expect("No error should happen"))
         .collect();
-    let is_successful = summary_stats(results, !args.init);
+    let is_successful = summary_stats(&results, &schedule, !args.init || args.replay.is_some());
+
+    let mut report = Report::new(&results, args.seed, &schedule.profile, started.elapsed());
+    if let Some(path) = &args.fault_timeline {
+        match Timeline::load(path) {
+            Ok(timeline) => {
+                report.correlate(&timeline);
+                print_fault_report(&report);
+            }
+            Err(e) => tracing::warn!("Loading the fault timeline failed: {e}"),
+        }
+    }
+    if let Some(path) = &args.report {
+        if let Err(e) = report.save_json(path) {
+            tracing::error!("Writing the report failed: {e}");
+        }
+    }
+    if let Some(path) = &args.junit_report {
+        if let Err(e) = report.save_junit(path) {
+            tracing::error!("Writing the JUnit report failed: {e}");
+        }
+    }
 
     std::process::exit(if is_successful { 0 } else { 1 });
 }
+
+fn print_fault_report(report: &Report) {
+    let Some(faults) = &report.faults else {
+        return;
+    };
+    println!("==== Fault Breakdown ====");
+    for (fault, breakdown) in &faults.breakdown {
+        println!(
+            "  - {fault}: {} steps, expected failure {:.1}%, acceptable failure {:.1}%, unexpected failure {:.1}%",
+            breakdown.steps,
+            breakdown.expected_failure_rate * 100.0,
+            breakdown.acceptable_failure_rate * 100.0,
+            breakdown.unexpected_failure_rate * 100.0
+        );
+    }
+    println!("-- Unexpected Failures --");
+    for failure in &faults.failures {
+        let active_faults = if failure.active_faults.is_empty() {
+            "no fault".to_string()
+        } else {
+            failure.active_faults.join(", ")
+        };
+        println!(
+            "  - thread {} step {} ({}) at {} height {:?}: {active_faults}",
+            failure.thread, failure.step_id, failure.step_type, failure.finished_at, failure.height
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::CONTENT_TYPE;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::{TokioIo, TokioTimer};
+use prometheus::{Encoder, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+use tokio::net::TcpListener;
+
+use crate::code::Code;
+
+/// Idle scrapes are dropped after the timeout
+const METRICS_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    pub step_outcomes: IntCounterVec,
+    pub executed_txs: IntCounter,
+    pub retries: IntCounter,
+    pub block_height: IntGauge,
+    pub epoch: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("workload".to_string()), None)
+            .expect("Registry should be created");
+        let step_outcomes = IntCounterVec::new(
+            Opts::new("step_outcomes_total", "Step outcomes by the code type"),
+            &["step_type", "code_type"],
+        )
+        .expect("Metric should be valid");
+        let executed_txs = IntCounter::new("executed_txs_total", "Executed transactions")
+            .expect("Metric should be valid");
+        let retries = IntCounter::new("retries_total", "Retries of queries and transactions")
+            .expect("Metric should be valid");
+        let block_height = IntGauge::new("block_height", "Latest block height seen")
+            .expect("Metric should be valid");
+        let epoch = IntGauge::new("epoch", "Latest epoch seen").expect("Metric should be valid");
+
+        registry
+            .register(Box::new(step_outcomes.clone()))
+            .and_then(|_| registry.register(Box::new(executed_txs.clone())))
+            .and_then(|_| registry.register(Box::new(retries.clone())))
+            .and_then(|_| registry.register(Box::new(block_height.clone())))
+            .and_then(|_| registry.register(Box::new(epoch.clone())))
+            .expect("Metrics should be registered");
+
+        Self {
+            registry,
+            step_outcomes,
+            executed_txs,
+            retries,
+            block_height,
+            epoch,
+        }
+    }
+
+    pub fn record_outcome(&self, code: &Code) {
+        self.step_outcomes
+            .with_label_values(&[&code.step_type().to_string(), code.code_type().as_str()])
+            .inc();
+    }
+
+    pub fn observe_height(&self, height: u64) {
+        if height as i64 > self.block_height.get() {
+            self.block_height.set(height as i64);
+        }
+    }
+
+    pub fn observe_epoch(&self, epoch: u64) {
+        if epoch as i64 > self.epoch.get() {
+            self.epoch.set(epoch as i64);
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut buffer = vec![];
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Metrics should be encoded");
+        buffer
+    }
+}
+
+/// Serve the metrics at `/metrics` on the address in the background
+pub async fn serve_metrics(addr: SocketAddr) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Serving metrics on http://{addr}/metrics");
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(e) => {
+                    tracing::warn!("Accepting a metrics connection failed: {e}");
+                    continue;
+                }
+            };
+            // each connection is served separately not to block the other scrapes
+            tokio::spawn(async move {
+                let connection = http1::Builder::new()
+                    .timer(TokioTimer::new())
+                    .header_read_timeout(METRICS_READ_TIMEOUT)
+                    .serve_connection(TokioIo::new(stream), service_fn(respond));
+                if let Err(e) = connection.await {
+                    tracing::warn!("Serving metrics failed: {e}");
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+async fn respond(request: Request<Incoming>) -> Result<Response<Full<Bytes>>, Infallible> {
+    let response = if request.uri().path() == "/metrics" {
+        Response::builder()
+            .header(CONTENT_TYPE, prometheus::TEXT_FORMAT)
+            .body(Full::new(Bytes::from(METRICS.encode())))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::from_static(b"Not Found")))
+    };
+    Ok(response.expect("Response should be valid"))
+}
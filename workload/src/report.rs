@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::fault::Timeline;
+use crate::stats::{verdict, Stats, StepEntry};
+use crate::types::{Height, StepId};
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct StepCounts {
+    pub success: u64,
+    pub skip: u64,
+    pub acceptable_failure: u64,
+    pub unexpected_failure: u64,
+    pub fatal: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ThreadReport {
+    pub thread: usize,
+    pub duration_sec: f64,
+    pub steps: BTreeMap<String, StepCounts>,
+    pub fatal_failure_logs: BTreeMap<StepId, serde_json::Value>,
+    pub acceptable_failure_logs: BTreeMap<StepId, serde_json::Value>,
+    pub unexpected_failure_logs: BTreeMap<StepId, serde_json::Value>,
+    pub pre_balance_check_failures: BTreeMap<StepId, BTreeMap<String, serde_json::Value>>,
+    pub entries: Vec<StepEntry>,
+}
+
+/// A failure annotated with the faults active while the step was running
+#[derive(Clone, Debug, Serialize)]
+pub struct FaultedFailure {
+    pub thread: usize,
+    pub step_id: StepId,
+    pub step_type: String,
+    pub outcome: &'static str,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub height: Option<Height>,
+    pub active_faults: Vec<String>,
+    pub details: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct FaultBreakdown {
+    pub steps: u64,
+    pub expected_failure: u64,
+    pub acceptable_failure: u64,
+    pub unexpected_failure: u64,
+    pub expected_failure_rate: f64,
+    pub acceptable_failure_rate: f64,
+    pub unexpected_failure_rate: f64,
+}
+
+/// Failures and their rates for each fault, `none` for the steps run in steady state. The
+/// failures of the IBC steps while the relayer is isolated are expected
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct FaultReport {
+    pub failures: Vec<FaultedFailure>,
+    pub breakdown: BTreeMap<String, FaultBreakdown>,
+}
+
+/// Result of a workload run for dashboards and CI
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub seed: u64,
+    pub profile: String,
+    pub summary: String,
+    pub is_successful: bool,
+    pub duration_sec: f64,
+    pub steps: BTreeMap<String, StepCounts>,
+    pub threads: Vec<ThreadReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub faults: Option<FaultReport>,
+}
+
+impl Report {
+    pub fn new(stats: &[Stats], seed: u64, profile: &str, duration: Duration) -> Self {
+        let (summary, is_successful) = verdict(stats);
+        let threads: Vec<_> = stats
+            .iter()
+            .enumerate()
+            .map(|(thread, s)| ThreadReport::new(thread, s))
+            .collect();
+
+        let mut steps = BTreeMap::<String, StepCounts>::new();
+        for thread in &threads {
+            for (step_type, counts) in &thread.steps {
+                let total = steps.entry(step_type.clone()).or_default();
+                total.success += counts.success;
+                total.skip += counts.skip;
+                total.acceptable_failure += counts.acceptable_failure;
+                total.unexpected_failure += counts.unexpected_failure;
+                total.fatal += counts.fatal;
+            }
+        }
+
+        Self {
+            seed,
+            profile: profile.to_string(),
+            summary: summary.to_string(),
+            is_successful,
+            duration_sec: duration.as_secs_f64(),
+            steps,
+            threads,
+            faults: None,
+        }
+    }
+
+    /// Annotate the failures with the faults of the timeline
+    pub fn correlate(&mut self, timeline: &Timeline) {
+        let mut report = FaultReport::default();
+        for thread in &self.threads {
+            for entry in &thread.entries {
+                if entry.outcome == "skip" {
+                    continue;
+                }
+                let active_faults = timeline.active_between(entry.started_at, entry.finished_at);
+                let is_expected =
+                    matches!(entry.outcome, "acceptable_failure" | "unexpected_failure")
+                        && active_faults
+                            .iter()
+                            .any(|fault| fault.explains(&entry.step_type));
+
+                let mut keys: Vec<_> = active_faults.iter().map(|f| f.label()).collect();
+                keys.sort();
+                keys.dedup();
+                if keys.is_empty() {
+                    keys.push("none".to_string());
+                }
+                for key in keys {
+                    let breakdown = report.breakdown.entry(key).or_default();
+                    breakdown.steps += 1;
+                    match entry.outcome {
+                        _ if is_expected => breakdown.expected_failure += 1,
+                        "acceptable_failure" => breakdown.acceptable_failure += 1,
+                        "unexpected_failure" | "fatal" => breakdown.unexpected_failure += 1,
+                        _ => {}
+                    }
+                }
+
+                let details = match entry.outcome {
+                    _ if is_expected => continue,
+                    "unexpected_failure" => thread.unexpected_failure_logs.get(&entry.step_id),
+                    "fatal" => thread.fatal_failure_logs.get(&entry.step_id),
+                    _ => continue,
+                };
+                report.failures.push(FaultedFailure {
+                    thread: thread.thread,
+                    step_id: entry.step_id,
+                    step_type: entry.step_type.clone(),
+                    outcome: entry.outcome,
+                    started_at: entry.started_at,
+                    finished_at: entry.finished_at,
+                    height: entry.height,
+                    active_faults: active_faults.iter().map(|f| f.label()).collect(),
+                    details: details.cloned().unwrap_or_default(),
+                });
+            }
+        }
+        for breakdown in report.breakdown.values_mut() {
+            breakdown.expected_failure_rate =
+                breakdown.expected_failure as f64 / breakdown.steps as f64;
+            breakdown.acceptable_failure_rate =
+                breakdown.acceptable_failure as f64 / breakdown.steps as f64;
+            breakdown.unexpected_failure_rate =
+                breakdown.unexpected_failure as f64 / breakdown.steps as f64;
+        }
+
+        self.faults = Some(report);
+    }
+
+    pub fn save_json(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("Report should be convertible");
+        std::fs::write(path, json)
+    }
+
+    pub fn save_junit(&self, path: &Path) -> std::io::Result<()> {
+        std::fs::write(path, self.to_junit())
+    }
+
+    /// A test case for each step type and the pre-balance checks of each thread
+    pub fn to_junit(&self) -> String {
+        let mut cases = String::new();
+        let mut failures = 0;
+        let mut skipped = 0;
+
+        for (step_type, counts) in &self.steps {
+            let _ = writeln!(
+                cases,
+                r#"    <testcase classname="workload.step" name="{}">"#,
+                escape_xml(step_type)
+            );
+            let failed_logs: Vec<_> = self
+                .threads
+                .iter()
+                .flat_map(|thread| {
+                    thread
+                        .fatal_failure_logs
+                        .iter()
+                        .chain(thread.unexpected_failure_logs.iter())
+                        .filter(|(_, log)| log["step_type"] == step_type.as_str())
+                        .map(move |(id, log)| format!("thread {} step {id}: {log}", thread.thread))
+                })
+                .collect();
+            if counts.fatal + counts.unexpected_failure > 0 {
+                failures += 1;
+                let kind = if counts.fatal > 0 {
+                    "fatal"
+                } else {
+                    "unexpected"
+                };
+                let _ = writeln!(
+                    cases,
+                    r#"      <failure type="{kind}" message="fatal: {}, unexpected: {}">{}</failure>"#,
+                    counts.fatal,
+                    counts.unexpected_failure,
+                    escape_xml(&failed_logs.join("\n"))
+                );
+            } else if counts.success + counts.acceptable_failure == 0 {
+                skipped += 1;
+                let _ = writeln!(cases, r#"      <skipped message="skip: {}"/>"#, counts.skip);
+            }
+            let _ = writeln!(
+                cases,
+                "      <system-out>success: {}, skip: {}, acceptable failure: {}</system-out>",
+                counts.success, counts.skip, counts.acceptable_failure
+            );
+            let _ = writeln!(cases, "    </testcase>");
+        }
+
+        for thread in &self.threads {
+            let _ = writeln!(
+                cases,
+                r#"    <testcase classname="workload.pre-balance" name="thread-{}">"#,
+                thread.thread
+            );
+            if !thread.pre_balance_check_failures.is_empty() {
+                failures += 1;
+                let details = serde_json::to_string_pretty(&thread.pre_balance_check_failures)
+                    .expect("Details should be convertible");
+                let _ = writeln!(
+                    cases,
+                    r#"      <failure type="pre-balance" message="{} steps mismatched">{}</failure>"#,
+                    thread.pre_balance_check_failures.len(),
+                    escape_xml(&details)
+                );
+            }
+            let _ = writeln!(cases, "    </testcase>");
+        }
+
+        let tests = self.steps.len() + self.threads.len();
+        format!(
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                "\n",
+                r#"<testsuites name="namada-chain-workload" tests="{tests}" failures="{failures}" time="{time:.3}">"#,
+                "\n",
+                r#"  <testsuite name="seed-{seed}" tests="{tests}" failures="{failures}" skipped="{skipped}" time="{time:.3}">"#,
+                "\n",
+                r#"    <properties><property name="seed" value="{seed}"/><property name="profile" value="{profile}"/><property name="summary" value="{summary}"/></properties>"#,
+                "\n{cases}  </testsuite>\n</testsuites>\n"
+            ),
+            tests = tests,
+            failures = failures,
+            skipped = skipped,
+            time = self.duration_sec,
+            seed = self.seed,
+            profile = escape_xml(&self.profile),
+            summary = escape_xml(&self.summary),
+            cases = cases,
+        )
+    }
+}
+
+impl ThreadReport {
+    fn new(thread: usize, stats: &Stats) -> Self {
+        let mut steps = BTreeMap::<String, StepCounts>::new();
+        for (step_type, count) in &stats.success {
+            steps.entry(step_type.to_string()).or_default().success += count;
+        }
+        for (step_type, count) in &stats.skip {
+            steps.entry(step_type.to_string()).or_default().skip += count;
+        }
+        for (step_type, count) in &stats.acceptable_failures {
+            steps
+                .entry(step_type.to_string())
+                .or_default()
+                .acceptable_failure += count;
+        }
+        for (step_type, count) in &stats.unexpected_failures {
+            steps
+                .entry(step_type.to_string())
+                .or_default()
+                .unexpected_failure += count;
+        }
+        for (step_type, count) in &stats.fatal {
+            steps.entry(step_type.to_string()).or_default().fatal += count;
+        }
+
+        let logs = |logs: &std::collections::HashMap<StepId, String>| {
+            logs.iter()
+                .map(|(id, details)| {
+                    let details = serde_json::from_str(details)
+                        .unwrap_or_else(|_| serde_json::Value::String(details.clone()));
+                    (*id, details)
+                })
+                .collect()
+        };
+
+        Self {
+            thread,
+            duration_sec: stats.duration.as_secs_f64(),
+            steps,
+            fatal_failure_logs: logs(&stats.fatal_failure_logs),
+            acceptable_failure_logs: logs(&stats.acceptable_failure_logs),
+            unexpected_failure_logs: logs(&stats.unexpected_failure_logs),
+            pre_balance_check_failures: stats
+                .pre_balance_check_failures
+                .iter()
+                .map(|(id, details)| (*id, details.clone().into_iter().collect()))
+                .collect(),
+            entries: stats.entries.clone(),
+        }
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use strum::IntoEnumIterator;
+
+use crate::config::AppConfig;
+use crate::error::Error;
+use crate::step::StepType;
+use crate::utils::with_rng;
+
+pub const DEFAULT_STEP_WEIGHT: u64 = 1;
+
+/// Weights used to pick the next step type.
+/// A step which isn't listed in the profile has the default weight, `0` disables it. Double signs
+/// are only scheduled by a profile listing them.
+#[derive(Clone, Debug)]
+pub struct Schedule {
+    pub profile: String,
+    weights: Vec<(StepType, u64)>,
+    index: WeightedIndex<u64>,
+}
+
+impl Schedule {
+    pub fn load(config: &AppConfig) -> Result<Self, Error> {
+        let Some(profile) = &config.profile else {
+            return Self::new("uniform", HashMap::new());
+        };
+        let weights = match config.profiles.get(profile) {
+            Some(weights) => weights.clone(),
+            None => builtin_profile(profile)
+                .ok_or_else(|| Error::Config(format!("Unknown profile: {profile}")))?,
+        };
+
+        Self::new(profile, weights)
+    }
+
+    fn new(profile: &str, weights: HashMap<String, u64>) -> Result<Self, Error> {
+        let mut overrides = HashMap::new();
+        for (step, weight) in weights {
+            let step_type = StepType::from_str(&step).map_err(Error::Config)?;
+            if is_excluded(&step_type) {
+                return Err(Error::Config(format!(
+                    "{step} can't be scheduled in the profile {profile}"
+                )));
+            }
+            overrides.insert(step_type, weight);
+        }
+
+        let weights: Vec<_> = StepType::iter()
+            .filter(|step_type| !is_excluded(step_type))
+            .map(|step_type| {
+                let weight = overrides
+                    .get(&step_type)
+                    .cloned()
+                    .unwrap_or_else(|| default_weight(&step_type));
+                (step_type, weight)
+            })
+            .collect();
+        let index = WeightedIndex::new(weights.iter().map(|(_, weight)| *weight))
+            .map_err(|e| Error::Config(format!("Invalid weights for {profile}: {e}")))?;
+
+        Ok(Self {
+            profile: profile.to_string(),
+            weights,
+            index,
+        })
+    }
+
+    pub fn next_step_type(&self) -> StepType {
+        let i = with_rng(|rng| self.index.sample(rng));
+        self.weights[i].0.clone()
+    }
+
+    pub fn weights(&self) -> &[(StepType, u64)] {
+        &self.weights
+    }
+
+    /// Configured probability of each step type
+    pub fn distribution(&self) -> HashMap<String, f64> {
+        let total: u64 = self.weights.iter().map(|(_, weight)| weight).sum();
+        self.weights
+            .iter()
+            .map(|(step_type, weight)| (step_type.to_string(), *weight as f64 / total as f64))
+            .collect()
+    }
+}
+
+fn default_weight(step_type: &StepType) -> u64 {
+    match step_type {
+        StepType::DoubleSign(_) => 0,
+        _ => DEFAULT_STEP_WEIGHT,
+    }
+}
+
+fn is_excluded(step_type: &StepType) -> bool {
+    matches!(step_type, StepType::Initialize(_) | StepType::FundAll(_))
+}
+
+fn builtin_profile(profile: &str) -> Option<HashMap<String, u64>> {
+    let weights: &[(&str, u64)] = match profile {
+        "masp-heavy" => &[
+            ("shielding", 10),
+            ("shielded", 10),
+            ("unshielding", 10),
+            ("ibc-shielding-transfer", 5),
+            ("ibc-unshielding-transfer", 5),
+            ("batch-random", 3),
+        ],
+        "pos-churn" => &[
+            ("bond", 10),
+            ("unbond", 10),
+            ("redelegate", 10),
+            ("claim-rewards", 5),
+            ("become-validator", 3),
+            ("deactivate-validator", 3),
+            ("reactivate-validator", 3),
+            ("change-consensus-key", 3),
+            ("change-commission", 3),
+            ("double-sign", 1),
+            ("query-slashes", 3),
+            ("unjail-validator", 3),
+            ("batch-bond", 5),
+        ],
+        "governance" => &[
+            ("bond", 5),
+            ("become-validator", 2),
+            ("default-proposal", 3),
+            ("pgf-steward-proposal", 3),
+            ("pgf-funding-proposal", 5),
+            ("vote", 10),
+            ("query-tally", 5),
+            ("wasm-proposal", 3),
+            ("query-pgf", 5),
+            ("query-wasm-proposals", 5),
+        ],
+        "ibc-soak" => &[
+            ("ibc-transfer-send", 10),
+            ("ibc-transfer-recv", 10),
+            ("ibc-shielding-transfer", 10),
+            ("ibc-unshielding-transfer", 10),
+        ],
+        _ => return None,
+    };
+
+    Some(
+        weights
+            .iter()
+            .map(|(step, weight)| (step.to_string(), *weight))
+            .collect(),
+    )
+}
@@ -0,0 +1,234 @@
+use std::path::Path;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::sleep;
+
+use crate::code::Code;
+use crate::config::AppConfig;
+use crate::context::Ctx;
+use crate::error::CheckError;
+use crate::executor::WorkloadExecutor;
+use crate::schedule::Schedule;
+use crate::step::StepType;
+use crate::trace::{append_trace, TraceEntry, TraceError};
+use crate::utils::{base_dir, thread_id};
+
+/// Find a minimal set of steps reproducing the fatal failure of the last step
+pub struct Shrinker {
+    config: Arc<AppConfig>,
+    schedule: Arc<Schedule>,
+    reset_cmd: String,
+    attempts: u64,
+}
+
+impl Shrinker {
+    pub fn new(config: Arc<AppConfig>, schedule: Arc<Schedule>, reset_cmd: String) -> Self {
+        Self {
+            config,
+            schedule,
+            reset_cmd,
+            attempts: 0,
+        }
+    }
+
+    pub async fn shrink(&mut self, entries: Vec<TraceEntry>) -> Result<Vec<TraceEntry>, String> {
+        let Some((failing, preceding)) = entries.split_last() else {
+            return Err("The trace is empty".to_string());
+        };
+        // Steps are identified by their position since the step IDs restart with each run
+        let (mandatory, candidates): (Vec<_>, Vec<_>) =
+            (0..preceding.len()).partition(|i| is_initialization(&preceding[*i]));
+
+        let Some(target) = self.failed_check(&entries).await? else {
+            return Err("The trace doesn't end in a fatal state check failure".to_string());
+        };
+        tracing::info!("Shrinking {} steps for `{target}`", candidates.len());
+
+        // shortest prefix, the whole trace reproduces the failure
+        let (mut low, mut high) = (0, candidates.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self
+                .reproduces(preceding, failing, &mandatory, &candidates[..mid], &target)
+                .await?
+            {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        tracing::info!("Shortest prefix has {high} steps");
+
+        // delta debugging
+        let mut steps = candidates[..high].to_vec();
+        let mut n = 2;
+        while steps.len() >= 2 {
+            let chunks = split(&steps, n);
+            let mut reduced = false;
+
+            for chunk in &chunks {
+                if self
+                    .reproduces(preceding, failing, &mandatory, chunk, &target)
+                    .await?
+                {
+                    steps = chunk.clone();
+                    n = 2;
+                    reduced = true;
+                    break;
+                }
+            }
+
+            if !reduced {
+                for i in 0..chunks.len() {
+                    let complement: Vec<_> = chunks
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .flat_map(|(_, chunk)| chunk.clone())
+                        .collect();
+                    if self
+                        .reproduces(preceding, failing, &mandatory, &complement, &target)
+                        .await?
+                    {
+                        steps = complement;
+                        n = (n - 1).max(2);
+                        reduced = true;
+                        break;
+                    }
+                }
+            }
+
+            if !reduced {
+                if n >= steps.len() {
+                    break;
+                }
+                n = (n * 2).min(steps.len());
+            }
+        }
+
+        tracing::info!(
+            "Shrunk {} steps to {} steps with {} replays",
+            preceding.len() - mandatory.len(),
+            steps.len(),
+            self.attempts
+        );
+
+        Ok(with_failing(preceding, &mandatory, &steps, failing))
+    }
+
+    async fn reproduces(
+        &mut self,
+        preceding: &[TraceEntry],
+        failing: &TraceEntry,
+        mandatory: &[usize],
+        steps: &[usize],
+        target: &str,
+    ) -> Result<bool, String> {
+        let entries = with_failing(preceding, mandatory, steps, failing);
+
+        let reproduced = self
+            .failed_check(&entries)
+            .await?
+            .is_some_and(|failed_check| failed_check == target);
+        tracing::info!(
+            "Replay {} with {} steps: reproduced {reproduced}",
+            self.attempts,
+            entries.len()
+        );
+        Ok(reproduced)
+    }
+
+    /// Replay the steps on a fresh chain and return the failed check for a fatal failure
+    async fn failed_check(&mut self, entries: &[TraceEntry]) -> Result<Option<String>, String> {
+        self.attempts += 1;
+        self.reset()?;
+
+        let ctx = loop {
+            match Ctx::new(&self.config).await {
+                Ok(ctx) => break ctx,
+                Err(e) => {
+                    tracing::info!("Setup Context failed: {e}, retrying...");
+                    sleep(Duration::from_secs(2)).await;
+                }
+            }
+        };
+        let mut executor = WorkloadExecutor::new(ctx, self.schedule.clone());
+        if let Err(e) = executor.init_faucet().await {
+            tracing::warn!("Executor initialization failed: {e}");
+            return Ok(None);
+        }
+
+        match executor.replay(entries, false).await {
+            Some(Code::Fatal(_, CheckError::State(_))) => {
+                Ok(executor.failed_check().map(|check| check.to_string()))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// A replay on a chain which wasn't reset wouldn't tell anything about the steps
+    fn reset(&self) -> Result<(), String> {
+        tracing::info!("Resetting the chain: {}", self.reset_cmd);
+        match Command::new("sh").arg("-c").arg(&self.reset_cmd).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => return Err(format!("Resetting the chain exited with {status}")),
+            Err(e) => return Err(format!("Resetting the chain failed: {e}")),
+        }
+
+        // The wallet and the shielded context belong to the previous chain
+        let id = thread_id();
+        for dir in [
+            format!("wallet-{id}"),
+            format!("masp-{id}"),
+            format!("state-{id}"),
+        ] {
+            let path = base_dir().join(dir);
+            if path.exists() {
+                std::fs::remove_dir_all(&path)
+                    .map_err(|e| format!("Removing {} failed: {e}", path.display()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn save_trace(path: &Path, entries: &[TraceEntry]) -> Result<(), TraceError> {
+    if path.exists() {
+        std::fs::remove_file(path).map_err(TraceError::File)?;
+    }
+    entries
+        .iter()
+        .try_for_each(|entry| append_trace(path, entry))
+}
+
+fn is_initialization(entry: &TraceEntry) -> bool {
+    matches!(
+        entry.step_type(),
+        Ok(StepType::Initialize(_)) | Ok(StepType::FundAll(_))
+    )
+}
+
+fn with_failing(
+    preceding: &[TraceEntry],
+    mandatory: &[usize],
+    steps: &[usize],
+    failing: &TraceEntry,
+) -> Vec<TraceEntry> {
+    let mut indexes: Vec<_> = mandatory.iter().chain(steps).cloned().collect();
+    indexes.sort();
+    indexes
+        .into_iter()
+        .map(|i| preceding[i].clone())
+        .chain(std::iter::once(failing.clone()))
+        .collect()
+}
+
+fn split(steps: &[usize], n: usize) -> Vec<Vec<usize>> {
+    let chunk_size = steps.len().div_ceil(n);
+    steps
+        .chunks(chunk_size)
+        .map(|chunk| chunk.to_vec())
+        .collect()
+}
@@ -5,8 +5,8 @@ use rand::seq::IteratorRandom;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::constants::{MAX_BATCH_TX_NUM, PIPELINE_LEN};
-use crate::types::{Alias, Epoch, ProposalId};
+use crate::constants::{MAX_BATCH_TX_NUM, MIN_TRANSFER_BALANCE, PIPELINE_LEN};
+use crate::types::{Alias, Epoch, Height, Proposal, ProposalId, ProposalVote};
 use crate::utils::with_rng;
 
 #[derive(Error, Debug)]
@@ -59,13 +59,26 @@ pub struct Bond {
     pub amount: u64,
 }
 
+/// A double sign whose slash hasn't been applied to the bonds yet
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingSlash {
+    pub validator: String,
+    /// Height where the evidence was committed
+    pub height: Height,
+    /// Bonds to the validator when it double signed, the later bonds aren't slashed
+    pub bonds: HashMap<Alias, u64>,
+    /// Unbonds from the validator when it double signed, they could be slashed
+    #[serde(default)]
+    pub unbonds: HashMap<Alias, u64>,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct State {
     pub accounts: HashMap<Alias, Account>,
-    pub balances: HashMap<Alias, u64>,
-    pub masp_balances: HashMap<Alias, u64>,
-    pub ibc_balances: HashMap<Alias, HashMap<String, u64>>,
-    pub ibc_masp_balances: HashMap<Alias, HashMap<String, u64>>,
+    /// Transparent balances by the account and the token denom
+    pub balances: HashMap<Alias, HashMap<String, u64>>,
+    /// Shielded balances by the base account and the token denom
+    pub masp_balances: HashMap<Alias, HashMap<String, u64>>,
     pub foreign_balances: HashMap<Alias, u64>,
     pub bonds: HashMap<Alias, HashMap<String, (u64, Epoch)>>,
     pub unbonds: HashMap<Alias, HashMap<String, u64>>,
@@ -73,7 +86,30 @@ pub struct State {
     pub claimed_epochs: HashMap<Alias, Epoch>,
     pub validators: HashMap<Alias, Account>,
     pub deactivated_validators: HashMap<Alias, (Account, Epoch)>,
-    pub proposals: HashMap<u64, (u64, u64)>,
+    pub proposals: HashMap<ProposalId, Proposal>,
+    #[serde(default)]
+    pub jailed_validators: HashMap<Alias, (Account, Epoch)>,
+    #[serde(default)]
+    pub pending_slashes: HashMap<Alias, PendingSlash>,
+    /// Slashed amount of the bonds and unbonds by the source and the validator
+    #[serde(default)]
+    pub slashed: HashMap<Alias, HashMap<String, u64>>,
+    /// Accounts targeted by PGF proposals, they aren't used by other steps to keep their balances
+    /// exact
+    #[serde(default)]
+    pub pgf_recipients: HashMap<Alias, Account>,
+    /// Continuous funding amount and the last paid epoch by the recipient
+    #[serde(default)]
+    pub pgf_fundings: HashMap<Alias, (u64, Epoch)>,
+    /// Stewards get the inflation, so their balances aren't tracked
+    #[serde(default)]
+    pub stewards: HashMap<Alias, Account>,
+    /// Votes of the workload accounts and validators by the proposal
+    #[serde(default)]
+    pub votes: HashMap<ProposalId, HashMap<Alias, ProposalVote>>,
+    /// The last epoch when a bond, an unbond or a redelegation changed the stake of the validator
+    #[serde(default)]
+    pub stake_changes: HashMap<String, Epoch>,
 }
 
 impl State {
@@ -82,8 +118,6 @@ impl State {
             accounts: HashMap::default(),
             balances: HashMap::default(),
             masp_balances: HashMap::default(),
-            ibc_balances: HashMap::default(),
-            ibc_masp_balances: HashMap::default(),
             foreign_balances: HashMap::default(),
             bonds: HashMap::default(),
             unbonds: HashMap::default(),
@@ -92,6 +126,14 @@ impl State {
             validators: HashMap::default(),
             deactivated_validators: HashMap::default(),
             proposals: HashMap::default(),
+            jailed_validators: HashMap::default(),
+            pending_slashes: HashMap::default(),
+            slashed: HashMap::default(),
+            pgf_recipients: HashMap::default(),
+            pgf_fundings: HashMap::default(),
+            stewards: HashMap::default(),
+            votes: HashMap::default(),
+            stake_changes: HashMap::default(),
         }
     }
 
@@ -118,8 +160,8 @@ impl State {
         min_balance: u64,
     ) -> bool {
         self.masp_balances
-            .iter()
-            .filter(|(_, balance)| **balance >= min_balance)
+            .values()
+            .filter(|balances| nam_balance(balances) >= min_balance)
             .count()
             >= number_of_accounts
     }
@@ -127,7 +169,9 @@ impl State {
     pub fn at_least_account_with_min_balance(&self, sample: usize, min_balance: u64) -> bool {
         self.balances
             .iter()
-            .filter(|(_, balance)| **balance >= min_balance)
+            .filter(|(alias, balances)| {
+                nam_balance(balances) >= min_balance && self.accounts.contains_key(alias)
+            })
             .count()
             >= sample
     }
@@ -149,9 +193,57 @@ impl State {
         self.deactivated_validators.len() >= sample
     }
 
+    pub fn at_least_jailed_validator(&self, sample: usize) -> bool {
+        self.jailed_validators.len() >= sample
+    }
+
+    /// A validator can't be unbonded from or redelegated from until its slashes are processed
+    pub fn is_frozen(&self, validator: &str) -> bool {
+        self.pending_slashes
+            .values()
+            .any(|pending| pending.validator == validator)
+    }
+
+    /// The stake of the validator is exact at the epoch when no stake change was pending and it
+    /// has never been slashed
+    pub fn is_stake_tracked_at(&self, validator: &str, epoch: Epoch) -> bool {
+        // the change was requested at the epoch, but the execution could be at the next epoch
+        let is_settled = self
+            .stake_changes
+            .get(validator)
+            .is_none_or(|changed| changed + PIPELINE_LEN < epoch);
+        let is_slashed = self
+            .slashed
+            .values()
+            .any(|slashed| slashed.contains_key(validator));
+        is_settled && !is_slashed && !self.is_frozen(validator)
+    }
+
+    /// Bonded and redelegated amount of the source to the validator
+    pub fn delegation(&self, source: &Alias, validator: &str) -> u64 {
+        let bond = self
+            .bonds
+            .get(source)
+            .and_then(|bonds| bonds.get(validator))
+            .map_or(0, |(amount, _)| *amount);
+        let redelegation = self
+            .redelegations
+            .get(source)
+            .and_then(|redelegations| redelegations.get(validator))
+            .copied()
+            .unwrap_or_default();
+        bond + redelegation
+    }
+
+    pub fn is_bonded_to(&self, validator: &str) -> bool {
+        self.bonds
+            .values()
+            .any(|bonds| bonds.get(validator).is_some_and(|(amount, _)| *amount > 0))
+    }
+
     pub fn any_votable_proposal(&self, current_epoch: u64) -> bool {
-        self.proposals.iter().any(|(_, (start_epoch, end_epoch))| {
-            current_epoch >= *start_epoch && current_epoch < *end_epoch
+        self.proposals.values().any(|proposal| {
+            current_epoch >= proposal.start_epoch && current_epoch < proposal.end_epoch
         })
     }
 
@@ -175,37 +267,71 @@ impl State {
         with_rng(|rng| {
             self.masp_balances
                 .iter()
-                .filter(|(alias, balance)| !blacklist.contains(alias) && **balance >= min_value)
+                .filter(|(alias, balances)| {
+                    !blacklist.contains(alias) && nam_balance(balances) >= min_value
+                })
                 .filter_map(|(alias, _)| self.accounts.get(alias).cloned())
                 .choose(rng)
         })
     }
 
-    pub fn random_account_with_ibc_balance(&self, blacklist: Vec<Alias>) -> Option<Account> {
+    /// Pick an account and a token to transfer, NAM requires the minimum transfer balance
+    pub fn random_account_with_token_balance(
+        &self,
+        blacklist: Vec<Alias>,
+    ) -> Option<(Account, String)> {
+        with_rng(|rng| {
+            token_candidates(&self.balances, &blacklist)
+                .filter_map(|(alias, denom)| {
+                    self.accounts
+                        .get(alias)
+                        .map(|account| (account.clone(), denom.clone()))
+                })
+                .choose(rng)
+        })
+    }
+
+    /// Pick a shielded account and a token to transfer, NAM requires the minimum transfer balance
+    pub fn random_masp_account_with_token_balance(
+        &self,
+        blacklist: Vec<Alias>,
+    ) -> Option<(Account, String)> {
+        with_rng(|rng| {
+            token_candidates(&self.masp_balances, &blacklist)
+                .filter_map(|(alias, denom)| {
+                    self.accounts
+                        .get(alias)
+                        .map(|account| (account.clone(), denom.clone()))
+                })
+                .choose(rng)
+        })
+    }
+
+    pub fn random_account_with_denom_balance(
+        &self,
+        blacklist: Vec<Alias>,
+        denom: &str,
+    ) -> Option<Account> {
         with_rng(|rng| {
             self.accounts
                 .iter()
                 .filter(|(alias, _)| !blacklist.contains(alias))
-                .filter(|(alias, _)| {
-                    self.ibc_balances
-                        .get(alias)
-                        .is_some_and(|balances| balances.iter().any(|(_, b)| *b > MAX_BATCH_TX_NUM))
-                })
+                .filter(|(alias, _)| self.get_balance_for(alias, denom) > MAX_BATCH_TX_NUM)
                 .map(|(_, account)| account.clone())
                 .choose(rng)
         })
     }
 
-    pub fn random_masp_account_with_ibc_balance(&self, blacklist: Vec<Alias>) -> Option<Account> {
+    pub fn random_masp_account_with_denom_balance(
+        &self,
+        blacklist: Vec<Alias>,
+        denom: &str,
+    ) -> Option<Account> {
         with_rng(|rng| {
             self.accounts
                 .iter()
                 .filter(|(alias, _)| !blacklist.contains(alias))
-                .filter(|(alias, _)| {
-                    self.ibc_masp_balances
-                        .get(alias)
-                        .is_some_and(|balances| balances.iter().any(|(_, b)| *b > MAX_BATCH_TX_NUM))
-                })
+                .filter(|(alias, _)| self.get_shielded_balance_for(alias, denom) > MAX_BATCH_TX_NUM)
                 .map(|(_, account)| account.clone())
                 .choose(rng)
         })
@@ -282,6 +408,52 @@ impl State {
         })
     }
 
+    /// Jailed validators which can be unjailed, their slashes should have been processed
+    pub fn random_jailed_validator(
+        &self,
+        blacklist: Vec<Alias>,
+        current_epoch: Epoch,
+        sample_size: usize,
+    ) -> Vec<Account> {
+        with_rng(|rng| {
+            self.jailed_validators
+                .iter()
+                .filter(|(alias, (account, epoch))| {
+                    !blacklist.contains(alias)
+                        && !self.pending_slashes.contains_key(alias)
+                        && account.is_established()
+                        && current_epoch > *epoch
+                })
+                .choose_multiple(rng, sample_size)
+                .into_iter()
+                .map(|(_, (account, _))| account.clone())
+                .collect()
+        })
+    }
+
+    /// Accounts without any stake which can be reserved for PGF proposals
+    pub fn random_pgf_candidates(
+        &self,
+        blacklist: Vec<Alias>,
+        min_balance: u64,
+        sample_size: usize,
+    ) -> Vec<Account> {
+        with_rng(|rng| {
+            self.accounts
+                .iter()
+                .filter(|(alias, _)| {
+                    !blacklist.contains(alias)
+                        && !self.bonds.contains_key(alias)
+                        && !self.unbonds.contains_key(alias)
+                        && self.get_balance_for(alias, &Alias::nam().name) >= min_balance
+                })
+                .choose_multiple(rng, sample_size)
+                .into_iter()
+                .map(|(_, account)| account.clone())
+                .collect()
+        })
+    }
+
     pub fn random_bond(&self, current_epoch: Epoch) -> Option<Bond> {
         with_rng(|rng| {
             self.bonds
@@ -290,7 +462,10 @@ impl State {
                     bonds.iter().filter_map(|(validator, (amount, epoch))| {
                         // the bond was requested at the epoch,
                         // but the execution could be at the next epoch
-                        if *amount > 0 && current_epoch > epoch + PIPELINE_LEN {
+                        if *amount > 0
+                            && current_epoch > epoch + PIPELINE_LEN
+                            && !self.is_frozen(validator)
+                        {
                             Some(Bond {
                                 alias: source.to_owned(),
                                 validator: validator.to_owned(),
@@ -313,12 +488,13 @@ impl State {
         with_rng(|rng| {
             self.balances
                 .iter()
-                .filter_map(|(alias, balance)| {
+                .filter_map(|(alias, balances)| {
                     if blacklist.contains(alias) {
                         return None;
                     }
-                    if balance >= &min_balance {
-                        Some(self.accounts.get(alias).unwrap().clone())
+                    if nam_balance(balances) >= min_balance {
+                        // PGF recipients have balances, but they aren't available
+                        self.accounts.get(alias).cloned()
                     } else {
                         None
                     }
@@ -335,29 +511,22 @@ impl State {
         self.claimed_epochs.get(alias).cloned()
     }
 
-    pub fn get_balance_for(&self, alias: &Alias) -> u64 {
-        self.balances.get(alias).cloned().unwrap_or_default()
+    pub fn get_balance_for(&self, alias: &Alias, denom: &str) -> u64 {
+        self.balances
+            .get(alias)
+            .and_then(|balances| balances.get(denom))
+            .cloned()
+            .unwrap_or_default()
     }
 
-    pub fn get_shielded_balance_for(&self, alias: &Alias) -> u64 {
+    pub fn get_shielded_balance_for(&self, alias: &Alias, denom: &str) -> u64 {
         self.masp_balances
             .get(&alias.base())
+            .and_then(|balances| balances.get(denom))
             .cloned()
             .unwrap_or_default()
     }
 
-    pub fn get_ibc_balance_for(&self, alias: &Alias, denom: &str) -> u64 {
-        let balances = if alias.is_spending_key() || alias.is_payment_address() {
-            self.ibc_masp_balances.get(&alias.base())
-        } else {
-            self.ibc_balances.get(alias)
-        };
-        let Some(balances) = balances else {
-            return 0;
-        };
-        balances.get(denom).cloned().unwrap_or_default()
-    }
-
     pub fn get_foreign_balance_for(&self, alias: &Alias) -> u64 {
         self.foreign_balances
             .get(alias)
@@ -376,10 +545,12 @@ impl State {
         with_rng(|rng| {
             self.proposals
                 .iter()
-                .filter_map(|(proposal_id, (start_epoch, end_epoch))| {
+                .filter_map(|(proposal_id, proposal)| {
                     // the following vote will be request at the current epoch,
                     // but the execution could be at the next epoch
-                    if current_epoch >= *start_epoch && current_epoch < *end_epoch - 1 {
+                    if current_epoch >= proposal.start_epoch
+                        && current_epoch < proposal.end_epoch - 1
+                    {
                         Some(proposal_id.to_owned())
                     } else {
                         None
@@ -401,8 +572,8 @@ impl State {
                 address_type: AddressType::Implicit,
             },
         );
-        self.balances.insert(alias.clone(), 0);
-        self.masp_balances.insert(alias.clone(), 0);
+        self.balances.insert(alias.clone(), HashMap::default());
+        self.masp_balances.insert(alias.clone(), HashMap::default());
     }
 
     pub fn add_established_account(
@@ -420,7 +591,7 @@ impl State {
                 address_type: AddressType::Established,
             },
         );
-        self.balances.insert(alias.clone(), 0);
+        self.balances.insert(alias.clone(), HashMap::default());
     }
 
     pub fn modify_established_account(
@@ -435,67 +606,50 @@ impl State {
         });
     }
 
-    pub fn increase_balance(&mut self, target: &Alias, amount: u64) {
+    pub fn increase_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
         if target.is_faucet() {
             return;
         }
-        *self.balances.get_mut(target).unwrap() += amount;
-    }
-
-    pub fn increase_masp_balance(&mut self, target: &Alias, amount: u64) {
-        *self.masp_balances.get_mut(&target.base()).unwrap() += amount;
+        *self
+            .balances
+            .get_mut(target)
+            .unwrap()
+            .entry(denom.to_string())
+            .or_insert(0) += amount;
     }
 
-    pub fn increase_ibc_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
-        if target.is_faucet() {
-            return;
-        }
-        let default = HashMap::from_iter([(denom.to_string(), 0)]);
-        if target.is_spending_key() || target.is_payment_address() {
-            *self
-                .ibc_masp_balances
-                .entry(target.base())
-                .or_insert(default)
-                .entry(denom.to_string())
-                .or_insert(0) += amount;
-        } else {
-            *self
-                .ibc_balances
-                .entry(target.clone())
-                .or_insert(default)
-                .entry(denom.to_string())
-                .or_insert(0) += amount;
-        }
+    pub fn increase_masp_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
+        *self
+            .masp_balances
+            .get_mut(&target.base())
+            .unwrap()
+            .entry(denom.to_string())
+            .or_insert(0) += amount;
     }
 
     pub fn increase_foreign_balance(&mut self, target: &Alias, amount: u64) {
         *self.foreign_balances.entry(target.clone()).or_insert(0) += amount;
     }
 
-    pub fn decrease_balance(&mut self, target: &Alias, amount: u64) {
+    pub fn decrease_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
         if target.is_faucet() {
             return;
         }
-        *self.balances.get_mut(target).unwrap() -= amount;
-    }
-
-    pub fn decrease_masp_balance(&mut self, target: &Alias, amount: u64) {
-        *self.masp_balances.get_mut(&target.base()).unwrap() -= amount;
+        *self
+            .balances
+            .get_mut(target)
+            .unwrap()
+            .get_mut(denom)
+            .unwrap() -= amount;
     }
 
-    pub fn decrease_ibc_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
-        if target.is_faucet() {
-            return;
-        }
-        if target.is_spending_key() || target.is_payment_address() {
-            self.ibc_masp_balances
-                .entry(target.base())
-                .and_modify(|balance| *balance.get_mut(denom).unwrap() -= amount);
-        } else {
-            self.ibc_balances
-                .entry(target.clone())
-                .and_modify(|balance| *balance.get_mut(denom).unwrap() -= amount);
-        }
+    pub fn decrease_masp_balance(&mut self, target: &Alias, denom: &str, amount: u64) {
+        *self
+            .masp_balances
+            .get_mut(&target.base())
+            .unwrap()
+            .get_mut(denom)
+            .unwrap() -= amount;
     }
 
     pub fn decrease_foreign_balance(&mut self, target: &Alias, amount: u64) {
@@ -503,17 +657,17 @@ impl State {
     }
 
     pub fn modify_balance_fee(&mut self, source: &Alias, fee: u64) {
+        let nam = Alias::nam().name;
         if source.is_spending_key() {
-            *self.masp_balances.get_mut(&source.base()).unwrap() -= fee;
-        } else if !source.is_faucet() {
-            *self.balances.get_mut(source).unwrap() -= fee;
+            self.decrease_masp_balance(source, &nam, fee);
+        } else {
+            self.decrease_balance(source, &nam, fee);
         }
     }
 
     pub fn modify_bond(&mut self, source: &Alias, validator: &str, amount: u64, epoch: Epoch) {
-        if !source.is_faucet() {
-            *self.balances.get_mut(source).unwrap() -= amount;
-        }
+        self.stake_changes.insert(validator.to_string(), epoch);
+        self.decrease_balance(source, &Alias::nam().name, amount);
         let default = HashMap::from_iter([(validator.to_string(), (0u64, 0u64))]);
         let bond = self
             .bonds
@@ -525,7 +679,16 @@ impl State {
         bond.1 = epoch;
     }
 
-    pub fn modify_redelegate(&mut self, source: &Alias, from: &str, to: &str, amount: u64) {
+    pub fn modify_redelegate(
+        &mut self,
+        source: &Alias,
+        from: &str,
+        to: &str,
+        amount: u64,
+        epoch: Epoch,
+    ) {
+        self.stake_changes.insert(from.to_string(), epoch);
+        self.stake_changes.insert(to.to_string(), epoch);
         let default = HashMap::from_iter([(to.to_string(), 0u64)]);
         *self
             .redelegations
@@ -538,7 +701,8 @@ impl State {
             .and_modify(|bond| bond.get_mut(from).unwrap().0 -= amount);
     }
 
-    pub fn modify_unbond(&mut self, source: &Alias, validator: &str, amount: u64) {
+    pub fn modify_unbond(&mut self, source: &Alias, validator: &str, amount: u64, epoch: Epoch) {
+        self.stake_changes.insert(validator.to_string(), epoch);
         let default = HashMap::from_iter([(validator.to_string(), 0u64)]);
         *self
             .unbonds
@@ -568,10 +732,138 @@ impl State {
         self.validators.insert(alias.clone(), account);
     }
 
-    pub fn add_proposals(&mut self, new_proposals: HashMap<ProposalId, (Epoch, Epoch)>) {
+    pub fn add_pending_slash(&mut self, alias: &Alias, validator: &str, height: Height) {
+        let bonds = self
+            .bonds
+            .iter()
+            .filter_map(|(source, bonds)| {
+                bonds
+                    .get(validator)
+                    .map(|(amount, _)| (source.clone(), *amount))
+            })
+            .collect();
+        let unbonds = self
+            .unbonds
+            .iter()
+            .filter_map(|(source, unbonds)| {
+                unbonds
+                    .get(validator)
+                    .map(|amount| (source.clone(), *amount))
+            })
+            .collect();
+        self.pending_slashes.insert(
+            alias.clone(),
+            PendingSlash {
+                validator: validator.to_string(),
+                height,
+                bonds,
+                unbonds,
+            },
+        );
+    }
+
+    /// Replace the bond and the unbond with the slashed ones
+    pub fn apply_slash(&mut self, source: &Alias, validator: &str, bond: u64, unbond: u64) {
+        let mut slashed = 0;
+        if let Some((pre_bond, _)) = self
+            .bonds
+            .get_mut(source)
+            .and_then(|bonds| bonds.get_mut(validator))
+        {
+            slashed += pre_bond.saturating_sub(bond);
+            *pre_bond = bond;
+        }
+        if let Some(pre_unbond) = self
+            .unbonds
+            .get_mut(source)
+            .and_then(|unbonds| unbonds.get_mut(validator))
+        {
+            slashed += pre_unbond.saturating_sub(unbond);
+            *pre_unbond = unbond;
+        }
+        *self
+            .slashed
+            .entry(source.clone())
+            .or_default()
+            .entry(validator.to_string())
+            .or_insert(0) += slashed;
+    }
+
+    pub fn remove_pending_slash(&mut self, alias: &Alias) {
+        self.pending_slashes.remove(alias);
+    }
+
+    /// A deactivated validator can be jailed too, it's back in the validator set once unjailed
+    pub fn set_validator_as_jailed(&mut self, alias: &Alias, epoch: Epoch) {
+        let account = self.validators.remove(alias).or_else(|| {
+            self.deactivated_validators
+                .remove(alias)
+                .map(|(account, _)| account)
+        });
+        if let Some(account) = account {
+            self.jailed_validators
+                .insert(alias.clone(), (account, epoch));
+        }
+    }
+
+    pub fn unjail_validator(&mut self, alias: &Alias) {
+        if let Some((account, _)) = self.jailed_validators.remove(alias) {
+            self.validators.insert(alias.clone(), account);
+        }
+    }
+
+    pub fn add_proposals(&mut self, new_proposals: HashMap<ProposalId, Proposal>) {
         self.proposals.extend(new_proposals);
     }
 
+    pub fn add_vote(&mut self, proposal_id: ProposalId, voter: &Alias, vote: ProposalVote) {
+        self.votes
+            .entry(proposal_id)
+            .or_default()
+            .insert(voter.clone(), vote);
+    }
+
+    pub fn tally_proposal(&mut self, proposal_id: ProposalId) {
+        if let Some(proposal) = self.proposals.get_mut(&proposal_id) {
+            proposal.is_tallied = true;
+        }
+        self.votes.remove(&proposal_id);
+    }
+
+    pub fn settle_proposal(&mut self, proposal_id: ProposalId) {
+        if let Some(proposal) = self.proposals.get_mut(&proposal_id) {
+            proposal.is_settled = true;
+        }
+    }
+
+    pub fn reserve_for_pgf(&mut self, alias: &Alias) {
+        if let Some(account) = self.accounts.remove(alias) {
+            self.pgf_recipients.insert(alias.clone(), account);
+        }
+    }
+
+    /// Return the recipient to the available accounts unless it's funded continuously
+    pub fn release_from_pgf(&mut self, alias: &Alias) {
+        if self.pgf_fundings.contains_key(alias) {
+            return;
+        }
+        if let Some(account) = self.pgf_recipients.remove(alias) {
+            self.accounts.insert(alias.clone(), account);
+        }
+    }
+
+    pub fn set_pgf_funding(&mut self, alias: &Alias, amount: u64, paid_epoch: Epoch) {
+        self.pgf_fundings
+            .insert(alias.clone(), (amount, paid_epoch));
+    }
+
+    pub fn set_as_steward(&mut self, alias: &Alias) {
+        if let Some(account) = self.pgf_recipients.remove(alias) {
+            self.balances.remove(alias);
+            self.stewards.insert(alias.clone(), account);
+        }
+    }
+
     pub fn set_claimed_epoch(&mut self, source: &Alias, epoch: Epoch) {
         let claimed_epoch = self.claimed_epochs.entry(source.clone()).or_insert(0);
         if epoch > *claimed_epoch {
@@ -579,7 +871,37 @@ impl State {
         }
     }
 
-    pub fn overwrite_balance(&mut self, source: &Alias, balance: u64) {
-        *self.balances.get_mut(source).unwrap() = balance;
+    pub fn overwrite_balance(&mut self, source: &Alias, denom: &str, balance: u64) {
+        self.balances
+            .get_mut(source)
+            .unwrap()
+            .insert(denom.to_string(), balance);
     }
 }
+
+fn nam_balance(balances: &HashMap<String, u64>) -> u64 {
+    balances
+        .get(&Alias::nam().name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Pairs of the account and the denom which have enough balance to be transferred
+fn token_candidates<'a>(
+    balances: &'a HashMap<Alias, HashMap<String, u64>>,
+    blacklist: &'a [Alias],
+) -> impl Iterator<Item = (&'a Alias, &'a String)> {
+    let nam = Alias::nam().name;
+    balances
+        .iter()
+        .filter(move |(alias, _)| !blacklist.contains(alias))
+        .flat_map(|(alias, balances)| balances.iter().map(move |entry| (alias, entry)))
+        .filter(move |(_, (denom, balance))| {
+            if **denom == nam {
+                **balance >= MIN_TRANSFER_BALANCE
+            } else {
+                **balance > MAX_BATCH_TX_NUM
+            }
+        })
+        .map(|(alias, (denom, _))| (alias, denom))
+}
@@ -1,8 +1,106 @@
 use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
 use crate::code::{Code, CodeType};
+use crate::metrics::METRICS;
+use crate::schedule::Schedule;
 use crate::step::StepType;
-use crate::types::StepId;
+use crate::types::{Height, StepId};
+use crate::utils::take_retry_count;
+
+/// Latency samples in milliseconds
+#[derive(Clone, Debug, Default)]
+pub struct Latency {
+    samples: Vec<u64>,
+}
+
+impl Latency {
+    pub fn record(&mut self, duration: Duration) {
+        self.samples.push(duration.as_millis() as u64);
+    }
+
+    pub fn merge(&mut self, other: &Latency) {
+        self.samples.extend_from_slice(&other.samples);
+    }
+
+    pub fn count(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn mean(&self) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        self.samples.iter().sum::<u64>() / self.samples.len() as u64
+    }
+
+    /// Nearest-rank percentile
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.samples.is_empty() {
+            return 0;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort_unstable();
+        let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+}
+
+impl std::fmt::Display for Latency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "n={} mean={}ms p50={}ms p90={}ms p99={}ms max={}ms",
+            self.count(),
+            self.mean(),
+            self.percentile(50.0),
+            self.percentile(90.0),
+            self.percentile(99.0),
+            self.percentile(100.0)
+        )
+    }
+}
+
+/// Latencies of each phase of a step or a task
+#[derive(Clone, Debug, Default)]
+pub struct PhaseLatency {
+    pub build: Latency,
+    pub execute: Latency,
+    pub settlement: Latency,
+    pub check: Latency,
+}
+
+impl PhaseLatency {
+    pub fn merge(&mut self, other: &PhaseLatency) {
+        self.build.merge(&other.build);
+        self.execute.merge(&other.execute);
+        self.settlement.merge(&other.settlement);
+        self.check.merge(&other.check);
+    }
+
+    fn phases(&self) -> [(&'static str, &Latency); 4] {
+        [
+            ("build", &self.build),
+            ("execute", &self.execute),
+            ("settlement", &self.settlement),
+            ("check", &self.check),
+        ]
+    }
+}
+
+/// Outcome of a step with the time and the latest block height it has observed
+#[derive(Clone, Debug, Serialize)]
+pub struct StepEntry {
+    pub step_id: StepId,
+    pub step_type: String,
+    pub outcome: &'static str,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub height: Option<Height>,
+}
 
 #[derive(Clone, Debug, Default)]
 pub struct Stats {
@@ -15,10 +113,61 @@ pub struct Stats {
     pub acceptable_failure_logs: HashMap<StepId, String>,
     pub unexpected_failure_logs: HashMap<StepId, String>,
     pub pre_balance_check_failures: HashMap<StepId, HashMap<String, serde_json::Value>>,
+    pub step_latencies: HashMap<StepType, PhaseLatency>,
+    pub task_latencies: HashMap<String, PhaseLatency>,
+    pub retries: HashMap<StepType, u64>,
+    pub executed_txs: u64,
+    pub entries: Vec<StepEntry>,
+    pub last_height: Option<Height>,
+    pub step_started_at: Option<DateTime<Utc>>,
+    pub duration: Duration,
 }
 
 impl Stats {
-    pub fn update(&mut self, id: StepId, code: &Code) {
+    pub fn step_latency(&mut self, step_type: &StepType) -> &mut PhaseLatency {
+        self.step_latencies.entry(step_type.clone()).or_default()
+    }
+
+    pub fn task_latency(&mut self, task_name: &str) -> &mut PhaseLatency {
+        self.task_latencies
+            .entry(task_name.to_string())
+            .or_default()
+    }
+
+    pub fn add_retries(&mut self, step_type: &StepType, retries: u64) {
+        if retries > 0 {
+            *self.retries.entry(step_type.clone()).or_insert(0) += retries;
+        }
+    }
+
+    /// Executed transactions per second
+    pub fn throughput(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.executed_txs as f64 / self.duration.as_secs_f64()
+    }
+
+    pub fn start_step(&mut self) {
+        self.step_started_at = Some(Utc::now());
+    }
+
+    pub fn observe_height(&mut self, height: Height) {
+        self.last_height = Some(self.last_height.unwrap_or_default().max(height));
+    }
+
+    pub fn update(&mut self, id: StepId, code: &Code, height: Option<Height>) {
+        self.add_retries(code.step_type(), take_retry_count());
+        METRICS.record_outcome(code);
+        let finished_at = Utc::now();
+        self.entries.push(StepEntry {
+            step_id: id,
+            step_type: code.step_type().to_string(),
+            outcome: code.code_type().as_str(),
+            started_at: self.step_started_at.take().unwrap_or(finished_at),
+            finished_at,
+            height: height.or(self.last_height),
+        });
         match code.code_type() {
             CodeType::Success => {
                 self.success
@@ -81,6 +230,26 @@ impl std::fmt::Display for Stats {
             writeln!(f, "  - {step_type}: {count}")?;
         }
 
+        writeln!(f, "-- Latency --")?;
+        for (step_type, latency) in self.step_latencies.iter() {
+            writeln!(f, "  - {step_type}:")?;
+            for (phase, latency) in latency.phases() {
+                if latency.count() > 0 {
+                    writeln!(f, "    - {phase}: {latency}")?;
+                }
+            }
+        }
+        writeln!(f, "-- Retries --")?;
+        for (step_type, count) in self.retries.iter() {
+            writeln!(f, "  - {step_type}: {count}")?;
+        }
+        writeln!(
+            f,
+            "-- Throughput: {} txs, {:.3} tx/s --",
+            self.executed_txs,
+            self.throughput()
+        )?;
+
         writeln!(f, "----------------")?;
 
         writeln!(f, "-- Fatal Failure Logs --")?;
@@ -111,15 +280,34 @@ impl std::fmt::Display for Stats {
     }
 }
 
-pub fn summary_stats(stats: Vec<Stats>, output: bool) -> bool {
+pub fn verdict(stats: &[Stats]) -> (&'static str, bool) {
+    if stats.iter().any(|s| !s.fatal.is_empty()) {
+        ("Fatal failures happened", false)
+    } else if stats.iter().any(|s| !s.unexpected_failures.is_empty()) {
+        ("Non-fatal failures happened", false)
+    } else if stats
+        .iter()
+        .any(|s| !s.pre_balance_check_failures.is_empty())
+    {
+        ("Pre-balance check failure happened", false)
+    } else if stats.iter().all(|s| s.success.is_empty()) {
+        ("No successful transaction", false)
+    } else {
+        ("Done successfully", true)
+    }
+}
+
+pub fn summary_stats(stats: &[Stats], schedule: &Schedule, output: bool) -> bool {
     let mut success = HashMap::new();
     let mut fatal = HashMap::new();
     let mut skip = HashMap::new();
     let mut acceptable_failures = HashMap::new();
     let mut unexpected_failures = HashMap::new();
-    let all_prebalance_correct = stats
-        .iter()
-        .all(|s| s.pre_balance_check_failures.is_empty());
+    let mut step_latencies = HashMap::<String, PhaseLatency>::new();
+    let mut task_latencies = HashMap::<String, PhaseLatency>::new();
+    let mut retries = HashMap::new();
+    let mut executed_txs = 0;
+    let mut throughput = 0.0;
     for s in stats {
         for (st, v) in &s.success {
             *success.entry(st.to_string()).or_insert(0) += *v;
@@ -136,19 +324,45 @@ pub fn summary_stats(stats: Vec<Stats>, output: bool) -> bool {
         for (st, v) in &s.unexpected_failures {
             *unexpected_failures.entry(st.to_string()).or_insert(0) += *v;
         }
+        for (st, v) in &s.step_latencies {
+            step_latencies.entry(st.to_string()).or_default().merge(v);
+        }
+        for (name, v) in &s.task_latencies {
+            task_latencies.entry(name.clone()).or_default().merge(v);
+        }
+        for (st, v) in &s.retries {
+            *retries.entry(st.to_string()).or_insert(0) += *v;
+        }
+        executed_txs += s.executed_txs;
+        // threads run concurrently
+        throughput += s.throughput();
     }
 
-    let (summary, is_successful) = if !fatal.is_empty() {
-        ("Fatal failures happened", false)
-    } else if !unexpected_failures.is_empty() {
-        ("Non-fatal failures happened", false)
-    } else if !all_prebalance_correct {
-        ("Pre-balance check failure happened", false)
-    } else if success.is_empty() {
-        ("No successful transaction", false)
-    } else {
-        ("Done successfully", true)
-    };
+    let mut observed = HashMap::new();
+    for counts in [
+        &success,
+        &fatal,
+        &skip,
+        &acceptable_failures,
+        &unexpected_failures,
+    ] {
+        for (st, v) in counts {
+            *observed.entry(st.clone()).or_insert(0) += *v;
+        }
+    }
+    // Initialize, FundAll and the disabled steps aren't part of the distribution
+    let scheduled: Vec<_> = schedule
+        .weights()
+        .iter()
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(step_type, _)| step_type.to_string())
+        .collect();
+    let total_steps: u64 = scheduled
+        .iter()
+        .filter_map(|step_type| observed.get(step_type))
+        .sum();
+
+    let (summary, is_successful) = verdict(stats);
 
     if output {
         println!("==== Summary: {summary} ====");
@@ -172,6 +386,45 @@ pub fn summary_stats(stats: Vec<Stats>, output: bool) -> bool {
         for (step_type, count) in unexpected_failures.iter() {
             println!("  - {step_type}: {count}");
         }
+        println!("-- Step Latency --");
+        for (step_type, latency) in step_latencies.iter() {
+            println!("  - {step_type}:");
+            for (phase, latency) in latency.phases() {
+                if latency.count() > 0 {
+                    println!("    - {phase}: {latency}");
+                }
+            }
+        }
+        println!("-- Task Latency --");
+        for (task, latency) in task_latencies.iter() {
+            println!("  - {task}:");
+            for (phase, latency) in latency.phases() {
+                if latency.count() > 0 {
+                    println!("    - {phase}: {latency}");
+                }
+            }
+        }
+        println!("-- Retries --");
+        for (step_type, count) in retries.iter() {
+            println!("  - {step_type}: {count}");
+        }
+        println!("-- Throughput: {executed_txs} txs, {throughput:.3} tx/s --");
+        println!("-- Step Distribution ({}) --", schedule.profile);
+        let configured = schedule.distribution();
+        for step_type in &scheduled {
+            let expected = configured.get(step_type).cloned().unwrap_or_default();
+            let count = observed.get(step_type).cloned().unwrap_or_default();
+            let actual = if total_steps == 0 {
+                0.0
+            } else {
+                count as f64 / total_steps as f64
+            };
+            println!(
+                "  - {step_type}: configured {:.1}%, observed {:.1}% ({count})",
+                expected * 100.0,
+                actual * 100.0
+            );
+        }
     }
 
     is_successful
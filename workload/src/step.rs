@@ -2,40 +2,47 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 use enum_dispatch::enum_dispatch;
-use rand::prelude::IteratorRandom;
-use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::context::Ctx;
 use crate::error::StepError;
 use crate::state::State;
 use crate::task::Task;
-use crate::utils::with_rng;
 
 mod batch;
 mod become_validator;
 mod bond;
+mod change_commission;
 mod change_consensus_key;
 mod change_metadata;
 mod claim_rewards;
 mod deactivate_validator;
 mod default_proposal;
+mod double_sign;
 mod faucet_transfer;
 mod fund_all;
 mod ibc_transfer;
 mod init_account;
 mod initialize;
 mod new_wallet_keypair;
+mod pgf_funding_proposal;
+mod pgf_steward_proposal;
+mod query_pgf;
+mod query_slashes;
+mod query_tally;
+mod query_wasm_proposals;
 mod reactivate_validator;
 mod redelegate;
 mod shielded_transfer;
 mod shielding;
 mod transparent_transfer;
 mod unbond;
+mod unjail_validator;
 mod unshielding;
 mod update_account;
 mod utils;
 mod vote;
+mod wasm_proposal;
 
 #[enum_dispatch]
 #[derive(Clone, Debug, EnumIter, Eq, Hash, PartialEq)]
@@ -61,29 +68,24 @@ pub enum StepType {
     BecomeValidator(become_validator::BecomeValidator),
     DeactivateValidator(deactivate_validator::DeactivateValidator),
     ReactivateValidator(reactivate_validator::ReactivateValidator),
+    UnjailValidator(unjail_validator::UnjailValidator),
     ChangeMetadata(change_metadata::ChangeMetadata),
+    ChangeCommission(change_commission::ChangeCommission),
     ChangeConsensusKey(change_consensus_key::ChangeConsensusKey),
+    DoubleSign(double_sign::DoubleSign),
+    QuerySlashes(query_slashes::QuerySlashes),
     DefaultProposal(default_proposal::DefaultProposal),
+    PgfStewardProposal(pgf_steward_proposal::PgfStewardProposal),
+    PgfFundingProposal(pgf_funding_proposal::PgfFundingProposal),
+    QueryPgf(query_pgf::QueryPgf),
+    WasmProposal(wasm_proposal::WasmProposal),
+    QueryWasmProposals(query_wasm_proposals::QueryWasmProposals),
     Vote(vote::Vote),
+    QueryTally(query_tally::QueryTally),
     BatchBond(batch::BatchBond),
     BatchRandom(batch::BatchRandom),
 }
 
-impl StepType {
-    pub fn random_step_type() -> Self {
-        let exclude = [
-            Self::Initialize(Default::default()),
-            Self::FundAll(Default::default()),
-        ];
-        with_rng(|rng| {
-            StepType::iter()
-                .filter(|v| !exclude.contains(v))
-                .choose(rng)
-                .expect("StepType should exist")
-        })
-    }
-}
-
 impl FromStr for StepType {
     type Err = String;
 
@@ -91,11 +93,13 @@ impl FromStr for StepType {
         let step_type = match step {
             "initialize" => Self::Initialize(Default::default()),
             "fund-all" => Self::FundAll(Default::default()),
-            "new-wallet-key-pair" => Self::NewWalletKeyPair(Default::default()),
+            "new-wallet-key-pair" | "new-wallet-keypair" => {
+                Self::NewWalletKeyPair(Default::default())
+            }
             "faucet-transfer" => Self::FaucetTransfer(Default::default()),
             "transparent-transfer" => Self::TransparentTransfer(Default::default()),
             "shielding" => Self::Shielding(Default::default()),
-            "shielded" => Self::Shielded(Default::default()),
+            "shielded" | "shielded-transfer" => Self::Shielded(Default::default()),
             "unshielding" => Self::Unshielding(Default::default()),
             "ibc-transfer-send" => Self::IbcTransferSend(Default::default()),
             "ibc-transfer-recv" => Self::IbcTransferRecv(Default::default()),
@@ -110,10 +114,20 @@ impl FromStr for StepType {
             "become-validator" => Self::BecomeValidator(Default::default()),
             "deactivate-validator" => Self::DeactivateValidator(Default::default()),
             "reactivate-validator" => Self::ReactivateValidator(Default::default()),
+            "unjail-validator" => Self::UnjailValidator(Default::default()),
             "change-metadata" => Self::ChangeMetadata(Default::default()),
+            "change-commission" => Self::ChangeCommission(Default::default()),
             "change-consensus-key" => Self::ChangeConsensusKey(Default::default()),
+            "double-sign" => Self::DoubleSign(Default::default()),
+            "query-slashes" => Self::QuerySlashes(Default::default()),
             "default-proposal" => Self::DefaultProposal(Default::default()),
+            "pgf-steward-proposal" => Self::PgfStewardProposal(Default::default()),
+            "pgf-funding-proposal" => Self::PgfFundingProposal(Default::default()),
+            "query-pgf" => Self::QueryPgf(Default::default()),
+            "wasm-proposal" => Self::WasmProposal(Default::default()),
+            "query-wasm-proposals" => Self::QueryWasmProposals(Default::default()),
             "vote" => Self::Vote(Default::default()),
+            "query-tally" => Self::QueryTally(Default::default()),
             "batch-bond" => Self::BatchBond(Default::default()),
             "batch-random" => Self::BatchRandom(Default::default()),
             _ => return Err(format!("Invalid step type was given: {step}")),
@@ -6,6 +6,7 @@ use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
+use crate::types::Alias;
 use crate::utils::{get_epoch, get_validator_addresses, retry_config, with_rng};
 
 use super::utils;
@@ -26,7 +27,7 @@ impl StepContext for Bond {
         let source_account = state
             .random_account_with_min_balance(vec![], MIN_TRANSFER_BALANCE)
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
-        let amount_account = state.get_balance_for(&source_account.alias);
+        let amount_account = state.get_balance_for(&source_account.alias, &Alias::nam().name);
         let amount = utils::random_between(1, amount_account / MAX_BATCH_TX_NUM);
 
         let current_epoch = get_epoch(ctx, retry_config()).await?;
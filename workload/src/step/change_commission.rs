@@ -0,0 +1,100 @@
+use namada_sdk::dec::Dec;
+
+use crate::constants::PIPELINE_LEN;
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task, TaskSettings};
+use crate::types::CommissionChangeKind;
+use crate::utils::{get_commission_rate, get_epoch, is_validator_jailed, retry_config};
+
+use super::utils;
+
+/// Change the commission rate of a validator, sometimes with a change which should be rejected
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ChangeCommission;
+
+fn to_percent(dec: Dec) -> u64 {
+    dec.checked_mul(Dec::from(100u64))
+        .and_then(|dec| dec.to_uint())
+        .map(|percent| percent.as_u64())
+        .unwrap_or_default()
+}
+
+impl StepContext for ChangeCommission {
+    fn name(&self) -> String {
+        "change-commission".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state.at_least_validator(1))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let Some(account) = state.random_validator(vec![], 1).pop() else {
+            return Ok(vec![]);
+        };
+        let validator = ctx
+            .namada
+            .wallet
+            .read()
+            .await
+            .find_address(&account.alias.name)
+            .ok_or_else(|| {
+                StepError::Wallet(format!("No validator address: {}", account.alias.name))
+            })?
+            .to_string();
+
+        let epoch = get_epoch(ctx, retry_config()).await?;
+        if is_validator_jailed(ctx, &account.alias, epoch, retry_config()).await? {
+            return Ok(vec![]);
+        }
+
+        // The new rate is compared with the rate at the epoch before the pipeline epoch
+        let commission =
+            get_commission_rate(ctx, &validator, epoch + PIPELINE_LEN - 1, retry_config()).await?;
+        let (Some(pre_rate), Some(max_change)) = (
+            commission.commission_rate,
+            commission.max_commission_change_per_epoch,
+        ) else {
+            return Ok(vec![]);
+        };
+        let pre_percent = to_percent(pre_rate);
+        let max_change = to_percent(max_change);
+
+        let kind = match utils::random_between(0, 4) {
+            0 if pre_percent + max_change < 100 || pre_percent > max_change => {
+                CommissionChangeKind::ExceedingMaxChange
+            }
+            0 | 1 => CommissionChangeKind::AboveMaxRate,
+            _ => CommissionChangeKind::Valid,
+        };
+        let rate = match kind {
+            CommissionChangeKind::Valid => utils::random_between(
+                pre_percent.saturating_sub(max_change),
+                (pre_percent + max_change).min(100),
+            ),
+            CommissionChangeKind::ExceedingMaxChange if pre_percent + max_change < 100 => {
+                pre_percent + max_change + 1
+            }
+            CommissionChangeKind::ExceedingMaxChange => pre_percent - max_change - 1,
+            CommissionChangeKind::AboveMaxRate => utils::random_between(101, 200),
+        };
+        let rate = Dec::new(rate as i128, 2).unwrap();
+
+        let gas_payer = utils::get_gas_payer(account.public_keys.iter(), state);
+        let task_settings = TaskSettings::new(account.public_keys, gas_payer);
+
+        Ok(vec![Task::ChangeCommission(
+            task::change_commission::ChangeCommission::builder()
+                .source(account.alias)
+                .validator(validator)
+                .pre_rate(pre_rate)
+                .rate(rate)
+                .kind(kind)
+                .settings(task_settings)
+                .build(),
+        )])
+    }
+}
@@ -3,6 +3,7 @@ use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
+use crate::utils::{get_epoch, is_validator_jailed, retry_config};
 
 use super::utils;
 
@@ -18,8 +19,12 @@ impl StepContext for ChangeConsensusKey {
         Ok(state.at_least_validator(1))
     }
 
-    async fn build_task(&self, _ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let account = state.random_validator(vec![], 1).pop().unwrap();
+        let epoch = get_epoch(ctx, retry_config()).await?;
+        if is_validator_jailed(ctx, &account.alias, epoch, retry_config()).await? {
+            return Ok(vec![]);
+        }
 
         let random_alias = utils::random_alias();
         let consensus_key_alias = format!("{}-consensus", random_alias.name);
@@ -3,7 +3,7 @@ use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
-use crate::utils::{get_epoch, retry_config};
+use crate::utils::{get_epoch, is_validator_jailed, retry_config};
 
 use super::utils;
 
@@ -23,6 +23,10 @@ impl StepContext for DeactivateValidator {
         let account = state.random_validator(vec![], 1).pop().unwrap();
 
         let epoch = get_epoch(ctx, retry_config()).await?;
+        // The jailing is found by the query-slashes step later
+        if is_validator_jailed(ctx, &account.alias, epoch, retry_config()).await? {
+            return Ok(vec![]);
+        }
 
         let gas_payer = utils::get_gas_payer(account.public_keys.iter(), state);
         let task_settings = TaskSettings::new(account.public_keys, gas_payer);
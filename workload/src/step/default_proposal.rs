@@ -1,5 +1,3 @@
-use namada_sdk::rpc;
-
 use crate::constants::PROPOSAL_DEPOSIT;
 use crate::context::Ctx;
 use crate::error::StepError;
@@ -23,27 +21,14 @@ impl StepContext for DefaultProposal {
     }
 
     async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
-        let client = &ctx.namada.client;
         let source_account = state
             .random_account_with_min_balance(vec![], PROPOSAL_DEPOSIT)
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
 
         let current_epoch = get_epoch(ctx, retry_config()).await?;
 
-        let gov_prams = rpc::query_governance_parameters(client).await;
-
-        let start_epoch = utils::random_between(
-            current_epoch + 2,
-            current_epoch + gov_prams.max_proposal_latency,
-        );
-        let end_epoch = utils::random_between(
-            start_epoch + gov_prams.min_proposal_voting_period,
-            start_epoch + gov_prams.max_proposal_period - 5,
-        );
-        let grace_epoch = utils::random_between(
-            end_epoch + gov_prams.min_proposal_grace_epochs,
-            end_epoch + 5,
-        );
+        let (start_epoch, end_epoch, grace_epoch) =
+            utils::random_proposal_epochs(ctx, current_epoch).await;
 
         let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
         let task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
@@ -0,0 +1,69 @@
+use rand::seq::IteratorRandom;
+
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task};
+use crate::utils::{
+    get_epoch, get_validator_addresses, is_validator_jailed, retry_config, with_rng,
+};
+
+/// Double sign with the consensus key of a validator owned by the workload which is in the
+/// consensus set
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DoubleSign;
+
+impl StepContext for DoubleSign {
+    fn name(&self) -> String {
+        "double-sign".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state.at_least_validator(1))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let current_epoch = get_epoch(ctx, retry_config()).await?;
+        let consensus_validators: Vec<String> = get_validator_addresses(ctx, retry_config())
+            .await?
+            .into_iter()
+            .map(|address| address.to_string())
+            .collect();
+
+        let wallet = ctx.namada.wallet.read().await;
+        let mut candidates = vec![];
+        for account in state.validators.values() {
+            if state.pending_slashes.contains_key(&account.alias) {
+                continue;
+            }
+            let address = wallet
+                .find_address(&account.alias.name)
+                .ok_or_else(|| {
+                    StepError::Wallet(format!("No validator address: {}", account.alias.name))
+                })?
+                .to_string();
+            if consensus_validators.contains(&address) {
+                candidates.push((account.alias.clone(), address));
+            }
+        }
+        drop(wallet);
+
+        let mut signers = vec![];
+        for (alias, address) in candidates {
+            if !is_validator_jailed(ctx, &alias, current_epoch, retry_config()).await? {
+                signers.push((alias, address));
+            }
+        }
+        let Some((validator, address)) = with_rng(|rng| signers.into_iter().choose(rng)) else {
+            return Ok(vec![]);
+        };
+
+        Ok(vec![Task::DoubleSign(
+            task::double_sign::DoubleSign::builder()
+                .validator(validator)
+                .address(address)
+                .build(),
+        )])
+    }
+}
@@ -1,9 +1,15 @@
+use rand::seq::IteratorRandom;
+
 use crate::constants::FAUCET_AMOUNT;
 use crate::context::Ctx;
 use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
+use crate::types::Alias;
+use crate::utils::with_rng;
+
+use super::utils;
 
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct FaucetTransfer;
@@ -17,16 +23,23 @@ impl StepContext for FaucetTransfer {
         Ok(true)
     }
 
-    async fn build_task(&self, _ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let target_account = state
             .random_account(vec![])
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
+        // NAM is needed for the fees, the extra tokens are funded less often
+        let denom = if utils::coin_flip(0.5) {
+            Alias::nam().name
+        } else {
+            with_rng(|rng| ctx.tokens.iter().choose(rng).cloned()).unwrap_or(Alias::nam().name)
+        };
 
         let task_settings = TaskSettings::faucet();
 
         Ok(vec![Task::FaucetTransfer(
             task::faucet_transfer::FaucetTransfer::builder()
                 .target(target_account.alias)
+                .denom(denom)
                 .amount(FAUCET_AMOUNT)
                 .settings(task_settings)
                 .build(),
@@ -17,14 +17,16 @@ impl StepContext for FundAll {
         Ok(!state.accounts.is_empty())
     }
 
-    async fn build_task(&self, _ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let tasks: Vec<_> = state
             .accounts
             .keys()
-            .map(|alias| {
+            .flat_map(|alias| ctx.tokens.iter().map(move |denom| (alias, denom)))
+            .map(|(alias, denom)| {
                 Task::FaucetTransfer(
                     task::faucet_transfer::FaucetTransfer::builder()
                         .target(alias.clone())
+                        .denom(denom.clone())
                         .amount(FAUCET_AMOUNT)
                         .settings(TaskSettings::faucet())
                         .build(),
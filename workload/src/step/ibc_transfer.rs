@@ -10,7 +10,7 @@ use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
 use crate::types::Alias;
-use crate::utils::{get_masp_epoch, ibc_denom, is_native_denom, retry_config};
+use crate::utils::{get_masp_epoch, ibc_denom, retry_config};
 
 use super::utils;
 
@@ -28,7 +28,10 @@ impl StepContext for IbcTransferSend {
 
     async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let (source_account, denom) = state
-            .random_account_with_ibc_balance(vec![])
+            .random_account_with_denom_balance(
+                vec![],
+                &ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN),
+            )
             .filter(|_| utils::coin_flip(0.5))
             .map(|account| (account, ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN)))
             .or_else(|| {
@@ -38,11 +41,7 @@ impl StepContext for IbcTransferSend {
             })
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
         let target_account = ctx.cosmos.account.to_string();
-        let balance = if is_native_denom(&denom) {
-            state.get_balance_for(&source_account.alias)
-        } else {
-            state.get_ibc_balance_for(&source_account.alias, &denom)
-        };
+        let balance = state.get_balance_for(&source_account.alias, &denom);
         let amount = utils::random_between(1, balance / MAX_BATCH_TX_NUM);
 
         let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
@@ -166,7 +165,10 @@ impl StepContext for IbcUnshieldingTransfer {
 
     async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let Some((source_account, denom)) = state
-            .random_masp_account_with_ibc_balance(vec![])
+            .random_masp_account_with_denom_balance(
+                vec![],
+                &ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN),
+            )
             .filter(|_| utils::coin_flip(0.5))
             .map(|account| (account, ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN)))
             .or_else(|| {
@@ -178,16 +180,13 @@ impl StepContext for IbcUnshieldingTransfer {
             return Ok(vec![]);
         };
         let target_account = ctx.cosmos.account.to_string();
-        let balance = if is_native_denom(&denom) {
-            state.get_shielded_balance_for(&source_account.alias)
-        } else {
-            state.get_ibc_balance_for(&source_account.alias.spending_key(), &denom)
-        };
+        let balance = state.get_shielded_balance_for(&source_account.alias, &denom);
         let amount = utils::random_between(1, balance / MAX_BATCH_TX_NUM);
 
-        let transparent_source_balance = state.get_balance_for(&source_account.alias.base());
+        let nam = Alias::nam().name;
+        let transparent_source_balance = state.get_balance_for(&source_account.alias.base(), &nam);
         let shielded_source_balance =
-            state.get_shielded_balance_for(&source_account.alias.spending_key());
+            state.get_shielded_balance_for(&source_account.alias.spending_key(), &nam);
         if transparent_source_balance < DEFAULT_FEE && shielded_source_balance < DEFAULT_FEE {
             // Insufficient balance for the fee
             return Ok(vec![]);
@@ -84,6 +84,7 @@ impl StepContext for Initialize {
                 Task::FaucetTransfer(
                     task::faucet_transfer::FaucetTransfer::builder()
                         .target(alias.clone())
+                        .denom(Alias::nam().name)
                         .amount(FAUCET_AMOUNT)
                         .settings(task_settings.clone())
                         .build(),
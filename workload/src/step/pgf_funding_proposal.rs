@@ -0,0 +1,72 @@
+use crate::constants::{DEFAULT_FEE, MAX_PGF_FUNDING, PROPOSAL_DEPOSIT};
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task, TaskSettings};
+use crate::utils::{get_epoch, retry_config};
+
+use super::utils;
+
+/// Propose continuous and retroactive PGF fundings to the workload accounts
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PgfFundingProposal;
+
+impl StepContext for PgfFundingProposal {
+    fn name(&self) -> String {
+        "pgf-funding-proposal".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(!state
+            .random_pgf_candidates(vec![], PROPOSAL_DEPOSIT + DEFAULT_FEE, 1)
+            .is_empty())
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let Some(source_account) = state
+            .random_pgf_candidates(vec![], PROPOSAL_DEPOSIT + DEFAULT_FEE, 1)
+            .pop()
+        else {
+            return Ok(vec![]);
+        };
+        let recipients = state.random_pgf_candidates(
+            vec![source_account.alias.clone()],
+            0,
+            utils::random_between(1, 3),
+        );
+        if recipients.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut continuous = vec![];
+        let mut retro = vec![];
+        for recipient in recipients {
+            let amount = utils::random_between(1, MAX_PGF_FUNDING);
+            if utils::coin_flip(0.5) {
+                continuous.push((recipient.alias, amount));
+            } else {
+                retro.push((recipient.alias, amount));
+            }
+        }
+
+        let current_epoch = get_epoch(ctx, retry_config()).await?;
+        let (start_epoch, end_epoch, grace_epoch) =
+            utils::random_proposal_epochs(ctx, current_epoch).await;
+
+        let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
+        let task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
+
+        Ok(vec![Task::PgfFundingProposal(
+            task::pgf_funding_proposal::PgfFundingProposal::builder()
+                .source(source_account.alias)
+                .continuous(continuous)
+                .retro(retro)
+                .start_epoch(start_epoch)
+                .end_epoch(end_epoch)
+                .grace_epoch(grace_epoch)
+                .settings(task_settings)
+                .build(),
+        )])
+    }
+}
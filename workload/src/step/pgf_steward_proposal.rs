@@ -0,0 +1,51 @@
+use crate::constants::{DEFAULT_FEE, PROPOSAL_DEPOSIT};
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task, TaskSettings};
+use crate::utils::{get_epoch, retry_config};
+
+use super::utils;
+
+/// Propose the author itself as a new PGF steward
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct PgfStewardProposal;
+
+impl StepContext for PgfStewardProposal {
+    fn name(&self) -> String {
+        "pgf-steward-proposal".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(!state
+            .random_pgf_candidates(vec![], PROPOSAL_DEPOSIT + DEFAULT_FEE, 1)
+            .is_empty())
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let Some(source_account) = state
+            .random_pgf_candidates(vec![], PROPOSAL_DEPOSIT + DEFAULT_FEE, 1)
+            .pop()
+        else {
+            return Ok(vec![]);
+        };
+
+        let current_epoch = get_epoch(ctx, retry_config()).await?;
+        let (start_epoch, end_epoch, grace_epoch) =
+            utils::random_proposal_epochs(ctx, current_epoch).await;
+
+        let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
+        let task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
+
+        Ok(vec![Task::PgfStewardProposal(
+            task::pgf_steward_proposal::PgfStewardProposal::builder()
+                .source(source_account.alias)
+                .start_epoch(start_epoch)
+                .end_epoch(end_epoch)
+                .grace_epoch(grace_epoch)
+                .settings(task_settings)
+                .build(),
+        )])
+    }
+}
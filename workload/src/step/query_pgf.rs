@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::constants::PROPOSAL_DEPOSIT;
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::query_pgf::PgfPayment;
+use crate::task::{self, Task};
+use crate::types::{Alias, Epoch, ProposalKind};
+use crate::utils::{get_epoch, get_proposal_result, retry_config};
+
+/// Settle the activated PGF proposals and collect the PGF payments to the workload accounts
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QueryPgf;
+
+fn payment<'a>(
+    payments: &'a mut HashMap<Alias, PgfPayment>,
+    state: &State,
+    target: &Alias,
+    from_epoch: Epoch,
+) -> &'a mut PgfPayment {
+    payments.entry(target.clone()).or_insert_with(|| {
+        PgfPayment::builder()
+            .target(target.clone())
+            .pre_balance(state.get_balance_for(target, &Alias::nam().name))
+            .from_epoch(from_epoch)
+            .build()
+    })
+}
+
+impl StepContext for QueryPgf {
+    fn name(&self) -> String {
+        "query-pgf".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(!state.pgf_fundings.is_empty()
+            || state
+                .proposals
+                .values()
+                .any(|proposal| proposal.kind.is_pgf() && !proposal.is_settled))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let epoch = get_epoch(ctx, retry_config()).await?;
+
+        let mut payments: HashMap<Alias, PgfPayment> = HashMap::new();
+        for (target, (amount, paid_epoch)) in &state.pgf_fundings {
+            if *paid_epoch < epoch {
+                payment(&mut payments, state, target, paid_epoch + 1).amount = *amount;
+            }
+        }
+
+        let mut settled = vec![];
+        let mut stewards = vec![];
+        for (proposal_id, proposal) in &state.proposals {
+            if !proposal.kind.is_pgf() || proposal.is_settled || proposal.activation_epoch > epoch {
+                continue;
+            }
+            let Some(is_passed) = get_proposal_result(ctx, *proposal_id, retry_config()).await?
+            else {
+                continue;
+            };
+            settled.push((*proposal_id, is_passed));
+
+            let is_owned = |alias: &Alias| state.pgf_recipients.contains_key(alias);
+            match &proposal.kind {
+                ProposalKind::PgfSteward {
+                    steward: Some(steward),
+                } if is_owned(steward) => {
+                    // no refund since the balance of a new steward isn't tracked
+                    stewards.push((steward.clone(), is_passed));
+                    continue;
+                }
+                ProposalKind::PgfFunding { continuous, retro } if is_passed => {
+                    for (target, amount) in continuous.iter().filter(|(t, _)| is_owned(t)) {
+                        payment(&mut payments, state, target, proposal.activation_epoch).amount =
+                            *amount;
+                    }
+                    for (target, amount) in retro.iter().filter(|(t, _)| is_owned(t)) {
+                        payment(&mut payments, state, target, epoch).one_off += amount;
+                    }
+                }
+                _ => {}
+            }
+            // the deposit is refunded when the proposal passed
+            if is_passed && is_owned(&proposal.author) {
+                payment(&mut payments, state, &proposal.author, epoch).one_off += PROPOSAL_DEPOSIT;
+            }
+        }
+
+        if settled.is_empty() && payments.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Task::QueryPgf(
+            task::query_pgf::QueryPgf::builder()
+                .epoch(epoch)
+                .settled(settled)
+                .payments(payments.into_values().collect())
+                .stewards(stewards)
+                .build(),
+        )])
+    }
+}
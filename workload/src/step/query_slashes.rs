@@ -0,0 +1,154 @@
+use std::collections::HashSet;
+
+use namada_sdk::dec::Dec;
+use namada_sdk::proof_of_stake::types::ValidatorState;
+
+use crate::constants::EVIDENCE_WAIT_BLOCKS;
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task};
+use crate::types::Amount;
+use crate::utils::{
+    get_bond, get_epoch, get_unbonds, get_validator_slashes, get_validator_state, retry_config,
+};
+
+/// Find the validators jailed by the chain, e.g. for the downtime, and the processed slashes of
+/// the double signs to update the bonds and the unbonds
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QuerySlashes;
+
+impl StepContext for QuerySlashes {
+    fn name(&self) -> String {
+        "query-slashes".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state.at_least_validator(1)
+            || state.at_least_deactivated_validator(1)
+            || !state.pending_slashes.is_empty())
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let epoch = get_epoch(ctx, retry_config()).await?;
+
+        let mut jailed = vec![];
+        for alias in state
+            .validators
+            .keys()
+            .chain(state.deactivated_validators.keys())
+        {
+            let (_, (validator_state, _)) =
+                get_validator_state(ctx, alias, epoch, retry_config()).await?;
+            if matches!(validator_state, Some(ValidatorState::Jailed)) {
+                jailed.push(alias.clone());
+            }
+        }
+
+        let mut slashed = vec![];
+        let mut bonds = vec![];
+        for (alias, pending) in &state.pending_slashes {
+            let processed: Vec<_> = get_validator_slashes(ctx, &pending.validator, retry_config())
+                .await?
+                .into_iter()
+                .filter(|slash| {
+                    slash.block_height <= pending.height
+                        && slash.block_height + EVIDENCE_WAIT_BLOCKS >= pending.height
+                })
+                .collect();
+            let Some(infraction_epoch) = processed.first().map(|slash| slash.epoch.into()) else {
+                continue;
+            };
+            let rate = processed
+                .iter()
+                .map(|slash| slash.rate)
+                .try_fold(Dec::zero(), |acc, rate| acc.checked_add(rate))
+                .unwrap_or(Dec::one())
+                .min(Dec::one());
+
+            let sources: HashSet<_> = state
+                .bonds
+                .iter()
+                .filter(|(_, bonds)| bonds.contains_key(&pending.validator))
+                .map(|(source, _)| source.clone())
+                .chain(
+                    state
+                        .unbonds
+                        .iter()
+                        .filter(|(_, unbonds)| unbonds.contains_key(&pending.validator))
+                        .map(|(source, _)| source.clone()),
+                )
+                .collect();
+            for source in sources {
+                let pre_bond = state
+                    .bonds
+                    .get(&source)
+                    .and_then(|bonds| bonds.get(&pending.validator))
+                    .map(|(amount, _)| *amount)
+                    .unwrap_or_default();
+                let pre_unbond = state
+                    .unbonds
+                    .get(&source)
+                    .and_then(|unbonds| unbonds.get(&pending.validator))
+                    .cloned()
+                    .unwrap_or_default();
+                // Only the stake at the infraction is slashable
+                let slashable_bond = pending
+                    .bonds
+                    .get(&source)
+                    .cloned()
+                    .unwrap_or_default()
+                    .min(pre_bond);
+                let slashable_unbond = pending.unbonds.get(&source).cloned().unwrap_or_default();
+                let post_bond: Amount =
+                    get_bond(ctx, &source, &pending.validator, epoch, retry_config())
+                        .await?
+                        .to_string()
+                        .parse()
+                        .expect("Amount conversion shouldn't fail");
+                let post_unbond: Amount =
+                    get_unbonds(ctx, &source, &pending.validator, retry_config())
+                        .await?
+                        .to_string()
+                        .parse()
+                        .expect("Amount conversion shouldn't fail");
+
+                bonds.push(
+                    task::query_slashes::SlashedBond::builder()
+                        .source(source)
+                        .validator(pending.validator.clone())
+                        .rate(rate)
+                        .pre_bond(pre_bond)
+                        .slashable_bond(slashable_bond)
+                        .post_bond(post_bond)
+                        .pre_unbond(pre_unbond)
+                        .slashable_unbond(slashable_unbond)
+                        .post_unbond(post_unbond)
+                        .build(),
+                );
+            }
+            slashed.push(
+                task::query_slashes::ProcessedSlash::builder()
+                    .target(alias.clone())
+                    .validator(pending.validator.clone())
+                    .infraction_epoch(infraction_epoch)
+                    .rate(rate)
+                    .build(),
+            );
+        }
+
+        if jailed.is_empty() && slashed.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Task::QuerySlashes(
+            task::query_slashes::QuerySlashes::builder()
+                .jailed(jailed)
+                .slashed(slashed)
+                .bonds(bonds)
+                .epoch(epoch)
+                .build(),
+        )])
+    }
+}
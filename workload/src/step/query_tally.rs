@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use namada_sdk::address::Address;
+use namada_sdk::governance::utils::Vote;
+
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::query_tally::ExpectedTally;
+use crate::task::{self, Task};
+use crate::types::{Alias, Epoch, ProposalId, ProposalVote};
+use crate::utils::{
+    get_epoch, get_proposal_tally, get_proposal_votes, get_validator_stake, is_active_validator,
+    retry_config,
+};
+
+/// Recompute the tally of the proposals voted by the workload from the bonds
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QueryTally;
+
+impl StepContext for QueryTally {
+    fn name(&self) -> String {
+        "query-tally".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state
+            .votes
+            .keys()
+            .filter_map(|proposal_id| state.proposals.get(proposal_id))
+            .any(|proposal| !proposal.is_tallied))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let epoch = get_epoch(ctx, retry_config()).await?;
+
+        let mut tallies = vec![];
+        let mut untracked = vec![];
+        for proposal_id in state.votes.keys() {
+            let Some(proposal) = state.proposals.get(proposal_id) else {
+                continue;
+            };
+            if proposal.is_tallied || proposal.activation_epoch > epoch {
+                continue;
+            }
+            if get_proposal_tally(ctx, *proposal_id, retry_config())
+                .await?
+                .is_none()
+            {
+                continue;
+            }
+            let votes = get_proposal_votes(ctx, *proposal_id, retry_config()).await?;
+            match expected_tally(ctx, state, *proposal_id, proposal.end_epoch, &votes).await? {
+                Some(tally) => tallies.push(tally),
+                None => untracked.push(*proposal_id),
+            }
+        }
+
+        if tallies.is_empty() && untracked.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Task::QueryTally(
+            task::query_tally::QueryTally::builder()
+                .tallies(tallies)
+                .untracked(untracked)
+                .build(),
+        )])
+    }
+}
+
+/// Tally the votes with the stakes at the end epoch like the ledger does: a validator votes with
+/// its whole stake on chain and the delegator voting differently moves its bond to its own vote.
+/// `None` when the state can't tell the bonds of the delegators exactly.
+async fn expected_tally(
+    ctx: &Ctx,
+    state: &State,
+    proposal_id: ProposalId,
+    end_epoch: Epoch,
+    votes: &[Vote],
+) -> Result<Option<ExpectedTally>, StepError> {
+    let mut is_active: HashMap<Address, bool> = HashMap::new();
+    let mut validator_votes: HashMap<String, ProposalVote> = HashMap::new();
+    let mut delegations = vec![];
+    let mut tally = ExpectedTally::builder().proposal_id(proposal_id).build();
+
+    for vote in votes {
+        let validator = vote.validator.to_string();
+        if !is_active.contains_key(&vote.validator) {
+            let active =
+                is_active_validator(ctx, &vote.validator, end_epoch, retry_config()).await?;
+            is_active.insert(vote.validator.clone(), active);
+        }
+        if !is_active[&vote.validator] {
+            continue;
+        }
+
+        if vote.is_validator() {
+            // The stake includes the bonds of the other threads
+            let stake =
+                get_validator_stake(ctx, &vote.validator, end_epoch, retry_config()).await?;
+            *tally.power_mut(&vote.data) += stake;
+            validator_votes.insert(validator, vote.data.clone());
+        } else {
+            if !state.is_stake_tracked_at(&validator, end_epoch) {
+                return Ok(None);
+            }
+            let Some(voter) = ctx
+                .namada
+                .wallet
+                .read()
+                .await
+                .find_alias(&vote.delegator)
+                .map(|alias| Alias::from(alias.to_string()))
+            else {
+                return Ok(None);
+            };
+            let power = state.delegation(&voter, &validator);
+            delegations.push((validator, vote.data.clone(), power));
+        }
+    }
+
+    for (validator, vote, power) in delegations {
+        match validator_votes.get(&validator) {
+            Some(validator_vote) if *validator_vote == vote => {}
+            Some(validator_vote) => {
+                let validator_power = tally.power_mut(validator_vote);
+                *validator_power = validator_power.saturating_sub(power);
+                *tally.power_mut(&vote) += power;
+            }
+            None => *tally.power_mut(&vote) += power,
+        }
+    }
+
+    Ok(Some(tally))
+}
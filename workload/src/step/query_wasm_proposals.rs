@@ -0,0 +1,56 @@
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task};
+use crate::types::ProposalKind;
+use crate::utils::{get_epoch, get_proposal_result, retry_config};
+
+/// Settle the activated proposals with the workload code
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QueryWasmProposals;
+
+impl StepContext for QueryWasmProposals {
+    fn name(&self) -> String {
+        "query-wasm-proposals".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state.proposals.values().any(|proposal| {
+            matches!(
+                proposal.kind,
+                ProposalKind::DefaultWithWasm { marker: Some(_) }
+            ) && !proposal.is_settled
+        }))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let epoch = get_epoch(ctx, retry_config()).await?;
+
+        let mut settled = vec![];
+        for (proposal_id, proposal) in &state.proposals {
+            let ProposalKind::DefaultWithWasm {
+                marker: Some(marker),
+            } = proposal.kind
+            else {
+                continue;
+            };
+            if proposal.is_settled || proposal.activation_epoch > epoch {
+                continue;
+            }
+            if let Some(is_passed) = get_proposal_result(ctx, *proposal_id, retry_config()).await? {
+                settled.push((*proposal_id, marker, is_passed));
+            }
+        }
+
+        if settled.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(vec![Task::QueryWasmProposals(
+            task::query_wasm_proposals::QueryWasmProposals::builder()
+                .settled(settled)
+                .build(),
+        )])
+    }
+}
@@ -3,7 +3,7 @@ use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
-use crate::utils::{get_epoch, retry_config};
+use crate::utils::{get_epoch, is_validator_jailed, retry_config};
 
 use super::utils;
 
@@ -24,6 +24,9 @@ impl StepContext for ReactivateValidator {
         let Some(account) = state.random_deactivated_validator(vec![], epoch, 1).pop() else {
             return Ok(vec![]);
         };
+        if is_validator_jailed(ctx, &account.alias, epoch, retry_config()).await? {
+            return Ok(vec![]);
+        }
 
         let gas_payer = utils::get_gas_payer(account.public_keys.iter(), state);
         let task_settings = TaskSettings::new(account.public_keys, gas_payer);
@@ -1,6 +1,6 @@
 use std::collections::BTreeSet;
 
-use crate::constants::{COSMOS_TOKEN, DEFAULT_FEE, MAX_BATCH_TX_NUM, MIN_TRANSFER_BALANCE};
+use crate::constants::{DEFAULT_FEE, MAX_BATCH_TX_NUM, MIN_TRANSFER_BALANCE};
 use crate::context::Ctx;
 use crate::error::StepError;
 use crate::state::State;
@@ -8,7 +8,7 @@ use crate::step::utils::coin_flip;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
 use crate::types::Alias;
-use crate::utils::{get_masp_epoch, ibc_denom, is_native_denom, retry_config};
+use crate::utils::{get_masp_epoch, retry_config};
 
 use super::utils;
 
@@ -25,15 +25,7 @@ impl StepContext for ShieldedTransfer {
     }
 
     async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
-        let Some((source_account, denom)) = state
-            .random_masp_account_with_ibc_balance(vec![])
-            .filter(|_| utils::coin_flip(0.5))
-            .map(|account| (account, ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN)))
-            .or_else(|| {
-                state
-                    .random_masp_account_with_min_balance(vec![], MIN_TRANSFER_BALANCE)
-                    .map(|account| (account, Alias::nam().name))
-            })
+        let Some((source_account, denom)) = state.random_masp_account_with_token_balance(vec![])
         else {
             return Ok(vec![]);
         };
@@ -42,17 +34,14 @@ impl StepContext for ShieldedTransfer {
         let target_account = state
             .random_payment_address(vec![source_account.alias.clone()])
             .ok_or(StepError::BuildTask("No more target accounts".to_string()))?;
-        let balance = if is_native_denom(&denom) {
-            state.get_shielded_balance_for(&source_account.alias)
-        } else {
-            state.get_ibc_balance_for(&source_account.alias.spending_key(), &denom)
-        };
+        let balance = state.get_shielded_balance_for(&source_account.alias, &denom);
         let amount = utils::random_between(1, balance / MAX_BATCH_TX_NUM);
 
+        let nam = Alias::nam().name;
         let disposable_gas_payer = match (
-            is_native_denom(&denom),
-            state.get_balance_for(&source_account.alias.base()),
-            state.get_shielded_balance_for(&source_account.alias),
+            denom == nam,
+            state.get_balance_for(&source_account.alias.base(), &nam),
+            state.get_shielded_balance_for(&source_account.alias, &nam),
         ) {
             (true, balance, _) if balance < DEFAULT_FEE => true,
             (true, _, _) => coin_flip(0.5),
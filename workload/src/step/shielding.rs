@@ -1,11 +1,10 @@
-use crate::constants::{COSMOS_TOKEN, MAX_BATCH_TX_NUM, MIN_TRANSFER_BALANCE};
+use crate::constants::{MAX_BATCH_TX_NUM, MIN_TRANSFER_BALANCE};
 use crate::context::Ctx;
 use crate::error::StepError;
 use crate::state::State;
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
-use crate::types::Alias;
-use crate::utils::{get_masp_epoch, ibc_denom, is_native_denom, retry_config};
+use crate::utils::{get_masp_epoch, retry_config};
 
 use super::utils;
 
@@ -23,24 +22,13 @@ impl StepContext for Shielding {
 
     async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
         let (source_account, denom) = state
-            .random_account_with_ibc_balance(vec![])
-            .filter(|_| utils::coin_flip(0.5))
-            .map(|account| (account, ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN)))
-            .or_else(|| {
-                state
-                    .random_account_with_min_balance(vec![], MIN_TRANSFER_BALANCE)
-                    .map(|account| (account, Alias::nam().name))
-            })
+            .random_account_with_token_balance(vec![])
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
         let epoch = get_masp_epoch(ctx, retry_config()).await?;
         let target_account = state
             .random_payment_address(vec![])
             .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
-        let balance = if is_native_denom(&denom) {
-            state.get_balance_for(&source_account.alias)
-        } else {
-            state.get_ibc_balance_for(&source_account.alias, &denom)
-        };
+        let balance = state.get_balance_for(&source_account.alias, &denom);
         let amount = utils::random_between(1, balance / MAX_BATCH_TX_NUM);
 
         let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
@@ -1,11 +1,13 @@
+use namada_sdk::rpc;
 use rand::distributions::uniform::SampleUniform;
 use rand::distributions::{Alphanumeric, DistString};
 use rand::prelude::IteratorRandom;
 use rand::Rng;
 
 use crate::constants::DEFAULT_FEE;
+use crate::context::Ctx;
 use crate::state::State;
-use crate::types::Alias;
+use crate::types::{Alias, Epoch};
 use crate::utils::with_rng;
 
 pub(crate) fn coin_flip(p: f64) -> bool {
@@ -20,6 +22,29 @@ pub(crate) fn random_between<T: SampleUniform + std::cmp::PartialOrd>(from: T, t
     }
 }
 
+/// Voting start, voting end and activation epochs of a new proposal
+pub(crate) async fn random_proposal_epochs(
+    ctx: &Ctx,
+    current_epoch: Epoch,
+) -> (Epoch, Epoch, Epoch) {
+    let gov_prams = rpc::query_governance_parameters(&ctx.namada.client).await;
+
+    let start_epoch = random_between(
+        current_epoch + 2,
+        current_epoch + gov_prams.max_proposal_latency,
+    );
+    let end_epoch = random_between(
+        start_epoch + gov_prams.min_proposal_voting_period,
+        start_epoch + gov_prams.max_proposal_period - 5,
+    );
+    let grace_epoch = random_between(
+        end_epoch + gov_prams.min_proposal_grace_epochs,
+        end_epoch + 5,
+    );
+
+    (start_epoch, end_epoch, grace_epoch)
+}
+
 pub(crate) fn random_alias() -> Alias {
     format!(
         "workload-generator-{}",
@@ -48,7 +73,7 @@ pub fn get_gas_payer<'a>(candidates: impl IntoIterator<Item = &'a Alias>, state:
     let payer = with_rng(|rng| {
         candidates
             .into_iter()
-            .filter(|alias| state.get_balance_for(alias) >= DEFAULT_FEE)
+            .filter(|alias| state.get_balance_for(alias, &Alias::nam().name) >= DEFAULT_FEE)
             .choose(rng)
             .cloned()
             .unwrap_or(Alias::faucet())
@@ -1,16 +1,31 @@
+use rand::seq::IteratorRandom;
+
+use crate::constants::PIPELINE_LEN;
 use crate::context::Ctx;
 use crate::error::StepError;
-use crate::state::State;
+use crate::state::{Account, State};
 use crate::step::StepContext;
 use crate::task::{self, Task, TaskSettings};
-use crate::types::ProposalVote;
-use crate::utils::{get_epoch, retry_config};
+use crate::types::{Alias, Epoch, ProposalId, ProposalVote};
+use crate::utils::{get_epoch, retry_config, with_rng};
 
 use super::utils;
 
+/// Vote as a delegator, as a validator owned by the workload, or as a delegator overriding the
+/// vote of its validator
 #[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
 pub struct Vote;
 
+fn random_vote(except: Option<&ProposalVote>) -> ProposalVote {
+    with_rng(|rng| {
+        [ProposalVote::Yay, ProposalVote::Nay, ProposalVote::Abstain]
+            .into_iter()
+            .filter(|vote| Some(vote) != except)
+            .choose(rng)
+            .expect("Vote should exist")
+    })
+}
+
 impl StepContext for Vote {
     fn name(&self) -> String {
         "vote".to_string()
@@ -26,37 +41,131 @@ impl StepContext for Vote {
         let Some(proposal_id) = state.random_votable_proposal(current_epoch) else {
             return Ok(vec![]);
         };
-
-        // voter should have bonded at the start epoch
-        let start_epoch = state
+        let proposal = state
             .proposals
             .get(&proposal_id)
-            .expect("Proposal should exist")
-            .0;
-        let Some(source_bond) = state.random_bond(start_epoch) else {
+            .expect("Proposal should exist");
+
+        let wallet = ctx.namada.wallet.read().await;
+        let mut validators = vec![];
+        for alias in state.validators.keys() {
+            let address = wallet
+                .find_address(&alias.name)
+                .ok_or_else(|| StepError::Wallet(format!("No validator address: {}", alias.name)))?
+                .to_string();
+            validators.push((alias.clone(), address));
+        }
+        drop(wallet);
+
+        // the vote will be requested at the current epoch,
+        // but the execution could be at the next epoch
+        let is_validator_period =
+            (current_epoch + 1) * 3 < proposal.start_epoch + proposal.end_epoch * 2;
+        if is_validator_period && utils::coin_flip(0.3) {
+            if let Some(task) = validator_vote(state, proposal_id, current_epoch, &validators) {
+                return Ok(vec![task]);
+            }
+        }
+        if utils::coin_flip(0.5) {
+            if let Some(task) = override_vote(state, proposal_id, proposal.start_epoch, &validators)
+            {
+                return Ok(vec![task]);
+            }
+        }
+
+        // voter should have bonded at the start epoch
+        let Some(source_bond) = state.random_bond(proposal.start_epoch) else {
             return Ok(vec![]);
         };
         let source_account = state.get_account_by_alias(&source_bond.alias);
 
-        let vote = if utils::coin_flip(0.5) {
-            ProposalVote::Yay
-        } else if utils::coin_flip(0.5) {
-            ProposalVote::Nay
-        } else {
-            ProposalVote::Abstain
-        };
-
-        let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
-        let mut task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
-        task_settings.gas_limit *= 5;
-
-        Ok(vec![Task::Vote(
-            task::vote::Vote::builder()
-                .source(source_account.alias)
-                .proposal_id(proposal_id)
-                .vote(vote)
-                .settings(task_settings)
-                .build(),
+        Ok(vec![vote_task(
+            state,
+            source_account,
+            proposal_id,
+            random_vote(None),
         )])
     }
 }
+
+/// A validator votes with its stake when it has an effective bond and hasn't voted yet
+fn validator_vote(
+    state: &State,
+    proposal_id: ProposalId,
+    current_epoch: Epoch,
+    validators: &[(Alias, String)],
+) -> Option<Task> {
+    let votes = state.votes.get(&proposal_id);
+    let candidates = validators.iter().filter(|(alias, address)| {
+        let has_stake = state.bonds.values().any(|bonds| {
+            bonds
+                .get(address)
+                .is_some_and(|(amount, epoch)| *amount > 0 && current_epoch > epoch + PIPELINE_LEN)
+        });
+        has_stake
+            && !state.is_frozen(address)
+            && votes.is_none_or(|votes| !votes.contains_key(alias))
+    });
+    let (alias, _) = with_rng(|rng| candidates.choose(rng))?;
+    let account = state.validators.get(alias).expect("Validator should exist");
+
+    Some(vote_task(
+        state,
+        account.clone(),
+        proposal_id,
+        random_vote(None),
+    ))
+}
+
+/// A delegator of a validator which has voted votes differently
+fn override_vote(
+    state: &State,
+    proposal_id: ProposalId,
+    start_epoch: Epoch,
+    validators: &[(Alias, String)],
+) -> Option<Task> {
+    let votes = state.votes.get(&proposal_id)?;
+    let candidates = validators.iter().flat_map(|(alias, address)| {
+        votes
+            .get(alias)
+            .into_iter()
+            .flat_map(move |validator_vote| {
+                state.bonds.iter().filter_map(move |(source, bonds)| {
+                    // delegator should have bonded at the start epoch
+                    bonds
+                        .get(address)
+                        .filter(|(amount, epoch)| *amount > 0 && start_epoch > epoch + PIPELINE_LEN)
+                        .map(|_| (source, validator_vote))
+                })
+            })
+    });
+    let (source, validator_vote) = with_rng(|rng| candidates.choose(rng))?;
+    let source_account = state.get_account_by_alias(source);
+
+    Some(vote_task(
+        state,
+        source_account,
+        proposal_id,
+        random_vote(Some(validator_vote)),
+    ))
+}
+
+fn vote_task(
+    state: &State,
+    source_account: Account,
+    proposal_id: ProposalId,
+    vote: ProposalVote,
+) -> Task {
+    let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
+    let mut task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
+    task_settings.gas_limit *= 5;
+
+    Task::Vote(
+        task::vote::Vote::builder()
+            .source(source_account.alias)
+            .proposal_id(proposal_id)
+            .vote(vote)
+            .settings(task_settings)
+            .build(),
+    )
+}
@@ -0,0 +1,52 @@
+use rand::Rng;
+
+use crate::constants::PROPOSAL_DEPOSIT;
+use crate::context::Ctx;
+use crate::error::StepError;
+use crate::state::State;
+use crate::step::StepContext;
+use crate::task::{self, Task, TaskSettings};
+use crate::utils::{get_epoch, retry_config, with_rng};
+
+use super::utils;
+
+/// Submit a proposal with the code writing a random marker to a parameter
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct WasmProposal;
+
+impl StepContext for WasmProposal {
+    fn name(&self) -> String {
+        "wasm-proposal".to_string()
+    }
+
+    async fn is_valid(&self, _ctx: &Ctx, state: &State) -> Result<bool, StepError> {
+        Ok(state.at_least_account_with_min_balance(1, PROPOSAL_DEPOSIT))
+    }
+
+    async fn build_task(&self, ctx: &Ctx, state: &State) -> Result<Vec<Task>, StepError> {
+        let source_account = state
+            .random_account_with_min_balance(vec![], PROPOSAL_DEPOSIT)
+            .ok_or(StepError::BuildTask("No more accounts".to_string()))?;
+
+        let current_epoch = get_epoch(ctx, retry_config()).await?;
+
+        let (start_epoch, end_epoch, grace_epoch) =
+            utils::random_proposal_epochs(ctx, current_epoch).await;
+
+        let marker = with_rng(|rng| rng.gen());
+
+        let gas_payer = utils::get_gas_payer(source_account.public_keys.iter(), state);
+        let task_settings = TaskSettings::new(source_account.public_keys, gas_payer);
+
+        Ok(vec![Task::WasmProposal(
+            task::wasm_proposal::WasmProposal::builder()
+                .source(source_account.alias)
+                .marker(marker)
+                .start_epoch(start_epoch)
+                .end_epoch(end_epoch)
+                .grace_epoch(grace_epoch)
+                .settings(task_settings)
+                .build(),
+        )])
+    }
+}
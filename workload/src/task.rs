@@ -4,42 +4,53 @@ use std::fmt::Display;
 use cosmrs::Any;
 use enum_dispatch::enum_dispatch;
 use namada_sdk::{args, signing::SigningTxData, tx::Tx};
+use serde::{Deserialize, Serialize};
 use tokio::time::{sleep, Duration};
 
-use crate::check::Check;
+use crate::check::{self, Check};
 use crate::constants::DEFAULT_GAS_LIMIT;
 use crate::context::Ctx;
 use crate::error::TaskError;
 use crate::state::State;
-use crate::types::{Alias, Fee, Height, MaspEpoch};
+use crate::types::{Alias, Amount, CommissionChangeKind, Fee, Height, MaspEpoch};
 use crate::utils::{
     execute_cosmos_tx, execute_tx, get_block_height, get_masp_epoch, get_masp_epoch_at_height,
-    retry_config, wait_block_settlement, wait_cosmos_settlement, RetryConfig,
+    get_shielded_balance, retry_config, wait_block_settlement, wait_cosmos_settlement, RetryConfig,
 };
 
 pub mod batch;
 pub mod become_validator;
 pub mod bond;
+pub mod change_commission;
 pub mod change_consensus_key;
 pub mod change_metadata;
 pub mod claim_rewards;
 pub mod deactivate_validator;
 pub mod default_proposal;
+pub mod double_sign;
 pub mod faucet_transfer;
 pub mod ibc_transfer;
 pub mod init_account;
 pub mod new_wallet_keypair;
+pub mod pgf_funding_proposal;
+pub mod pgf_steward_proposal;
+pub mod query_pgf;
+pub mod query_slashes;
+pub mod query_tally;
+pub mod query_wasm_proposals;
 pub mod reactivate_validator;
 pub mod redelegate;
 pub mod shielded;
 pub mod shielding;
 pub mod transparent_transfer;
 pub mod unbond;
+pub mod unjail_validator;
 pub mod unshielding;
 pub mod update_account;
 pub mod vote;
+pub mod wasm_proposal;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TaskSettings {
     pub signers: BTreeSet<Alias>,
     pub gas_payer: Alias,
@@ -72,8 +83,36 @@ impl TaskSettings {
     }
 }
 
+/// Check of the fee paid from the MASP balance of the spending key gas payer. `amount` is NAM spent
+/// from the same spending key by the tx. The shielded context should be synced in advance.
+pub async fn build_masp_fee_check(
+    ctx: &Ctx,
+    settings: &TaskSettings,
+    denom: &str,
+    amount: Amount,
+    retry_config: RetryConfig,
+) -> Result<Option<Check>, TaskError> {
+    let gas_payer = &settings.gas_payer;
+    if !gas_payer.is_spending_key() {
+        return Ok(None);
+    }
+    let nam = Alias::nam().name;
+    let pre_balance = get_shielded_balance(ctx, gas_payer, &nam, retry_config)
+        .await?
+        .unwrap_or_default();
+    let amount = if denom == nam { amount } else { 0 };
+
+    Ok(Some(Check::MaspFee(
+        check::masp_fee::MaspFee::builder()
+            .target(gas_payer.clone())
+            .pre_balance(pre_balance)
+            .amount(amount)
+            .build(),
+    )))
+}
+
 #[enum_dispatch]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Task {
     NewWalletKeyPair(new_wallet_keypair::NewWalletKeyPair),
     FaucetTransfer(faucet_transfer::FaucetTransfer),
@@ -93,12 +132,22 @@ pub enum Task {
     Unshielding(unshielding::Unshielding),
     BecomeValidator(become_validator::BecomeValidator),
     ChangeMetadata(change_metadata::ChangeMetadata),
+    ChangeCommission(change_commission::ChangeCommission),
     ChangeConsensusKey(change_consensus_key::ChangeConsensusKey),
+    DoubleSign(double_sign::DoubleSign),
     DeactivateValidator(deactivate_validator::DeactivateValidator),
     ReactivateValidator(reactivate_validator::ReactivateValidator),
+    UnjailValidator(unjail_validator::UnjailValidator),
+    QuerySlashes(query_slashes::QuerySlashes),
     UpdateAccount(update_account::UpdateAccount),
     DefaultProposal(default_proposal::DefaultProposal),
+    PgfStewardProposal(pgf_steward_proposal::PgfStewardProposal),
+    PgfFundingProposal(pgf_funding_proposal::PgfFundingProposal),
+    QueryPgf(query_pgf::QueryPgf),
+    WasmProposal(wasm_proposal::WasmProposal),
+    QueryWasmProposals(query_wasm_proposals::QueryWasmProposals),
     Vote(vote::Vote),
+    QueryTally(query_tally::QueryTally),
 }
 
 impl Task {
@@ -134,6 +183,8 @@ impl Task {
                     *fees.entry(settings.gas_payer.clone()).or_insert(0) += settings.gas_limit;
                 }
             }
+            // the tx is rejected before the submission
+            Task::ChangeCommission(cc) if cc.kind() == CommissionChangeKind::AboveMaxRate => {}
             _ => {
                 if let Some(settings) = self.task_settings() {
                     *fees.entry(settings.gas_payer.clone()).or_insert(0) += settings.gas_limit;
@@ -157,8 +208,14 @@ pub trait TaskContext {
 
     fn task_settings(&self) -> Option<&TaskSettings>;
 
+    /// Tasks only querying the chain override `execute` and don't build a tx
     #[allow(async_fn_in_trait)]
-    async fn build_tx(&self, ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError>;
+    async fn build_tx(&self, _ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError> {
+        Err(TaskError::BuildTx(format!(
+            "{} doesn't build a tx",
+            self.name()
+        )))
+    }
 
     #[allow(async_fn_in_trait)]
     async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
@@ -1,21 +1,21 @@
 use std::collections::HashMap;
 
 use namada_sdk::{args, signing::SigningTxData, tx::Tx};
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
-use crate::constants::COSMOS_TOKEN;
 use crate::context::Ctx;
 use crate::error::TaskError;
 use crate::state::State;
 use crate::task::{Task, TaskContext, TaskSettings};
-use crate::types::{Alias, Height};
+use crate::types::{Alias, Balance, Height};
 use crate::utils::{
-    execute_tx, get_balance, get_block_height, get_bond, get_shielded_balance, ibc_denom,
-    is_native_denom, merge_tx, retry_config, wait_block_settlement, RetryConfig,
+    execute_tx, get_balance, get_block_height, get_bond, get_shielded_balance, merge_tx,
+    retry_config, wait_block_settlement, RetryConfig,
 };
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Batch {
     tasks: Vec<Task>,
     settings: TaskSettings,
@@ -109,64 +109,49 @@ impl TaskContext for Batch {
         }
 
         let mut prepared_checks = vec![];
-        let mut balances: HashMap<Alias, i64> = HashMap::default();
-        let mut shielded_balances: HashMap<Alias, i64> = HashMap::default();
-        let mut ibc_balances: HashMap<Alias, i64> = HashMap::default();
+        let mut balances: HashMap<(Alias, String), i64> = HashMap::default();
+        let mut shielded_balances: HashMap<(Alias, String), i64> = HashMap::default();
         let mut bonds: HashMap<String, (u64, i64)> = HashMap::default();
+        let mut masp_fees: HashMap<Alias, Balance> = HashMap::default();
         for check in checks {
             match check {
                 Check::RevealPk(_) => prepared_checks.push(check),
+                Check::MaspFee(masp_fee) => {
+                    masp_fees
+                        .entry(masp_fee.target().clone())
+                        .or_insert(masp_fee.pre_balance());
+                }
                 Check::BalanceSource(balance_source) => {
-                    if is_native_denom(balance_source.denom()) {
-                        balances
-                            .entry(balance_source.target().clone())
-                            .and_modify(|balance| *balance -= balance_source.amount() as i64)
-                            .or_insert(-(balance_source.amount() as i64));
-                    } else {
-                        ibc_balances
-                            .entry(balance_source.target().clone())
-                            .and_modify(|balance| *balance -= balance_source.amount() as i64)
-                            .or_insert(-(balance_source.amount() as i64));
-                    }
+                    *balances
+                        .entry((
+                            balance_source.target().clone(),
+                            balance_source.denom().to_string(),
+                        ))
+                        .or_insert(0) -= balance_source.amount() as i64;
                 }
                 Check::BalanceTarget(balance_target) => {
-                    if is_native_denom(balance_target.denom()) {
-                        balances
-                            .entry(balance_target.target().clone())
-                            .and_modify(|balance| *balance += balance_target.amount() as i64)
-                            .or_insert(balance_target.amount() as i64);
-                    } else {
-                        ibc_balances
-                            .entry(balance_target.target().clone())
-                            .and_modify(|balance| *balance += balance_target.amount() as i64)
-                            .or_insert(balance_target.amount() as i64);
-                    }
+                    *balances
+                        .entry((
+                            balance_target.target().clone(),
+                            balance_target.denom().to_string(),
+                        ))
+                        .or_insert(0) += balance_target.amount() as i64;
                 }
                 Check::BalanceShieldedSource(balance_source) => {
-                    if is_native_denom(balance_source.denom()) {
-                        shielded_balances
-                            .entry(balance_source.target().base().clone())
-                            .and_modify(|balance| *balance -= balance_source.amount() as i64)
-                            .or_insert(-(balance_source.amount() as i64));
-                    } else {
-                        ibc_balances
-                            .entry(balance_source.target().spending_key().clone())
-                            .and_modify(|balance| *balance -= balance_source.amount() as i64)
-                            .or_insert(-(balance_source.amount() as i64));
-                    }
+                    *shielded_balances
+                        .entry((
+                            balance_source.target().base(),
+                            balance_source.denom().to_string(),
+                        ))
+                        .or_insert(0) -= balance_source.amount() as i64;
                 }
                 Check::BalanceShieldedTarget(balance_target) => {
-                    if is_native_denom(balance_target.denom()) {
-                        shielded_balances
-                            .entry(balance_target.target().base().clone())
-                            .and_modify(|balance| *balance += balance_target.amount() as i64)
-                            .or_insert(balance_target.amount() as i64);
-                    } else {
-                        ibc_balances
-                            .entry(balance_target.target().payment_address().clone())
-                            .and_modify(|balance| *balance += balance_target.amount() as i64)
-                            .or_insert(balance_target.amount() as i64);
-                    }
+                    *shielded_balances
+                        .entry((
+                            balance_target.target().base(),
+                            balance_target.denom().to_string(),
+                        ))
+                        .or_insert(0) += balance_target.amount() as i64;
                 }
                 Check::BondIncrease(bond_increase) => {
                     bonds
@@ -200,15 +185,14 @@ impl TaskContext for Batch {
             }
         }
 
-        let denom = Alias::nam().name;
-        for (alias, amount) in balances {
+        for ((alias, denom), amount) in balances {
             let (_, pre_balance) = get_balance(ctx, &alias, &denom, retry_config).await?;
             if amount >= 0 {
                 prepared_checks.push(Check::BalanceTarget(
                     check::balance_target::BalanceTarget::builder()
                         .target(alias)
                         .pre_balance(pre_balance)
-                        .denom(denom.clone())
+                        .denom(denom)
                         .amount(amount.unsigned_abs())
                         .build(),
                 ));
@@ -217,7 +201,7 @@ impl TaskContext for Batch {
                     check::balance_source::BalanceSource::builder()
                         .target(alias)
                         .pre_balance(pre_balance)
-                        .denom(denom.clone())
+                        .denom(denom)
                         .amount(amount.unsigned_abs())
                         .build(),
                 ));
@@ -251,7 +235,25 @@ impl TaskContext for Batch {
             }
         }
 
-        for (alias, amount) in shielded_balances {
+        // A gas payer is checked once with all the NAM it spends and receives in the batch, also
+        // when the batch fails
+        let nam = Alias::nam().name;
+        for (gas_payer, pre_balance) in masp_fees {
+            let amount = shielded_balances
+                .get(&(gas_payer.base(), nam.clone()))
+                .cloned()
+                .unwrap_or_default();
+            prepared_checks.push(Check::MaspFee(
+                check::masp_fee::MaspFee::builder()
+                    .target(gas_payer)
+                    .pre_balance(pre_balance)
+                    .amount(amount.min(0).unsigned_abs())
+                    .received(amount.max(0).unsigned_abs())
+                    .build(),
+            ));
+        }
+
+        for ((alias, denom), amount) in shielded_balances {
             // shielded-sync has been already done in each task.build_checks()
             let pre_balance = get_shielded_balance(ctx, &alias, &denom, retry_config)
                 .await?
@@ -261,7 +263,7 @@ impl TaskContext for Batch {
                     check::balance_shielded_target::BalanceShieldedTarget::builder()
                         .target(alias.payment_address())
                         .pre_balance(pre_balance)
-                        .denom(denom.clone())
+                        .denom(denom)
                         .amount(amount.unsigned_abs())
                         .build(),
                 ));
@@ -270,62 +272,13 @@ impl TaskContext for Batch {
                     check::balance_shielded_source::BalanceShieldedSource::builder()
                         .target(alias.spending_key())
                         .pre_balance(pre_balance)
-                        .denom(denom.clone())
+                        .denom(denom)
                         .amount(amount.unsigned_abs())
                         .build(),
                 ));
             }
         }
 
-        let ibc_denom = ibc_denom(&ctx.namada_channel_id, COSMOS_TOKEN);
-        for (alias, amount) in ibc_balances {
-            if alias.is_spending_key() || alias.is_payment_address() {
-                let pre_balance = get_shielded_balance(ctx, &alias, &ibc_denom, retry_config)
-                    .await?
-                    .unwrap_or_default();
-                if amount >= 0 {
-                    prepared_checks.push(Check::BalanceShieldedTarget(
-                        check::balance_shielded_target::BalanceShieldedTarget::builder()
-                            .target(alias.payment_address())
-                            .pre_balance(pre_balance)
-                            .denom(ibc_denom.clone())
-                            .amount(amount.unsigned_abs())
-                            .build(),
-                    ));
-                } else {
-                    prepared_checks.push(Check::BalanceShieldedSource(
-                        check::balance_shielded_source::BalanceShieldedSource::builder()
-                            .target(alias.spending_key())
-                            .pre_balance(pre_balance)
-                            .denom(ibc_denom.clone())
-                            .amount(amount.unsigned_abs())
-                            .build(),
-                    ));
-                }
-            } else {
-                let (_, pre_balance) = get_balance(ctx, &alias, &ibc_denom, retry_config).await?;
-                if amount >= 0 {
-                    prepared_checks.push(Check::BalanceTarget(
-                        check::balance_target::BalanceTarget::builder()
-                            .target(alias)
-                            .pre_balance(pre_balance)
-                            .denom(ibc_denom.clone())
-                            .amount(amount.unsigned_abs())
-                            .build(),
-                    ));
-                } else {
-                    prepared_checks.push(Check::BalanceSource(
-                        check::balance_source::BalanceSource::builder()
-                            .target(alias)
-                            .pre_balance(pre_balance)
-                            .denom(ibc_denom.clone())
-                            .amount(amount.unsigned_abs())
-                            .build(),
-                    ));
-                }
-            }
-        }
-
         Ok(prepared_checks)
     }
 
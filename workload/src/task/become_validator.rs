@@ -4,7 +4,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
-use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -13,9 +13,9 @@ use crate::error::TaskError;
 use crate::state::State;
 use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, CommissionChange, CommissionRate};
-use crate::utils::RetryConfig;
+use crate::utils::{key_rng, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct BecomeValidator {
     source: Alias,
     consensus_alias: Alias,
@@ -49,7 +49,7 @@ impl TaskContext for BecomeValidator {
                 Some(self.consensus_alias.name.clone()),
                 true,
                 None,
-                &mut OsRng,
+                &mut key_rng(&self.consensus_alias.name),
             )
             .expect("Key generation should not fail.")
             .1
@@ -61,7 +61,7 @@ impl TaskContext for BecomeValidator {
                 Some(self.eth_cold_alias.name.clone()),
                 true,
                 None,
-                &mut OsRng,
+                &mut key_rng(&self.eth_cold_alias.name),
             )
             .expect("Key generation should not fail.")
             .1
@@ -73,7 +73,7 @@ impl TaskContext for BecomeValidator {
                 Some(self.eth_hot_alias.name.clone()),
                 true,
                 None,
-                &mut OsRng,
+                &mut key_rng(&self.eth_hot_alias.name),
             )
             .expect("Key generation should not fail.")
             .1
@@ -85,7 +85,7 @@ impl TaskContext for BecomeValidator {
                 Some(self.protocol_alias.name.clone()),
                 true,
                 None,
-                &mut OsRng,
+                &mut key_rng(&self.protocol_alias.name),
             )
             .expect("Key generation should not fail.")
             .1
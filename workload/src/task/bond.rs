@@ -7,6 +7,7 @@ use namada_sdk::token;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -17,7 +18,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, Epoch, ValidatorAddress};
 use crate::utils::{get_balance, get_bond, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Bond {
     source: Alias,
     validator: ValidatorAddress,
@@ -0,0 +1,158 @@
+use namada_sdk::args::{self, TxBuilder};
+use namada_sdk::signing::SigningTxData;
+use namada_sdk::tx::data::GasLimit;
+use namada_sdk::tx::Tx;
+use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::constants::PIPELINE_LEN;
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, CommissionChangeKind, CommissionRate, Height, ValidatorAddress};
+use crate::utils::{
+    execute_tx, get_block_height, get_commission_rate, get_epoch, retry_config,
+    wait_block_settlement, RetryConfig,
+};
+
+const INVALID_COMMISSION_RATE_MESSAGE: &str = "Invalid new commission rate";
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct ChangeCommission {
+    source: Alias,
+    validator: ValidatorAddress,
+    pre_rate: CommissionRate,
+    rate: CommissionRate,
+    kind: CommissionChangeKind,
+    settings: TaskSettings,
+}
+
+impl ChangeCommission {
+    pub fn kind(&self) -> CommissionChangeKind {
+        self.kind
+    }
+}
+
+impl TaskContext for ChangeCommission {
+    fn name(&self) -> String {
+        "change-commission".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "change-commission/{}/{}/{}",
+            self.source.name, self.rate, self.kind
+        )
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        Some(&self.settings)
+    }
+
+    async fn build_tx(&self, ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError> {
+        let wallet = ctx.namada.wallet.read().await;
+        let source_address = wallet
+            .find_address(&self.source.name)
+            .ok_or_else(|| TaskError::Wallet(format!("No source address: {}", self.source.name)))?;
+        let fee_payer = wallet
+            .find_public_key(&self.settings.gas_payer.name)
+            .map_err(|e| TaskError::Wallet(e.to_string()))?;
+
+        let mut change_commission_tx_builder = ctx
+            .namada
+            .new_change_commission_rate(self.rate, source_address.into_owned());
+
+        change_commission_tx_builder =
+            change_commission_tx_builder.gas_limit(GasLimit::from(self.settings.gas_limit));
+        change_commission_tx_builder = change_commission_tx_builder.wrapper_fee_payer(fee_payer);
+        // Submit the invalid change for the chain to reject it
+        change_commission_tx_builder =
+            change_commission_tx_builder.force(self.kind != CommissionChangeKind::Valid);
+
+        let mut signing_keys = vec![];
+        for signer in &self.settings.signers {
+            let public_key = wallet
+                .find_public_key(&signer.name)
+                .map_err(|e| TaskError::Wallet(e.to_string()))?;
+            signing_keys.push(public_key)
+        }
+        change_commission_tx_builder = change_commission_tx_builder.signing_keys(signing_keys);
+        drop(wallet);
+
+        let (change_commission, signing_data) = change_commission_tx_builder
+            .build(&ctx.namada)
+            .await
+            .map_err(|e| TaskError::BuildTx(e.to_string()))?;
+
+        Ok((
+            change_commission,
+            vec![signing_data],
+            change_commission_tx_builder.tx,
+        ))
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        let retry_config = retry_config();
+        let start_height = get_block_height(ctx, retry_config)
+            .await
+            .unwrap_or_default();
+
+        let result = match self.build_tx(ctx).await {
+            Ok((tx, signing_data, tx_args)) => execute_tx(ctx, tx, signing_data, &tx_args).await,
+            Err(e) => Err(e),
+        };
+
+        // The rejection of the invalid change is expected, the check verifies the rate isn't
+        // changed
+        match (self.kind, result) {
+            (_, Ok(height))
+            | (
+                CommissionChangeKind::ExceedingMaxChange,
+                Err(TaskError::Execution { height, .. }),
+            ) => {
+                wait_block_settlement(ctx, height, retry_config).await;
+                Ok(height)
+            }
+            // A rate above 100% can't be submitted even when forced
+            (CommissionChangeKind::AboveMaxRate, Err(TaskError::BuildTx(e)))
+                if e.contains(INVALID_COMMISSION_RATE_MESSAGE) =>
+            {
+                wait_block_settlement(ctx, start_height, retry_config).await;
+                Ok(start_height)
+            }
+            (_, Err(e)) => {
+                wait_block_settlement(ctx, start_height, retry_config).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn build_checks(
+        &self,
+        ctx: &Ctx,
+        retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        // A former change could be pending at the pipeline epoch
+        let epoch = get_epoch(ctx, retry_config).await?;
+        let pre_rate =
+            get_commission_rate(ctx, &self.validator, epoch + PIPELINE_LEN, retry_config)
+                .await?
+                .commission_rate
+                .unwrap_or(self.pre_rate);
+
+        Ok(vec![Check::CommissionRate(
+            check::commission_rate::CommissionRate::builder()
+                .target(self.source.clone())
+                .validator(self.validator.clone())
+                .pre_rate(pre_rate)
+                .rate(self.rate)
+                .kind(self.kind)
+                .build(),
+        )])
+    }
+
+    fn update_state(&self, _state: &mut State) {}
+}
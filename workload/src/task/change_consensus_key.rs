@@ -4,7 +4,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
-use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::Check;
@@ -13,9 +13,9 @@ use crate::error::TaskError;
 use crate::state::State;
 use crate::task::{TaskContext, TaskSettings};
 use crate::types::Alias;
-use crate::utils::RetryConfig;
+use crate::utils::{key_rng, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct ChangeConsensusKey {
     source: Alias,
     consensus_alias: Alias,
@@ -44,7 +44,7 @@ impl TaskContext for ChangeConsensusKey {
                 Some(self.consensus_alias.name.clone()),
                 true,
                 None,
-                &mut OsRng,
+                &mut key_rng(&self.consensus_alias.name),
             )
             .expect("Key generation should not fail.")
             .1
@@ -6,6 +6,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -16,7 +17,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, ValidatorAddress};
 use crate::utils::{get_balance, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct ClaimRewards {
     source: Alias,
     from_validator: ValidatorAddress,
@@ -98,6 +99,6 @@ impl TaskContext for ClaimRewards {
     }
 
     fn update_state(&self, state: &mut State) {
-        state.increase_balance(&self.source, self.amount);
+        state.increase_balance(&self.source, &Alias::nam().name, self.amount);
     }
 }
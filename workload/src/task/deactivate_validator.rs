@@ -3,6 +3,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -13,7 +14,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Epoch, ValidatorStatus};
 use crate::utils::RetryConfig;
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct DeactivateValidator {
     target: Alias,
     epoch: Epoch,
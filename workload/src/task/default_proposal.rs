@@ -6,6 +6,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -17,7 +18,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Epoch};
 use crate::utils::{get_balance, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct DefaultProposal {
     source: Alias,
     start_epoch: Epoch,
@@ -113,7 +114,7 @@ impl TaskContext for DefaultProposal {
     }
 
     fn update_state(&self, state: &mut State) {
-        state.decrease_balance(&self.source, PROPOSAL_DEPOSIT);
+        state.decrease_balance(&self.source, &Alias::nam().name, PROPOSAL_DEPOSIT);
         // proposal will be added later
     }
 }
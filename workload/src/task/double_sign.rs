@@ -0,0 +1,92 @@
+use namada_sdk::key::tm_consensus_key_raw_hash;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::constants::EVIDENCE_WAIT_BLOCKS;
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, Height, ValidatorAddress};
+use crate::utils::{
+    find_duplicate_vote_evidence, get_consensus_key, retry_config, submit_duplicate_votes,
+    wait_block_settlement, RetryConfig,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct DoubleSign {
+    validator: Alias,
+    address: ValidatorAddress,
+}
+
+impl DoubleSign {
+    pub fn validator(&self) -> &Alias {
+        &self.validator
+    }
+
+    pub fn address(&self) -> &ValidatorAddress {
+        &self.address
+    }
+}
+
+impl TaskContext for DoubleSign {
+    fn name(&self) -> String {
+        "double-sign".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!("double-sign/{}", self.validator.name)
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        None
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        let retry_config = retry_config();
+
+        let consensus_pk = get_consensus_key(ctx, &self.address, retry_config)
+            .await?
+            .ok_or_else(|| {
+                TaskError::Evidence(format!("No consensus key of {}", self.validator.name))
+            })?;
+        let consensus_sk = ctx
+            .namada
+            .wallet
+            .write()
+            .await
+            .find_key_by_pk(&consensus_pk, None)
+            .map_err(|e| TaskError::Wallet(e.to_string()))?;
+
+        let infraction_height = submit_duplicate_votes(ctx, &consensus_sk).await?;
+        let tm_address = tm_consensus_key_raw_hash(&consensus_pk);
+        let height =
+            find_duplicate_vote_evidence(ctx, &tm_address, infraction_height, retry_config)
+                .await?
+                .ok_or_else(|| {
+                    TaskError::Evidence(format!(
+                        "Evidence of {} at height {infraction_height} wasn't committed within {EVIDENCE_WAIT_BLOCKS} blocks",
+                        self.validator.name
+                    ))
+                })?;
+        wait_block_settlement(ctx, height, retry_config).await;
+
+        Ok(height)
+    }
+
+    async fn build_checks(
+        &self,
+        _ctx: &Ctx,
+        _retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        Ok(vec![Check::ValidatorSlashed(
+            check::validator_slashed::ValidatorSlashed::builder()
+                .target(self.validator.clone())
+                .validator(self.address.clone())
+                .build(),
+        )])
+    }
+
+    fn update_state(&self, _state: &mut State) {}
+}
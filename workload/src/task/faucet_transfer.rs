@@ -1,9 +1,9 @@
-use namada_sdk::args::{self, InputAmount, TxBuilder, TxTransparentSource, TxTransparentTarget};
+use namada_sdk::args::{self, TxBuilder, TxTransparentSource, TxTransparentTarget};
 use namada_sdk::signing::SigningTxData;
-use namada_sdk::token::{self, DenominatedAmount};
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -12,11 +12,12 @@ use crate::error::TaskError;
 use crate::state::State;
 use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount};
-use crate::utils::{get_balance, RetryConfig};
+use crate::utils::{get_balance, get_token, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct FaucetTransfer {
     target: Alias,
+    denom: String,
     amount: Amount,
     settings: TaskSettings,
 }
@@ -27,7 +28,10 @@ impl TaskContext for FaucetTransfer {
     }
 
     fn summary(&self) -> String {
-        format!("faucet-transfer/{}/{}", self.target.name, self.amount)
+        format!(
+            "faucet-transfer/{}/{}/{}",
+            self.target.name, self.denom, self.amount
+        )
     }
 
     fn task_settings(&self) -> Option<&TaskSettings> {
@@ -35,10 +39,11 @@ impl TaskContext for FaucetTransfer {
     }
 
     async fn build_tx(&self, ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError> {
+        let (token_address, amount) = get_token(ctx, &self.denom, self.amount).await?;
+
         let wallet = ctx.namada.wallet.read().await;
 
         let faucet_alias = Alias::faucet();
-        let native_token_alias = Alias::nam();
 
         let source_address = wallet
             .find_address(&faucet_alias.name)
@@ -48,20 +53,9 @@ impl TaskContext for FaucetTransfer {
             .find_address(&self.target.name)
             .ok_or_else(|| TaskError::Wallet(format!("No target address: {}", self.target.name)))?
             .into_owned();
-        let token_address = wallet
-            .find_address(&native_token_alias.name)
-            .ok_or_else(|| {
-                TaskError::Wallet(format!(
-                    "No native token address: {}",
-                    native_token_alias.name
-                ))
-            })?
-            .into_owned();
         let fee_payer = wallet
             .find_public_key(&self.settings.gas_payer.name)
             .map_err(|e| TaskError::Wallet(e.to_string()))?;
-        let token_amount = token::Amount::from_u64(self.amount);
-        let amount = InputAmount::Unvalidated(DenominatedAmount::native(token_amount));
 
         let sources = vec![TxTransparentSource {
             source: source_address,
@@ -103,20 +97,19 @@ impl TaskContext for FaucetTransfer {
         ctx: &Ctx,
         retry_config: RetryConfig,
     ) -> Result<Vec<Check>, TaskError> {
-        let denom = Alias::nam().name;
-        let (_, pre_balance) = get_balance(ctx, &self.target, &denom, retry_config).await?;
+        let (_, pre_balance) = get_balance(ctx, &self.target, &self.denom, retry_config).await?;
 
         Ok(vec![Check::BalanceTarget(
             check::balance_target::BalanceTarget::builder()
                 .target(self.target.clone())
                 .pre_balance(pre_balance)
-                .denom(denom)
+                .denom(self.denom.clone())
                 .amount(self.amount)
                 .build(),
         )])
     }
 
     fn update_state(&self, state: &mut State) {
-        state.increase_balance(&self.target, self.amount);
+        state.increase_balance(&self.target, &self.denom, self.amount);
     }
 }
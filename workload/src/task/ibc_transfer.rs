@@ -11,6 +11,7 @@ use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
 use namada_sdk::{TransferSource, TransferTarget};
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -18,7 +19,7 @@ use crate::constants::IBC_TIMEOUT_HEIGHT_OFFSET;
 use crate::context::Ctx;
 use crate::error::TaskError;
 use crate::state::State;
-use crate::task::{TaskContext, TaskSettings};
+use crate::task::{build_masp_fee_check, TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, Height, MaspEpoch};
 use crate::utils::{
     base_denom, build_cosmos_ibc_transfer, cosmos_denom_hash, execute_tx, gen_shielding_tx,
@@ -27,7 +28,7 @@ use crate::utils::{
     shielded_sync_with_retry, wait_block_settlement, RetryConfig,
 };
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct IbcTransferSend {
     source: Alias,
     receiver: Alias,
@@ -160,16 +161,14 @@ impl TaskContext for IbcTransferSend {
     }
 
     fn update_state(&self, state: &mut State) {
+        state.decrease_balance(&self.source, &self.denom, self.amount);
         if is_native_denom(&self.denom) {
-            state.decrease_balance(&self.source, self.amount);
             state.increase_foreign_balance(&self.receiver, self.amount);
-        } else {
-            state.decrease_ibc_balance(&self.source, &self.denom, self.amount);
         }
     }
 }
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct IbcTransferRecv {
     sender: Alias,
     target: Alias,
@@ -291,16 +290,16 @@ impl TaskContext for IbcTransferRecv {
     fn update_state(&self, state: &mut State) {
         if self.denom == ibc_denom(&self.dest_channel_id, &Alias::nam().name) {
             // receiving NAM
-            state.increase_balance(&self.target, self.amount);
+            state.increase_balance(&self.target, &Alias::nam().name, self.amount);
             state.decrease_foreign_balance(&self.sender, self.amount);
         } else {
             let ibc_denom = ibc_denom(&self.dest_channel_id, &self.denom);
-            state.increase_ibc_balance(&self.target, &ibc_denom, self.amount);
+            state.increase_balance(&self.target, &ibc_denom, self.amount);
         }
     }
 }
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct IbcShieldingTransfer {
     sender: Alias,
     target: Alias,
@@ -442,16 +441,16 @@ impl TaskContext for IbcShieldingTransfer {
     fn update_state(&self, state: &mut State) {
         if self.denom == ibc_denom(&self.dest_channel_id, &Alias::nam().name) {
             // receiving NAM
-            state.increase_masp_balance(&self.target, self.amount);
+            state.increase_masp_balance(&self.target, &Alias::nam().name, self.amount);
             state.decrease_foreign_balance(&self.sender, self.amount);
         } else {
             let ibc_denom = ibc_denom(&self.dest_channel_id, &self.denom);
-            state.increase_ibc_balance(&self.target, &ibc_denom, self.amount);
+            state.increase_masp_balance(&self.target, &ibc_denom, self.amount);
         }
     }
 }
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct IbcUnshieldingTransfer {
     source: Alias,
     receiver: Alias,
@@ -602,15 +601,17 @@ impl TaskContext for IbcUnshieldingTransfer {
                 .build(),
         );
 
-        Ok(vec![source_check])
+        let fee_check =
+            build_masp_fee_check(ctx, &self.settings, &self.denom, self.amount, retry_config)
+                .await?;
+
+        Ok([source_check].into_iter().chain(fee_check).collect())
     }
 
     fn update_state(&self, state: &mut State) {
+        state.decrease_masp_balance(&self.source, &self.denom, self.amount);
         if is_native_denom(&self.denom) {
-            state.decrease_masp_balance(&self.source, self.amount);
             state.increase_foreign_balance(&self.receiver, self.amount);
-        } else {
-            state.decrease_ibc_balance(&self.source, &self.denom, self.amount);
         }
     }
 }
@@ -5,6 +5,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -15,7 +16,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Threshold};
 use crate::utils::RetryConfig;
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct InitAccount {
     target: Alias,
     sources: BTreeSet<Alias>,
@@ -1,6 +1,7 @@
 use namada_sdk::args;
 use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::Tx;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -11,7 +12,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::Alias;
 use crate::utils::{build_reveal_pk, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct NewWalletKeyPair {
     source: Alias,
 }
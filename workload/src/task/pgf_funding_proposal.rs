@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+
+use namada_sdk::args::{self, TxBuilder};
+use namada_sdk::governance::cli::onchain::{
+    OnChainProposal, PgfFunding, PgfFundingProposal as Proposal,
+};
+use namada_sdk::governance::storage::proposal::{PGFInternalTarget, PGFTarget};
+use namada_sdk::signing::SigningTxData;
+use namada_sdk::token;
+use namada_sdk::tx::data::GasLimit;
+use namada_sdk::tx::Tx;
+use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::constants::PROPOSAL_DEPOSIT;
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, Amount, Epoch};
+use crate::utils::{get_balance, RetryConfig};
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct PgfFundingProposal {
+    source: Alias,
+    continuous: Vec<(Alias, Amount)>,
+    retro: Vec<(Alias, Amount)>,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    grace_epoch: Epoch,
+    settings: TaskSettings,
+}
+
+impl TaskContext for PgfFundingProposal {
+    fn name(&self) -> String {
+        "pgf-funding-proposal".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "pgf-funding-proposal/{}/{}-continuous/{}-retro",
+            self.source.name,
+            self.continuous.len(),
+            self.retro.len()
+        )
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        Some(&self.settings)
+    }
+
+    async fn build_tx(&self, ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError> {
+        let wallet = ctx.namada.wallet.read().await;
+        let source_address = wallet
+            .find_address(&self.source.name)
+            .ok_or_else(|| TaskError::Wallet(format!("No source address: {}", self.source.name)))?;
+        let fee_payer = wallet
+            .find_public_key(&self.settings.gas_payer.name)
+            .map_err(|e| TaskError::Wallet(e.to_string()))?;
+
+        let to_targets = |fundings: &[(Alias, Amount)]| {
+            fundings
+                .iter()
+                .map(|(target, amount)| {
+                    let target_address = wallet.find_address(&target.name).ok_or_else(|| {
+                        TaskError::Wallet(format!("No target address: {}", target.name))
+                    })?;
+                    Ok(PGFTarget::Internal(PGFInternalTarget {
+                        target: target_address.into_owned(),
+                        amount: token::Amount::from_u64(*amount),
+                    }))
+                })
+                .collect::<Result<Vec<_>, TaskError>>()
+        };
+
+        let funding_proposal = Proposal {
+            proposal: OnChainProposal {
+                content: BTreeMap::from_iter([("workload".to_string(), "funding".to_string())]),
+                author: source_address.into_owned(),
+                voting_start_epoch: self.start_epoch.into(),
+                voting_end_epoch: self.end_epoch.into(),
+                activation_epoch: self.grace_epoch.into(),
+            },
+            data: PgfFunding {
+                continuous: to_targets(&self.continuous)?,
+                retro: to_targets(&self.retro)?,
+            },
+        };
+        let proposal_json =
+            serde_json::to_string(&funding_proposal).expect("Encoding proposal shouldn't fail");
+
+        let mut funding_proposal_tx_builder = ctx
+            .namada
+            .new_init_proposal(proposal_json.into_bytes())
+            .is_pgf_funding(true);
+
+        funding_proposal_tx_builder =
+            funding_proposal_tx_builder.gas_limit(GasLimit::from(self.settings.gas_limit));
+        funding_proposal_tx_builder = funding_proposal_tx_builder.wrapper_fee_payer(fee_payer);
+
+        let mut signing_keys = vec![];
+        for signer in &self.settings.signers {
+            let public_key = wallet
+                .find_public_key(&signer.name)
+                .map_err(|e| TaskError::Wallet(e.to_string()))?;
+            signing_keys.push(public_key)
+        }
+        funding_proposal_tx_builder = funding_proposal_tx_builder.signing_keys(signing_keys);
+        drop(wallet);
+
+        let (funding_proposal, signing_data) = funding_proposal_tx_builder
+            .build(&ctx.namada)
+            .await
+            .map_err(|e| TaskError::BuildTx(e.to_string()))?;
+
+        Ok((
+            funding_proposal,
+            vec![signing_data],
+            funding_proposal_tx_builder.tx,
+        ))
+    }
+
+    async fn build_checks(
+        &self,
+        ctx: &Ctx,
+        retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        let denom = Alias::nam().name;
+        let (_, pre_balance) = get_balance(ctx, &self.source, &denom, retry_config).await?;
+
+        Ok(vec![Check::BalanceSource(
+            check::balance_source::BalanceSource::builder()
+                .target(self.source.clone())
+                .pre_balance(pre_balance)
+                .denom(denom)
+                .amount(PROPOSAL_DEPOSIT)
+                .build(),
+        )])
+    }
+
+    fn update_state(&self, state: &mut State) {
+        state.decrease_balance(&self.source, &Alias::nam().name, PROPOSAL_DEPOSIT);
+        // the author and the recipients are kept until the proposal is settled by query-pgf
+        state.reserve_for_pgf(&self.source);
+        for (target, _) in self.continuous.iter().chain(&self.retro) {
+            state.reserve_for_pgf(target);
+        }
+    }
+}
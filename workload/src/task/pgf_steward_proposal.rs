@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use namada_sdk::args::{self, TxBuilder};
+use namada_sdk::governance::cli::onchain::{
+    OnChainProposal, PgfStewardProposal as Proposal, StewardsUpdate,
+};
+use namada_sdk::signing::SigningTxData;
+use namada_sdk::tx::data::GasLimit;
+use namada_sdk::tx::Tx;
+use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::constants::PROPOSAL_DEPOSIT;
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, Epoch};
+use crate::utils::{get_balance, RetryConfig};
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct PgfStewardProposal {
+    source: Alias,
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    grace_epoch: Epoch,
+    settings: TaskSettings,
+}
+
+impl TaskContext for PgfStewardProposal {
+    fn name(&self) -> String {
+        "pgf-steward-proposal".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!("pgf-steward-proposal/{}", self.source.name)
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        Some(&self.settings)
+    }
+
+    async fn build_tx(&self, ctx: &Ctx) -> Result<(Tx, Vec<SigningTxData>, args::Tx), TaskError> {
+        let wallet = ctx.namada.wallet.read().await;
+        let source_address = wallet
+            .find_address(&self.source.name)
+            .ok_or_else(|| TaskError::Wallet(format!("No source address: {}", self.source.name)))?
+            .into_owned();
+        let fee_payer = wallet
+            .find_public_key(&self.settings.gas_payer.name)
+            .map_err(|e| TaskError::Wallet(e.to_string()))?;
+
+        // Only the author can be added as a steward
+        let steward_proposal = Proposal {
+            proposal: OnChainProposal {
+                content: BTreeMap::from_iter([("workload".to_string(), "steward".to_string())]),
+                author: source_address.clone(),
+                voting_start_epoch: self.start_epoch.into(),
+                voting_end_epoch: self.end_epoch.into(),
+                activation_epoch: self.grace_epoch.into(),
+            },
+            data: StewardsUpdate {
+                add: Some(source_address),
+                remove: vec![],
+            },
+        };
+        let proposal_json =
+            serde_json::to_string(&steward_proposal).expect("Encoding proposal shouldn't fail");
+
+        let mut steward_proposal_tx_builder = ctx
+            .namada
+            .new_init_proposal(proposal_json.into_bytes())
+            .is_pgf_stewards(true);
+
+        steward_proposal_tx_builder =
+            steward_proposal_tx_builder.gas_limit(GasLimit::from(self.settings.gas_limit));
+        steward_proposal_tx_builder = steward_proposal_tx_builder.wrapper_fee_payer(fee_payer);
+
+        let mut signing_keys = vec![];
+        for signer in &self.settings.signers {
+            let public_key = wallet
+                .find_public_key(&signer.name)
+                .map_err(|e| TaskError::Wallet(e.to_string()))?;
+            signing_keys.push(public_key)
+        }
+        steward_proposal_tx_builder = steward_proposal_tx_builder.signing_keys(signing_keys);
+        drop(wallet);
+
+        let (steward_proposal, signing_data) = steward_proposal_tx_builder
+            .build(&ctx.namada)
+            .await
+            .map_err(|e| TaskError::BuildTx(e.to_string()))?;
+
+        Ok((
+            steward_proposal,
+            vec![signing_data],
+            steward_proposal_tx_builder.tx,
+        ))
+    }
+
+    async fn build_checks(
+        &self,
+        ctx: &Ctx,
+        retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        let denom = Alias::nam().name;
+        let (_, pre_balance) = get_balance(ctx, &self.source, &denom, retry_config).await?;
+
+        Ok(vec![Check::BalanceSource(
+            check::balance_source::BalanceSource::builder()
+                .target(self.source.clone())
+                .pre_balance(pre_balance)
+                .denom(denom)
+                .amount(PROPOSAL_DEPOSIT)
+                .build(),
+        )])
+    }
+
+    fn update_state(&self, state: &mut State) {
+        state.decrease_balance(&self.source, &Alias::nam().name, PROPOSAL_DEPOSIT);
+        // the author is kept until the proposal is settled by query-pgf
+        state.reserve_for_pgf(&self.source);
+    }
+}
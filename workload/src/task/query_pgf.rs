@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, Amount, Epoch, Height, ProposalId, ProposalKind};
+use crate::utils::{get_block_height, retry_config, RetryConfig};
+
+/// PGF payments to the recipient since the epoch
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct PgfPayment {
+    pub target: Alias,
+    pub pre_balance: Amount,
+    /// Retro funding and the refunded deposit
+    #[builder(default)]
+    pub one_off: Amount,
+    /// Continuous funding paid at the beginning of every epoch
+    #[builder(default)]
+    pub amount: Amount,
+    pub from_epoch: Epoch,
+}
+
+impl PgfPayment {
+    pub fn paid_until(&self, epoch: Epoch) -> Amount {
+        let epochs = (epoch + 1).saturating_sub(self.from_epoch);
+        self.one_off + self.amount * epochs
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct QueryPgf {
+    epoch: Epoch,
+    /// Activated proposals and whether they passed
+    settled: Vec<(ProposalId, bool)>,
+    payments: Vec<PgfPayment>,
+    stewards: Vec<(Alias, bool)>,
+}
+
+impl TaskContext for QueryPgf {
+    fn name(&self) -> String {
+        "query-pgf".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "query-pgf/{}-settled/{}-payments",
+            self.settled.len(),
+            self.payments.len()
+        )
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        None
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        Ok(get_block_height(ctx, retry_config()).await?)
+    }
+
+    async fn build_checks(
+        &self,
+        _ctx: &Ctx,
+        _retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        let payment_checks = self.payments.iter().map(|payment| {
+            Check::PgfFunding(
+                check::pgf_funding::PgfFunding::builder()
+                    .payment(payment.clone())
+                    .build(),
+            )
+        });
+        let steward_checks = self.stewards.iter().map(|(steward, is_steward)| {
+            Check::PgfSteward(
+                check::pgf_steward::PgfSteward::builder()
+                    .target(steward.clone())
+                    .is_steward(*is_steward)
+                    .build(),
+            )
+        });
+
+        Ok(payment_checks.chain(steward_checks).collect())
+    }
+
+    fn update_state(&self, state: &mut State) {
+        for payment in &self.payments {
+            state.increase_balance(
+                &payment.target,
+                &Alias::nam().name,
+                payment.paid_until(self.epoch),
+            );
+            if payment.amount > 0 {
+                state.set_pgf_funding(&payment.target, payment.amount, self.epoch);
+            }
+        }
+
+        for (proposal_id, is_passed) in &self.settled {
+            let Some(proposal) = state.proposals.get(proposal_id).cloned() else {
+                continue;
+            };
+            state.settle_proposal(*proposal_id);
+            match proposal.kind {
+                ProposalKind::PgfSteward {
+                    steward: Some(steward),
+                } if *is_passed => state.set_as_steward(&steward),
+                ProposalKind::PgfFunding { continuous, retro } => {
+                    for (target, _) in continuous.iter().chain(&retro) {
+                        state.release_from_pgf(target);
+                    }
+                }
+                _ => {}
+            }
+            state.release_from_pgf(&proposal.author);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use namada_sdk::dec::Dec;
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Alias, Amount, Epoch, Height, ValidatorAddress};
+use crate::utils::{get_block_height, retry_config, RetryConfig};
+
+/// The bond and the unbond of the source before and after the slash was processed
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct SlashedBond {
+    pub source: Alias,
+    pub validator: ValidatorAddress,
+    pub rate: Dec,
+    pub pre_bond: Amount,
+    pub slashable_bond: Amount,
+    pub post_bond: Amount,
+    pub pre_unbond: Amount,
+    pub slashable_unbond: Amount,
+    pub post_unbond: Amount,
+}
+
+/// The processed slash of a double sign of the validator
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct ProcessedSlash {
+    pub target: Alias,
+    pub validator: ValidatorAddress,
+    pub infraction_epoch: Epoch,
+    pub rate: Dec,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct QuerySlashes {
+    jailed: Vec<Alias>,
+    slashed: Vec<ProcessedSlash>,
+    bonds: Vec<SlashedBond>,
+    epoch: Epoch,
+}
+
+impl TaskContext for QuerySlashes {
+    fn name(&self) -> String {
+        "query-slashes".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "query-slashes/{}-jailed/{}-slashed",
+            self.jailed.len(),
+            self.slashed.len()
+        )
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        None
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        Ok(get_block_height(ctx, retry_config()).await?)
+    }
+
+    async fn build_checks(
+        &self,
+        _ctx: &Ctx,
+        _retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        let slashes = self.slashed.iter().map(|slash| {
+            Check::SlashProcessed(
+                check::slash_processed::SlashProcessed::builder()
+                    .target(slash.target.clone())
+                    .validator(slash.validator.clone())
+                    .infraction_epoch(slash.infraction_epoch)
+                    .rate(slash.rate)
+                    .build(),
+            )
+        });
+        let bonds = self.bonds.iter().map(|bond| {
+            Check::BondSlashed(
+                check::bond_slashed::BondSlashed::builder()
+                    .target(bond.source.clone())
+                    .validator(bond.validator.clone())
+                    .rate(bond.rate)
+                    .pre_bond(bond.pre_bond)
+                    .slashable_bond(bond.slashable_bond)
+                    .pre_unbond(bond.pre_unbond)
+                    .slashable_unbond(bond.slashable_unbond)
+                    .epoch(self.epoch)
+                    .build(),
+            )
+        });
+
+        Ok(slashes.chain(bonds).collect())
+    }
+
+    fn update_state(&self, state: &mut State) {
+        for alias in &self.jailed {
+            state.set_validator_as_jailed(alias, self.epoch);
+        }
+        for bond in &self.bonds {
+            state.apply_slash(
+                &bond.source,
+                &bond.validator,
+                bond.post_bond,
+                bond.post_unbond,
+            );
+        }
+        for slash in &self.slashed {
+            state.remove_pending_slash(&slash.target);
+        }
+    }
+}
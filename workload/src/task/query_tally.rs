@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Amount, Height, ProposalId, ProposalVote};
+use crate::utils::{get_block_height, retry_config, RetryConfig};
+
+/// Voting powers of the proposal recomputed from the bonds
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct ExpectedTally {
+    pub proposal_id: ProposalId,
+    #[builder(default)]
+    pub yay: Amount,
+    #[builder(default)]
+    pub nay: Amount,
+    #[builder(default)]
+    pub abstain: Amount,
+}
+
+impl ExpectedTally {
+    pub fn power_mut(&mut self, vote: &ProposalVote) -> &mut Amount {
+        match vote {
+            ProposalVote::Yay => &mut self.yay,
+            ProposalVote::Nay => &mut self.nay,
+            ProposalVote::Abstain => &mut self.abstain,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct QueryTally {
+    tallies: Vec<ExpectedTally>,
+    /// Tallied proposals whose stakes aren't tracked exactly
+    untracked: Vec<ProposalId>,
+}
+
+impl TaskContext for QueryTally {
+    fn name(&self) -> String {
+        "query-tally".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "query-tally/{}-tallies/{}-untracked",
+            self.tallies.len(),
+            self.untracked.len()
+        )
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        None
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        Ok(get_block_height(ctx, retry_config()).await?)
+    }
+
+    async fn build_checks(
+        &self,
+        _ctx: &Ctx,
+        _retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        Ok(self
+            .tallies
+            .iter()
+            .map(|tally| {
+                Check::ProposalTally(
+                    check::proposal_tally::ProposalTally::builder()
+                        .tally(tally.clone())
+                        .build(),
+                )
+            })
+            .collect())
+    }
+
+    fn update_state(&self, state: &mut State) {
+        let tallied = self.tallies.iter().map(|tally| &tally.proposal_id);
+        for proposal_id in tallied.chain(&self.untracked) {
+            state.tally_proposal(*proposal_id);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use typed_builder::TypedBuilder;
+
+use crate::check::{self, Check};
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::state::State;
+use crate::task::{TaskContext, TaskSettings};
+use crate::types::{Height, ProposalId};
+use crate::utils::{get_block_height, retry_config, RetryConfig};
+
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
+pub struct QueryWasmProposals {
+    /// Activated proposals with their marker and whether they passed
+    settled: Vec<(ProposalId, u64, bool)>,
+}
+
+impl TaskContext for QueryWasmProposals {
+    fn name(&self) -> String {
+        "query-wasm-proposals".to_string()
+    }
+
+    fn summary(&self) -> String {
+        format!("query-wasm-proposals/{}-settled", self.settled.len())
+    }
+
+    fn task_settings(&self) -> Option<&TaskSettings> {
+        None
+    }
+
+    async fn execute(&self, ctx: &Ctx) -> Result<Height, TaskError> {
+        Ok(get_block_height(ctx, retry_config()).await?)
+    }
+
+    async fn build_checks(
+        &self,
+        _ctx: &Ctx,
+        _retry_config: RetryConfig,
+    ) -> Result<Vec<Check>, TaskError> {
+        Ok(self
+            .settled
+            .iter()
+            .map(|(proposal_id, marker, is_passed)| {
+                Check::WasmProposalExecuted(
+                    check::wasm_proposal_executed::WasmProposalExecuted::builder()
+                        .proposal_id(*proposal_id)
+                        .marker(*marker)
+                        .is_passed(*is_passed)
+                        .build(),
+                )
+            })
+            .collect())
+    }
+
+    fn update_state(&self, state: &mut State) {
+        for (proposal_id, _, _) in &self.settled {
+            state.settle_proposal(*proposal_id);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use namada_sdk::token;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -17,7 +18,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, Epoch, ValidatorAddress};
 use crate::utils::{get_bond, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Redelegate {
     source: Alias,
     from_validator: ValidatorAddress,
@@ -146,6 +147,7 @@ impl TaskContext for Redelegate {
             &self.from_validator,
             &self.to_validator,
             self.amount,
+            self.epoch,
         )
     }
 }
@@ -5,6 +5,7 @@ use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -14,11 +15,10 @@ use crate::state::State;
 use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, Height, MaspEpoch};
 use crate::utils::{
-    get_balance, get_shielded_balance, get_token, is_native_denom, shielded_sync_with_retry,
-    RetryConfig,
+    get_balance, get_shielded_balance, get_token, shielded_sync_with_retry, RetryConfig,
 };
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Shielding {
     source: Alias,
     target: Alias,
@@ -138,12 +138,7 @@ impl TaskContext for Shielding {
     }
 
     fn update_state(&self, state: &mut State) {
-        if is_native_denom(&self.denom) {
-            state.decrease_balance(&self.source, self.amount);
-            state.increase_masp_balance(&self.target, self.amount);
-        } else {
-            state.decrease_ibc_balance(&self.source, &self.denom, self.amount);
-            state.increase_ibc_balance(&self.target, &self.denom, self.amount);
-        }
+        state.decrease_balance(&self.source, &self.denom, self.amount);
+        state.increase_masp_balance(&self.target, &self.denom, self.amount);
     }
 }
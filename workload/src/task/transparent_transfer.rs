@@ -3,6 +3,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -11,9 +12,9 @@ use crate::error::TaskError;
 use crate::state::State;
 use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, Amount};
-use crate::utils::{get_balance, get_token, is_native_denom, RetryConfig};
+use crate::utils::{get_balance, get_token, RetryConfig};
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct TransparentTransfer {
     source: Alias,
     target: Alias,
@@ -116,12 +117,7 @@ impl TaskContext for TransparentTransfer {
     }
 
     fn update_state(&self, state: &mut State) {
-        if is_native_denom(&self.denom) {
-            state.decrease_balance(&self.source, self.amount);
-            state.increase_balance(&self.target, self.amount);
-        } else {
-            state.decrease_ibc_balance(&self.source, &self.denom, self.amount);
-            state.increase_ibc_balance(&self.target, &self.denom, self.amount);
-        }
+        state.decrease_balance(&self.source, &self.denom, self.amount);
+        state.increase_balance(&self.target, &self.denom, self.amount);
     }
 }
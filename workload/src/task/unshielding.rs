@@ -7,20 +7,20 @@ use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
 use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
 use crate::context::Ctx;
 use crate::error::TaskError;
 use crate::state::State;
-use crate::task::{TaskContext, TaskSettings};
+use crate::task::{build_masp_fee_check, TaskContext, TaskSettings};
 use crate::types::{Alias, Amount, Height, MaspEpoch};
 use crate::utils::{
-    get_balance, get_shielded_balance, get_token, is_native_denom, shielded_sync_with_retry,
-    RetryConfig,
+    get_balance, get_shielded_balance, get_token, shielded_sync_with_retry, RetryConfig,
 };
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Unshielding {
     source: Alias,
     target: Alias,
@@ -161,16 +161,18 @@ impl TaskContext for Unshielding {
                 .build(),
         );
 
-        Ok(vec![source_check, target_check])
+        let fee_check =
+            build_masp_fee_check(ctx, &self.settings, &self.denom, self.amount, retry_config)
+                .await?;
+
+        Ok([source_check, target_check]
+            .into_iter()
+            .chain(fee_check)
+            .collect())
     }
 
     fn update_state(&self, state: &mut State) {
-        if is_native_denom(&self.denom) {
-            state.decrease_masp_balance(&self.source, self.amount);
-            state.increase_balance(&self.target, self.amount);
-        } else {
-            state.decrease_ibc_balance(&self.source, &self.denom, self.amount);
-            state.increase_ibc_balance(&self.target, &self.denom, self.amount);
-        }
+        state.decrease_masp_balance(&self.source, &self.denom, self.amount);
+        state.increase_balance(&self.target, &self.denom, self.amount);
     }
 }
@@ -3,6 +3,7 @@ use namada_sdk::signing::SigningTxData;
 use namada_sdk::tx::data::GasLimit;
 use namada_sdk::tx::Tx;
 use namada_sdk::Namada;
+use serde::{Deserialize, Serialize};
 use typed_builder::TypedBuilder;
 
 use crate::check::{self, Check};
@@ -13,7 +14,7 @@ use crate::task::{TaskContext, TaskSettings};
 use crate::types::{Alias, ProposalId, ProposalVote};
 use crate::utils::RetryConfig;
 
-#[derive(Clone, Debug, TypedBuilder)]
+#[derive(Clone, Debug, Serialize, Deserialize, TypedBuilder)]
 pub struct Vote {
     source: Alias,
     proposal_id: ProposalId,
@@ -85,5 +86,7 @@ impl TaskContext for Vote {
         )])
     }
 
-    fn update_state(&self, _state: &mut State) {}
+    fn update_state(&self, state: &mut State) {
+        state.add_vote(self.proposal_id, &self.source, self.vote.clone());
+    }
 }
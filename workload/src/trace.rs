@@ -0,0 +1,63 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::step::StepType;
+use crate::task::Task;
+use crate::types::StepId;
+use crate::utils::{base_dir, thread_id};
+
+#[derive(Error, Debug)]
+pub enum TraceError {
+    #[error("File error: `{0}`")]
+    File(std::io::Error),
+    #[error("Encode/Decode error: `{0}`")]
+    Serde(serde_json::Error),
+    #[error("Invalid step: `{0}`")]
+    InvalidStep(String),
+}
+
+/// Tasks built for a step
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub step_id: StepId,
+    pub step_type: String,
+    pub tasks: Vec<Task>,
+}
+
+impl TraceEntry {
+    pub fn step_type(&self) -> Result<StepType, TraceError> {
+        StepType::from_str(&self.step_type).map_err(TraceError::InvalidStep)
+    }
+}
+
+pub fn trace_path() -> PathBuf {
+    base_dir().join(format!("trace-{}.jsonl", thread_id()))
+}
+
+pub fn append_trace(path: &Path, entry: &TraceEntry) -> Result<(), TraceError> {
+    let json = serde_json::to_string(entry).map_err(TraceError::Serde)?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(TraceError::File)?;
+    writeln!(file, "{json}").map_err(TraceError::File)
+}
+
+pub fn load_trace(path: &Path) -> Result<Vec<TraceEntry>, TraceError> {
+    let content = std::fs::read_to_string(path).map_err(TraceError::File)?;
+    let entries: Vec<TraceEntry> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(TraceError::Serde))
+        .collect::<Result<_, _>>()?;
+    for entry in &entries {
+        entry.step_type()?;
+    }
+    Ok(entries)
+}
@@ -132,6 +132,8 @@ pub enum ValidatorStatus {
     Active,
     Reactivating,
     Inactive,
+    Jailed,
+    Unjailing,
 }
 
 impl fmt::Display for ValidatorStatus {
@@ -140,10 +142,71 @@ impl fmt::Display for ValidatorStatus {
             ValidatorStatus::Active => write!(f, "active"),
             ValidatorStatus::Inactive => write!(f, "inactive"),
             ValidatorStatus::Reactivating => write!(f, "reactivating"),
+            ValidatorStatus::Jailed => write!(f, "jailed"),
+            ValidatorStatus::Unjailing => write!(f, "unjailing"),
         }
     }
 }
 
+/// Whether the commission change should be accepted by the chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommissionChangeKind {
+    Valid,
+    ExceedingMaxChange,
+    AboveMaxRate,
+}
+
+impl fmt::Display for CommissionChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommissionChangeKind::Valid => write!(f, "valid"),
+            CommissionChangeKind::ExceedingMaxChange => write!(f, "exceeding-max-change"),
+            CommissionChangeKind::AboveMaxRate => write!(f, "above-max-rate"),
+        }
+    }
+}
+
+/// The proposal type with the workload accounts it targets
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProposalKind {
+    Default,
+    /// The marker is `None` when the code isn't built by the workload
+    DefaultWithWasm {
+        marker: Option<u64>,
+    },
+    PgfSteward {
+        steward: Option<Alias>,
+    },
+    PgfFunding {
+        continuous: Vec<(Alias, Amount)>,
+        retro: Vec<(Alias, Amount)>,
+    },
+}
+
+impl ProposalKind {
+    pub fn is_pgf(&self) -> bool {
+        matches!(
+            self,
+            ProposalKind::PgfSteward { .. } | ProposalKind::PgfFunding { .. }
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Proposal {
+    pub author: Alias,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub activation_epoch: Epoch,
+    pub kind: ProposalKind,
+    /// The result has been applied to the state
+    #[serde(default)]
+    pub is_settled: bool,
+    /// The tally has been checked
+    #[serde(default)]
+    pub is_tallied: bool,
+}
+
 pub type StepId = u64;
 pub type Amount = u64;
 pub type ValidatorAddress = String;
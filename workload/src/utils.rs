@@ -1,25 +1,34 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread_local;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use once_cell::sync::OnceCell;
 use rand::rngs::SmallRng;
 use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
 use tryhard::{backoff_strategies::ExponentialBackoff, NoOnRetry, RetryFutureConfig};
 
 use crate::constants::{INIT_DELAY_SEC, MAX_DELAY_SEC, MAX_RETRY_COUNT};
+use crate::metrics::METRICS;
 
 mod cosmos;
+mod evidence;
 mod ibc;
 mod query;
 mod tx;
+mod wallet;
+mod wasm;
 
 pub use cosmos::*;
+pub use evidence::*;
 pub use ibc::*;
 pub use query::*;
 pub use tx::*;
+pub use wallet::*;
+pub use wasm::*;
 
 pub fn base_dir() -> PathBuf {
     std::env::current_dir().unwrap().join("base")
@@ -39,6 +48,9 @@ thread_local! {
 
         RefCell::new(SmallRng::from_seed(seed_bytes))
     };
+
+    static RETRY_COUNT: Cell<u64> = const { Cell::new(0) };
+    static SETTLEMENT_WAIT: Cell<Duration> = const { Cell::new(Duration::ZERO) };
 }
 
 pub fn with_rng<F, R>(f: F) -> R
@@ -48,6 +60,38 @@ where
     THREAD_RNG.with(|rng| f(&mut rng.borrow_mut()))
 }
 
+/// RNG for the keys of the alias derived from the seed, the replay regenerates the same keys
+pub fn key_rng(alias: &str) -> ChaCha20Rng {
+    let seed = GLOBAL_SEED.get().expect("Seed must be initialized first");
+    let mut hasher = Sha256::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(alias.as_bytes());
+    ChaCha20Rng::from_seed(hasher.finalize().into())
+}
+
+pub fn count_retry() {
+    METRICS.retries.inc();
+    RETRY_COUNT.with(|count| count.set(count.get() + 1));
+}
+
+/// Return the number of retries since the last call
+pub fn take_retry_count() -> u64 {
+    RETRY_COUNT.with(|count| count.take())
+}
+
+pub fn record_settlement_wait(started: Instant) {
+    SETTLEMENT_WAIT.with(|wait| wait.set(wait.get() + started.elapsed()));
+}
+
+pub fn settlement_wait() -> Duration {
+    SETTLEMENT_WAIT.with(|wait| wait.get())
+}
+
+/// Return the time waiting for the block settlement since the last call
+pub fn take_settlement_wait() -> Duration {
+    SETTLEMENT_WAIT.with(|wait| wait.take())
+}
+
 pub fn thread_id() -> usize {
     THREAD_ID.with(|id| *id.borrow())
 }
@@ -1,4 +1,5 @@
 use std::str::FromStr;
+use std::time::Instant;
 
 use cosmrs::proto::prost::{Message, Name};
 use cosmrs::tx::{AuthInfo, Body, Fee, SignDoc, SignerInfo};
@@ -15,7 +16,7 @@ use crate::constants::{COSMOS_CHAIN_ID, COSMOS_FEE_AMOUNT, COSMOS_FEE_TOKEN, COS
 use crate::context::Ctx;
 use crate::error::{QueryError, TaskError};
 use crate::types::{Amount, Height};
-use crate::utils::RetryConfig;
+use crate::utils::{count_retry, record_settlement_wait, RetryConfig};
 
 pub fn build_cosmos_ibc_transfer(
     sender: &str,
@@ -119,6 +120,7 @@ pub async fn get_cosmos_height(ctx: &Ctx, retry_config: RetryConfig) -> Result<H
     let status = tryhard::retry_fn(|| ctx.cosmos.client.status())
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -131,6 +133,7 @@ pub async fn get_cosmos_height(ctx: &Ctx, retry_config: RetryConfig) -> Result<H
 }
 
 pub async fn wait_cosmos_settlement(ctx: &Ctx, height: Height) {
+    let started = Instant::now();
     loop {
         if let Ok(status) = ctx.cosmos.client.status().await {
             let current_height: u64 = status.sync_info.latest_block_height.into();
@@ -145,4 +148,5 @@ pub async fn wait_cosmos_settlement(ctx: &Ctx, height: Height) {
         }
         sleep(Duration::from_secs(2)).await
     }
+    record_settlement_wait(started);
 }
@@ -0,0 +1,128 @@
+use namada_sdk::key::{common, tm_consensus_key_raw_hash};
+use namada_sdk::tendermint::block::{self, parts};
+use namada_sdk::tendermint::evidence::{DuplicateVoteEvidence, Evidence};
+use namada_sdk::tendermint::vote::{self, ValidatorIndex, Vote};
+use namada_sdk::tendermint::{Hash, Signature};
+use namada_sdk::tendermint_rpc::{Client, Paging};
+
+use crate::constants::EVIDENCE_WAIT_BLOCKS;
+use crate::context::Ctx;
+use crate::error::TaskError;
+use crate::types::Height;
+use crate::utils::{wait_block_settlement, RetryConfig};
+
+/// Sign two prevotes for different blocks at the latest height with the consensus key and submit
+/// them as duplicate vote evidence. Returns the height of the votes.
+pub async fn submit_duplicate_votes(
+    ctx: &Ctx,
+    consensus_sk: &common::SecretKey,
+) -> Result<Height, TaskError> {
+    let client = &ctx.namada.client;
+    let common::SecretKey::Ed25519(signing_key) = consensus_sk else {
+        return Err(TaskError::Evidence(
+            "The consensus key isn't an ed25519 key".to_string(),
+        ));
+    };
+    let tm_address = tm_consensus_key_raw_hash(&consensus_sk.to_public());
+
+    let header = client
+        .latest_block()
+        .await
+        .map_err(|e| TaskError::Evidence(e.to_string()))?
+        .block
+        .header;
+    let validators = client
+        .validators(header.height, Paging::All)
+        .await
+        .map_err(|e| TaskError::Evidence(e.to_string()))?
+        .validators;
+    let (index, validator) = validators
+        .iter()
+        .enumerate()
+        .find(|(_, validator)| validator.address.to_string() == tm_address)
+        .ok_or_else(|| {
+            TaskError::Evidence(format!(
+                "{tm_address} isn't in the validator set at height {}",
+                header.height
+            ))
+        })?;
+    let total_voting_power = validators
+        .iter()
+        .map(|validator| validator.power())
+        .sum::<u64>()
+        .try_into()
+        .expect("Voting power should be valid");
+
+    // The votes should be ordered by their block ID
+    let mut votes = vec![];
+    for block_hash in [[1u8; 32], [2u8; 32]] {
+        let mut vote = Vote {
+            vote_type: vote::Type::Prevote,
+            height: header.height,
+            round: block::Round::default(),
+            block_id: Some(block::Id {
+                hash: Hash::Sha256(block_hash),
+                part_set_header: parts::Header::new(1, Hash::Sha256(block_hash))
+                    .expect("Part set header should be valid"),
+            }),
+            timestamp: Some(header.time),
+            validator_address: validator.address,
+            validator_index: ValidatorIndex::try_from(index as u32)
+                .expect("Validator index should be valid"),
+            signature: None,
+            extension: vec![],
+            extension_signature: None,
+        };
+        let sign_bytes = vote.clone().into_signable_vec(header.chain_id.clone());
+        let signature = signing_key.0.sign(&sign_bytes);
+        vote.signature = Some(
+            Signature::try_from(signature.to_bytes().as_slice())
+                .expect("Signature should be valid"),
+        );
+        votes.push(vote);
+    }
+    let vote_b = votes.pop().expect("Vote should exist");
+    let vote_a = votes.pop().expect("Vote should exist");
+
+    let mut evidence = DuplicateVoteEvidence::new(vote_a, vote_b)
+        .map_err(|e| TaskError::Evidence(e.to_string()))?;
+    evidence.total_voting_power = total_voting_power;
+    evidence.validator_power = validator.power;
+    evidence.timestamp = header.time;
+
+    client
+        .broadcast_evidence(Evidence::from(evidence))
+        .await
+        .map_err(|e| TaskError::Evidence(e.to_string()))?;
+
+    Ok(header.height.value())
+}
+
+/// Find the block committing the duplicate vote evidence of the validator at the height
+pub async fn find_duplicate_vote_evidence(
+    ctx: &Ctx,
+    tm_address: &str,
+    infraction_height: Height,
+    retry_config: RetryConfig,
+) -> Result<Option<Height>, TaskError> {
+    let client = &ctx.namada.client;
+    for height in infraction_height + 1..=infraction_height + EVIDENCE_WAIT_BLOCKS {
+        wait_block_settlement(ctx, height, retry_config).await;
+        let block = client
+            .block(height as u32)
+            .await
+            .map_err(|e| TaskError::Evidence(e.to_string()))?
+            .block;
+        let is_committed = block.evidence.iter().any(|evidence| match evidence {
+            Evidence::DuplicateVote(evidence) => {
+                evidence.vote_a.height.value() == infraction_height
+                    && evidence.vote_a.validator_address.to_string() == tm_address
+            }
+            _ => false,
+        });
+        if is_committed {
+            return Ok(Some(height));
+        }
+    }
+    Ok(None)
+}
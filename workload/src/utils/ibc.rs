@@ -26,7 +26,8 @@ use crate::context::Ctx;
 use crate::error::QueryError;
 use crate::types::{Alias, Height};
 use crate::utils::{
-    get_block_height, get_cosmos_height, wait_block_settlement, wait_cosmos_settlement, RetryConfig,
+    count_retry, get_block_height, get_cosmos_height, wait_block_settlement,
+    wait_cosmos_settlement, RetryConfig,
 };
 
 pub fn is_native_denom(denom: &str) -> bool {
@@ -91,6 +92,7 @@ pub async fn get_ibc_packet_sequence(
     let block_results = tryhard::retry_fn(query_fn)
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -205,6 +207,7 @@ pub async fn is_ibc_transfer_successful(
     let block = tryhard::retry_fn(|| ctx.namada.client.block(height.0))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
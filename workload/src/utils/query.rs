@@ -1,16 +1,25 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::time::{self, Instant};
 
 use namada_sdk::account::Account;
 use namada_sdk::address::Address;
 use namada_sdk::args::InputAmount;
+use namada_sdk::borsh::BorshDeserialize;
 use namada_sdk::control_flow::install_shutdown_signal;
+use namada_sdk::governance::storage::keys::get_proposal_code_key;
+use namada_sdk::governance::storage::proposal::{AddRemove, PGFAction, PGFTarget, ProposalType};
+use namada_sdk::governance::utils::{ProposalResult, TallyResult, Vote};
 use namada_sdk::io::DevNullProgressBar;
+use namada_sdk::key::common;
 use namada_sdk::masp::shielded_wallet::ShieldedApi;
 use namada_sdk::masp::{IndexerMaspClient, LedgerMaspClient, MaspLocalTaskEnv, ShieldedSyncConfig};
 use namada_sdk::masp_primitives::zip32;
-use namada_sdk::proof_of_stake::types::ValidatorStateInfo;
+use namada_sdk::proof_of_stake::types::{
+    CommissionPair, Slash, ValidatorState, ValidatorStateInfo,
+};
+use namada_sdk::proof_of_stake::PosParams;
+use namada_sdk::queries::RPC;
 use namada_sdk::token::{self, DenominatedAmount, MaspEpoch};
 use namada_sdk::{rpc, Namada};
 use namada_wallet::DatedKeypair;
@@ -18,10 +27,17 @@ use reqwest::Url;
 use tokio::time::{sleep, Duration};
 use tryhard::{backoff_strategies::ExponentialBackoff, NoOnRetry, RetryFutureConfig};
 
+use crate::constants::PIPELINE_LEN;
 use crate::context::Ctx;
 use crate::error::QueryError;
-use crate::types::{Alias, Amount, Epoch, Height, ProposalId, ProposalVote};
-use crate::utils::{ibc_token_address, is_native_denom, RetryConfig};
+use crate::metrics::METRICS;
+use crate::types::{
+    Alias, Amount, Epoch, Height, Proposal, ProposalId, ProposalKind, ProposalVote,
+};
+use crate::utils::{
+    count_retry, find_wasm_marker, ibc_token_address, is_native_denom, record_settlement_wait,
+    retry_config, wasm_marker_key, RetryConfig,
+};
 
 pub async fn get_token(
     ctx: &Ctx,
@@ -31,12 +47,33 @@ pub async fn get_token(
     let wallet = ctx.namada.wallet.read().await;
 
     let token_amount = token::Amount::from_u64(amount);
-    let (token_address, denominated_amount) = if is_native_denom(denom) {
+    let (token_address, denominated_amount) = if denom == Alias::nam().name {
         let address = wallet
             .find_address(denom)
             .ok_or_else(|| QueryError::Wallet(format!("No native token address: {}", denom)))?
             .into_owned();
         (address, DenominatedAmount::native(token_amount))
+    } else if is_native_denom(denom) {
+        let address = wallet
+            .find_address(denom)
+            .ok_or_else(|| QueryError::Wallet(format!("No token address: {}", denom)))?
+            .into_owned();
+        drop(wallet);
+        // the amount is in the smallest unit of the token
+        let token = RPC.vp().token();
+        let denomination = tryhard::retry_fn(|| token.denomination(&ctx.namada.client, &address))
+            .with_config(retry_config())
+            .on_retry(|attempt, _, error| {
+                count_retry();
+                let error = error.to_string();
+                async move {
+                    tracing::info!("Retry {attempt} due to {error}...");
+                }
+            })
+            .await
+            .map_err(|e| QueryError::Rpc(namada_sdk::error::Error::Other(e.to_string())))?
+            .ok_or_else(|| QueryError::Wallet(format!("No denomination of token: {}", denom)))?;
+        (address, DenominatedAmount::new(token_amount, denomination))
     } else {
         (
             ibc_token_address(denom),
@@ -63,6 +100,7 @@ pub async fn get_account_info(
     let account = tryhard::retry_fn(|| rpc::get_account_info(&ctx.namada.client, &source_address))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -89,6 +127,7 @@ pub async fn is_validator(
     let is_validator = tryhard::retry_fn(|| rpc::is_validator(&ctx.namada.client, &source_address))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -118,6 +157,7 @@ pub async fn get_validator_state(
     })
     .with_config(retry_config)
     .on_retry(|attempt, _, error| {
+        count_retry();
         let error = error.to_string();
         async move {
             tracing::info!("Retry {attempt} due to {error}...");
@@ -129,6 +169,22 @@ pub async fn get_validator_state(
     Ok((target_address, state))
 }
 
+/// The validator is jailed at the epoch or will be at the pipeline epoch
+pub async fn is_validator_jailed(
+    ctx: &Ctx,
+    target: &Alias,
+    epoch: Epoch,
+    retry_config: RetryConfig,
+) -> Result<bool, QueryError> {
+    for epoch in [epoch, epoch + PIPELINE_LEN] {
+        let (_, (state, _)) = get_validator_state(ctx, target, epoch, retry_config).await?;
+        if matches!(state, Some(ValidatorState::Jailed)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 pub async fn get_validator_addresses(
     ctx: &Ctx,
     retry_config: RetryConfig,
@@ -144,6 +200,33 @@ pub async fn get_validator_addresses(
     Ok(validators)
 }
 
+/// The total stake of the consensus validators at the epoch
+pub async fn get_consensus_stake(
+    ctx: &Ctx,
+    epoch: Epoch,
+    retry_config: RetryConfig,
+) -> Result<token::Amount, QueryError> {
+    let validators =
+        tryhard::retry_fn(|| rpc::get_all_consensus_validators(&ctx.namada.client, epoch.into()))
+            .with_config(retry_config)
+            .on_retry(|attempt, _, error| {
+                count_retry();
+                let error = error.to_string();
+                async move {
+                    tracing::info!("Retry {attempt} due to {error}...");
+                }
+            })
+            .await
+            .map_err(QueryError::Rpc)?;
+
+    Ok(validators
+        .into_iter()
+        .try_fold(token::Amount::zero(), |acc, v| {
+            acc.checked_add(v.bonded_stake)
+        })
+        .expect("Total stake shouldn't overflow"))
+}
+
 pub async fn is_pk_revealed(
     ctx: &Ctx,
     target: &Alias,
@@ -159,6 +242,7 @@ pub async fn is_pk_revealed(
     tryhard::retry_fn(|| rpc::is_public_key_revealed(&ctx.namada.client, &target_address))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {attempt} due to {error}...");
@@ -192,6 +276,7 @@ pub async fn get_balance(
     })
     .with_config(retry_config)
     .on_retry(|attempt, _, error| {
+        count_retry();
         let error = error.to_string();
         async move {
             tracing::info!("Retry {attempt} due to {error}...");
@@ -257,6 +342,7 @@ pub async fn get_block_height(ctx: &Ctx, retry_config: RetryConfig) -> Result<He
     let block = tryhard::retry_fn(|| rpc::query_block(&ctx.namada.client))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -269,6 +355,7 @@ pub async fn get_block_height(ctx: &Ctx, retry_config: RetryConfig) -> Result<He
 }
 
 pub async fn wait_block_settlement(ctx: &Ctx, height: Height, retry_config: RetryConfig) {
+    let started = Instant::now();
     loop {
         if let Ok(current_height) = get_block_height(ctx, retry_config).await {
             if current_height > height {
@@ -283,19 +370,24 @@ pub async fn wait_block_settlement(ctx: &Ctx, height: Height, retry_config: Retr
         }
         sleep(Duration::from_secs(2)).await
     }
+    record_settlement_wait(started);
 }
 
 pub async fn get_epoch(ctx: &Ctx, retry_config: RetryConfig) -> Result<Epoch, QueryError> {
     tryhard::retry_fn(|| rpc::query_epoch(&ctx.namada.client))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
             }
         })
         .await
-        .map(|epoch| epoch.into())
+        .map(|epoch| {
+            METRICS.observe_epoch(epoch.0);
+            epoch.into()
+        })
         .map_err(QueryError::Rpc)
 }
 
@@ -303,6 +395,7 @@ pub async fn get_masp_epoch(ctx: &Ctx, retry_config: RetryConfig) -> Result<Masp
     tryhard::retry_fn(|| rpc::query_masp_epoch(&ctx.namada.client))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -312,6 +405,26 @@ pub async fn get_masp_epoch(ctx: &Ctx, retry_config: RetryConfig) -> Result<Masp
         .map_err(QueryError::Rpc)
 }
 
+pub async fn get_epoch_at_height(
+    ctx: &Ctx,
+    height: Height,
+    retry_config: RetryConfig,
+) -> Result<Epoch, QueryError> {
+    let epoch = tryhard::retry_fn(|| rpc::query_epoch_at_height(&ctx.namada.client, height.into()))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)?
+        .expect("Epoch should exist");
+    Ok(epoch.into())
+}
+
 pub async fn get_masp_epoch_at_height(
     ctx: &Ctx,
     height: Height,
@@ -320,6 +433,7 @@ pub async fn get_masp_epoch_at_height(
     let epoch = tryhard::retry_fn(|| rpc::query_epoch_at_height(&ctx.namada.client, height.into()))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -333,6 +447,7 @@ pub async fn get_masp_epoch_at_height(
         tryhard::retry_fn(|| rpc::query_storage_value(&ctx.namada.client, &key))
             .with_config(retry_config)
             .on_retry(|attempt, _, error| {
+                count_retry();
                 let error = error.to_string();
                 async move {
                     tracing::info!("Retry {} due to {}...", attempt, error);
@@ -370,6 +485,7 @@ pub async fn get_bond(
     })
     .with_config(retry_config)
     .on_retry(|attempt, _, error| {
+        count_retry();
         let error = error.to_string();
         async move {
             tracing::info!("Retry {} due to {}...", attempt, error);
@@ -379,6 +495,120 @@ pub async fn get_bond(
     .map_err(QueryError::Rpc)
 }
 
+pub async fn get_consensus_key(
+    ctx: &Ctx,
+    validator: &str,
+    retry_config: RetryConfig,
+) -> Result<Option<common::PublicKey>, QueryError> {
+    let validator_address =
+        Address::from_str(validator).expect("ValidatorAddress should be converted");
+
+    tryhard::retry_fn(|| {
+        rpc::query_validator_consensus_keys(&ctx.namada.client, &validator_address)
+    })
+    .with_config(retry_config)
+    .on_retry(|attempt, _, error| {
+        count_retry();
+        let error = error.to_string();
+        async move {
+            tracing::info!("Retry {attempt} due to {error}...");
+        }
+    })
+    .await
+    .map_err(QueryError::Rpc)
+}
+
+pub async fn get_pos_params(ctx: &Ctx, retry_config: RetryConfig) -> Result<PosParams, QueryError> {
+    tryhard::retry_fn(|| rpc::get_pos_params(&ctx.namada.client))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)
+}
+
+pub async fn get_commission_rate(
+    ctx: &Ctx,
+    validator: &str,
+    epoch: Epoch,
+    retry_config: RetryConfig,
+) -> Result<CommissionPair, QueryError> {
+    let validator_address =
+        Address::from_str(validator).expect("ValidatorAddress should be converted");
+
+    tryhard::retry_fn(|| {
+        rpc::query_commission_rate(&ctx.namada.client, &validator_address, Some(epoch.into()))
+    })
+    .with_config(retry_config)
+    .on_retry(|attempt, _, error| {
+        count_retry();
+        let error = error.to_string();
+        async move {
+            tracing::info!("Retry {attempt} due to {error}...");
+        }
+    })
+    .await
+    .map_err(QueryError::Rpc)
+}
+
+/// Slashes to be processed at the epoch, they aren't applied to the bonds yet
+pub async fn get_enqueued_slashes(
+    ctx: &Ctx,
+    validator: &str,
+    retry_config: RetryConfig,
+) -> Result<BTreeMap<Epoch, Vec<Slash>>, QueryError> {
+    let validator_address =
+        Address::from_str(validator).expect("ValidatorAddress should be converted");
+
+    let pos = RPC.vp().pos();
+    let mut slashes = tryhard::retry_fn(|| pos.enqueued_slashes(&ctx.namada.client))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(|e| QueryError::Rpc(namada_sdk::error::Error::Other(e.to_string())))?;
+
+    Ok(slashes
+        .swap_remove(&validator_address)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(epoch, slashes)| (epoch.into(), slashes))
+        .collect())
+}
+
+/// Slashes which have been applied to the bonds
+pub async fn get_validator_slashes(
+    ctx: &Ctx,
+    validator: &str,
+    retry_config: RetryConfig,
+) -> Result<Vec<Slash>, QueryError> {
+    let validator_address =
+        Address::from_str(validator).expect("ValidatorAddress should be converted");
+
+    let pos = RPC.vp().pos();
+    tryhard::retry_fn(|| pos.validator_slashes(&ctx.namada.client, &validator_address))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(|e| QueryError::Rpc(namada_sdk::error::Error::Other(e.to_string())))
+}
+
 pub async fn get_rewards(
     ctx: &Ctx,
     source: &Alias,
@@ -400,6 +630,7 @@ pub async fn get_rewards(
     })
     .with_config(retry_config)
     .on_retry(|attempt, _, error| {
+        count_retry();
         let error = error.to_string();
         async move {
             tracing::info!("Retry {} due to {}...", attempt, error);
@@ -420,6 +651,7 @@ pub async fn shielded_sync_with_retry(
     match tryhard::retry_fn(|| shielded_sync(ctx, height, with_indexer))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -446,6 +678,7 @@ pub async fn shielded_sync_with_retry(
     match tryhard::retry_fn(|| shielded_sync(ctx, height, false))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -554,16 +787,85 @@ async fn shielded_sync(
 pub async fn get_proposals(
     ctx: &Ctx,
     last_proposal_id: Option<ProposalId>,
-) -> Result<HashMap<ProposalId, (Epoch, Epoch)>, QueryError> {
+) -> Result<HashMap<ProposalId, Proposal>, QueryError> {
     let mut proposals = HashMap::new();
     let mut proposal_id = last_proposal_id.map(|id| id + 1).unwrap_or_default();
     while let Some(proposal) = rpc::query_proposal_by_id(&ctx.namada.client, proposal_id)
         .await
         .map_err(QueryError::Rpc)?
     {
+        let wallet = ctx.namada.wallet.read().await;
+        // Addresses out of the wallet aren't owned by this workload
+        let find_alias = |address: &Address| -> Option<Alias> {
+            wallet
+                .find_alias(address)
+                .map(|alias| Alias::from(alias.to_string()))
+        };
+        let find_target = |target: &PGFTarget| match target {
+            PGFTarget::Internal(target) => find_alias(&target.target).map(|alias| {
+                let amount = target
+                    .amount
+                    .to_string()
+                    .parse()
+                    .expect("Amount conversion shouldn't fail");
+                (alias, amount)
+            }),
+            PGFTarget::Ibc(_) => None,
+        };
+        let kind = match &proposal.r#type {
+            ProposalType::DefaultWithWasm(_) => {
+                let (code, _) = rpc::query_storage_value_bytes(
+                    &ctx.namada.client,
+                    &get_proposal_code_key(proposal_id),
+                    None,
+                    false,
+                )
+                .await
+                .map_err(QueryError::Rpc)?;
+                let marker = code
+                    .and_then(|code| Vec::<u8>::try_from_slice(&code).ok())
+                    .and_then(|code| find_wasm_marker(&code));
+                ProposalKind::DefaultWithWasm { marker }
+            }
+            ProposalType::PGFSteward(actions) => ProposalKind::PgfSteward {
+                steward: actions.iter().find_map(|action| match action {
+                    AddRemove::Add(address) => find_alias(address),
+                    AddRemove::Remove(_) => None,
+                }),
+            },
+            ProposalType::PGFPayment(actions) => ProposalKind::PgfFunding {
+                continuous: actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        PGFAction::Continuous(AddRemove::Add(target)) => find_target(target),
+                        _ => None,
+                    })
+                    .collect(),
+                retro: actions
+                    .iter()
+                    .filter_map(|action| match action {
+                        PGFAction::Retro(target) => find_target(target),
+                        _ => None,
+                    })
+                    .collect(),
+            },
+            _ => ProposalKind::Default,
+        };
+        let author = find_alias(&proposal.author)
+            .unwrap_or_else(|| Alias::from(proposal.author.to_string()));
+        drop(wallet);
+
         proposals.insert(
             proposal_id,
-            (proposal.voting_start_epoch.0, proposal.voting_end_epoch.0),
+            Proposal {
+                author,
+                start_epoch: proposal.voting_start_epoch.0,
+                end_epoch: proposal.voting_end_epoch.0,
+                activation_epoch: proposal.activation_epoch.0,
+                kind,
+                is_settled: false,
+                is_tallied: false,
+            },
         );
 
         proposal_id += 1;
@@ -572,6 +874,184 @@ pub async fn get_proposals(
     Ok(proposals)
 }
 
+/// Whether the proposal passed, `None` before the tally
+pub async fn get_proposal_result(
+    ctx: &Ctx,
+    proposal_id: ProposalId,
+    retry_config: RetryConfig,
+) -> Result<Option<bool>, QueryError> {
+    let result = tryhard::retry_fn(|| rpc::query_proposal_result(&ctx.namada.client, proposal_id))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)?;
+
+    Ok(result.map(|result| matches!(result.result, TallyResult::Passed)))
+}
+
+/// The value of the parameter written by the proposal code with the marker
+pub async fn get_wasm_marker(
+    ctx: &Ctx,
+    marker: u64,
+    retry_config: RetryConfig,
+) -> Result<Option<Vec<u8>>, QueryError> {
+    let key = wasm_marker_key(marker);
+    let (value, _) =
+        tryhard::retry_fn(|| rpc::query_storage_value_bytes(&ctx.namada.client, &key, None, false))
+            .with_config(retry_config)
+            .on_retry(|attempt, _, error| {
+                count_retry();
+                let error = error.to_string();
+                async move {
+                    tracing::info!("Retry {attempt} due to {error}...");
+                }
+            })
+            .await
+            .map_err(QueryError::Rpc)?;
+
+    Ok(value)
+}
+
+/// The tallied voting powers, `None` before the tally
+pub async fn get_proposal_tally(
+    ctx: &Ctx,
+    proposal_id: ProposalId,
+    retry_config: RetryConfig,
+) -> Result<Option<ProposalResult>, QueryError> {
+    tryhard::retry_fn(|| rpc::query_proposal_result(&ctx.namada.client, proposal_id))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)
+}
+
+pub async fn get_proposal_votes(
+    ctx: &Ctx,
+    proposal_id: ProposalId,
+    retry_config: RetryConfig,
+) -> Result<Vec<Vote>, QueryError> {
+    tryhard::retry_fn(|| rpc::query_proposal_votes(&ctx.namada.client, proposal_id))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)
+}
+
+/// Whether the votes involving the validator are tallied at the epoch
+pub async fn is_active_validator(
+    ctx: &Ctx,
+    validator: &Address,
+    epoch: Epoch,
+    retry_config: RetryConfig,
+) -> Result<bool, QueryError> {
+    let (state, _) = tryhard::retry_fn(|| {
+        rpc::get_validator_state(&ctx.namada.client, validator, Some(epoch.into()))
+    })
+    .with_config(retry_config)
+    .on_retry(|attempt, _, error| {
+        count_retry();
+        let error = error.to_string();
+        async move {
+            tracing::info!("Retry {attempt} due to {error}...");
+        }
+    })
+    .await
+    .map_err(QueryError::Rpc)?;
+
+    Ok(!matches!(
+        state,
+        None | Some(ValidatorState::Jailed) | Some(ValidatorState::Inactive)
+    ))
+}
+
+pub async fn get_validator_stake(
+    ctx: &Ctx,
+    validator: &Address,
+    epoch: Epoch,
+    retry_config: RetryConfig,
+) -> Result<Amount, QueryError> {
+    let stake =
+        tryhard::retry_fn(|| rpc::get_validator_stake(&ctx.namada.client, epoch.into(), validator))
+            .with_config(retry_config)
+            .on_retry(|attempt, _, error| {
+                count_retry();
+                let error = error.to_string();
+                async move {
+                    tracing::info!("Retry {attempt} due to {error}...");
+                }
+            })
+            .await
+            .map_err(QueryError::Rpc)?;
+
+    Ok(stake
+        .to_string()
+        .parse()
+        .expect("Amount conversion shouldn't fail"))
+}
+
+pub async fn get_pgf_stewards(
+    ctx: &Ctx,
+    retry_config: RetryConfig,
+) -> Result<Vec<Address>, QueryError> {
+    let stewards = tryhard::retry_fn(|| rpc::query_pgf_stewards(&ctx.namada.client))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)?;
+
+    Ok(stewards
+        .into_iter()
+        .map(|steward| steward.address)
+        .collect())
+}
+
+/// Continuous funding amounts by the target
+pub async fn get_pgf_fundings(
+    ctx: &Ctx,
+    retry_config: RetryConfig,
+) -> Result<HashMap<String, token::Amount>, QueryError> {
+    let fundings = tryhard::retry_fn(|| rpc::query_pgf_fundings(&ctx.namada.client))
+        .with_config(retry_config)
+        .on_retry(|attempt, _, error| {
+            count_retry();
+            let error = error.to_string();
+            async move {
+                tracing::info!("Retry {attempt} due to {error}...");
+            }
+        })
+        .await
+        .map_err(QueryError::Rpc)?;
+
+    Ok(fundings
+        .into_iter()
+        .map(|funding| (funding.detail.target(), funding.detail.amount()))
+        .collect())
+}
+
 pub async fn get_vote_results(
     ctx: &Ctx,
     target: &Alias,
@@ -588,6 +1068,7 @@ pub async fn get_vote_results(
     let votes = tryhard::retry_fn(|| rpc::query_proposal_votes(&ctx.namada.client, proposal_id))
         .with_config(retry_config)
         .on_retry(|attempt, _, error| {
+            count_retry();
             let error = error.to_string();
             async move {
                 tracing::info!("Retry {} due to {}...", attempt, error);
@@ -609,3 +1090,41 @@ pub async fn get_vote_results(
 
     Ok(votes)
 }
+
+/// Total unbonded amount from the validator after slashing
+pub async fn get_unbonds(
+    ctx: &Ctx,
+    source: &Alias,
+    validator: &str,
+    retry_config: RetryConfig,
+) -> Result<token::Amount, QueryError> {
+    let wallet = ctx.namada.wallet.read().await;
+    let source_address = wallet
+        .find_address(&source.name)
+        .ok_or_else(|| QueryError::Wallet(format!("No source address: {}", source.name)))?
+        .into_owned();
+    drop(wallet);
+    let validator_address =
+        Address::from_str(validator).expect("ValidatorAddress should be converted");
+
+    let unbonds = tryhard::retry_fn(|| {
+        rpc::query_unbond_with_slashing(&ctx.namada.client, &source_address, &validator_address)
+    })
+    .with_config(retry_config)
+    .on_retry(|attempt, _, error| {
+        count_retry();
+        let error = error.to_string();
+        async move {
+            tracing::info!("Retry {attempt} due to {error}...");
+        }
+    })
+    .await
+    .map_err(QueryError::Rpc)?;
+
+    Ok(unbonds
+        .into_values()
+        .fold(token::Amount::zero(), |acc, amount| {
+            acc.checked_add(amount)
+                .expect("Unbond amount shouldn't overflow")
+        }))
+}
@@ -0,0 +1,68 @@
+use namada_sdk::key::SchemeType;
+use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::masp_primitives::zip32;
+use namada_sdk::PaymentAddress;
+
+use crate::context::Ctx;
+use crate::error::QueryError;
+use crate::types::Alias;
+use crate::utils::{get_block_height, key_rng, retry_config};
+
+/// Generate a keypair, a spending key and a payment address for the alias
+pub async fn gen_wallet_keys(ctx: &Ctx, alias: &Alias) -> Result<(), QueryError> {
+    let height = get_block_height(ctx, retry_config()).await?;
+
+    let mut wallet = ctx.namada.wallet.write().await;
+    let mut rng = key_rng(&alias.name);
+
+    wallet
+        .gen_store_secret_key(
+            SchemeType::Ed25519,
+            Some(alias.name.clone()),
+            true,
+            None,
+            &mut rng,
+        )
+        .ok_or_else(|| {
+            QueryError::Wallet(format!("Failed to generate keypair for {}", alias.name))
+        })?;
+
+    let spending_key_alias = alias.spending_key().name;
+    let (_alias, spending_key) = wallet
+        .gen_store_spending_key(
+            spending_key_alias.clone(),
+            Some(height.into()),
+            None,
+            true,
+            &mut rng,
+        )
+        .ok_or_else(|| {
+            QueryError::Wallet(format!(
+                "Failed to generate spending key for {}",
+                spending_key_alias
+            ))
+        })?;
+
+    let viewing_key = zip32::ExtendedFullViewingKey::from(&spending_key.into())
+        .fvk
+        .vk;
+    let (div, _g_d) = find_valid_diversifier(&mut rng);
+    let masp_payment_addr: namada_sdk::masp_primitives::sapling::PaymentAddress = viewing_key
+        .to_payment_address(div)
+        .expect("Conversion to PaymentAddress shouldn't fail");
+    let payment_addr = PaymentAddress::from(masp_payment_addr);
+
+    let payment_address_alias = alias.payment_address().name;
+    wallet
+        .insert_payment_addr(payment_address_alias.clone(), payment_addr, true)
+        .ok_or_else(|| {
+            QueryError::Wallet(format!(
+                "Failed to insert payment address for {}",
+                payment_address_alias
+            ))
+        })?;
+
+    wallet
+        .save()
+        .map_err(|e| QueryError::Wallet(format!("Failed to save the wallet: {e}")))
+}
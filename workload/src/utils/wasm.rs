@@ -0,0 +1,147 @@
+use namada_sdk::parameters;
+use namada_sdk::storage::{Key, KeySeg};
+
+/// The key and the value are written out of the memory where the tx inputs are written
+const DATA_OFFSET: u64 = 1 << 20;
+const MEMORY_PAGES: u64 = 17;
+
+/// The parameter written by the proposal code with the marker
+pub fn wasm_marker_key(marker: u64) -> Key {
+    Key::from(parameters::ADDRESS.to_db_key())
+        .push(&format!("workload_marker_{marker}"))
+        .expect("Marker key should be valid")
+}
+
+/// Build a tx wasm writing the marker to the marker parameter. Governance allows the parameter
+/// to be written only when the proposal is accepted.
+pub fn build_marker_wasm(marker: u64) -> Vec<u8> {
+    let key = wasm_marker_key(marker).to_string().into_bytes();
+    let value = marker.to_le_bytes();
+
+    let mut body = vec![0x00]; // no local
+    for arg in [
+        DATA_OFFSET,
+        key.len() as u64,
+        DATA_OFFSET + key.len() as u64,
+        value.len() as u64,
+    ] {
+        body.push(0x42); // i64.const
+        push_sleb(&mut body, arg as i64);
+    }
+    body.extend([0x10, 0x00]); // call namada_tx_write
+    body.extend([0x42, 0x01]); // return 1 as the success
+    body.push(0x0b);
+
+    let mut data = vec![0x00, 0x41]; // active segment at i32.const
+    push_sleb(&mut data, DATA_OFFSET as i64);
+    data.push(0x0b);
+    push_uleb(&mut data, (key.len() + value.len()) as u64);
+    data.extend(&key);
+    data.extend(value);
+
+    let mut code = vec![0x01];
+    push_uleb(&mut code, body.len() as u64);
+    code.extend(body);
+
+    let mut module = b"\0asm".to_vec();
+    module.extend(1u32.to_le_bytes());
+    // namada_tx_write: (i64, i64, i64, i64) -> (), _apply_tx: (i64, i64) -> i64
+    push_section(
+        &mut module,
+        0x01,
+        &[
+            0x02, 0x60, 0x04, 0x7e, 0x7e, 0x7e, 0x7e, 0x00, 0x60, 0x02, 0x7e, 0x7e, 0x01, 0x7e,
+        ],
+    );
+    let mut import = vec![0x01];
+    push_name(&mut import, "env");
+    push_name(&mut import, "namada_tx_write");
+    import.extend([0x00, 0x00]);
+    push_section(&mut module, 0x02, &import);
+    push_section(&mut module, 0x03, &[0x01, 0x01]);
+    let mut memory = vec![0x01, 0x00];
+    push_uleb(&mut memory, MEMORY_PAGES);
+    push_section(&mut module, 0x05, &memory);
+    let mut export = vec![0x02];
+    push_name(&mut export, "memory");
+    export.extend([0x02, 0x00]);
+    push_name(&mut export, "_apply_tx");
+    export.extend([0x00, 0x01]);
+    push_section(&mut module, 0x07, &export);
+    push_section(&mut module, 0x0a, &code);
+    let mut data_section = vec![0x01];
+    data_section.extend(data);
+    push_section(&mut module, 0x0b, &data_section);
+
+    module
+}
+
+/// The marker of the code built by `build_marker_wasm`, `None` for any other code
+pub fn find_wasm_marker(code: &[u8]) -> Option<u64> {
+    let value = code
+        .len()
+        .checked_sub(8)
+        .and_then(|start| code.get(start..))?;
+    let marker = u64::from_le_bytes(value.try_into().ok()?);
+    (build_marker_wasm(marker) == code).then_some(marker)
+}
+
+fn push_section(module: &mut Vec<u8>, id: u8, content: &[u8]) {
+    module.push(id);
+    push_uleb(module, content.len() as u64);
+    module.extend(content);
+}
+
+fn push_name(buf: &mut Vec<u8>, name: &str) {
+    push_uleb(buf, name.len() as u64);
+    buf.extend(name.as_bytes());
+}
+
+fn push_uleb(buf: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn push_sleb(buf: &mut Vec<u8>, mut value: i64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let is_last = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if is_last {
+            buf.push(byte);
+            return;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_wasm_is_valid() {
+        for marker in [0, 1, 63, 64, 1 << 20, u64::MAX] {
+            let code = build_marker_wasm(marker);
+            wasmparser::Validator::new()
+                .validate_all(&code)
+                .expect("Marker wasm should be valid");
+            assert_eq!(find_wasm_marker(&code), Some(marker));
+        }
+    }
+
+    #[test]
+    fn test_no_marker_in_other_code() {
+        let mut code = build_marker_wasm(1);
+        code[0] = 0x01;
+        assert_eq!(find_wasm_marker(&code), None);
+        assert_eq!(find_wasm_marker(&[]), None);
+    }
+}
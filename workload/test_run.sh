@@ -21,6 +21,26 @@ done
 
 CHAIN_ID=$(basename $CHAIN_ID)
 
+# Extra genesis tokens held by the faucet, e.g. "btc=tnam1...,eth=tnam1...", the genesis
+# provides them unless they are given
+if [ -z "${WORKLOAD_TOKENS}" ]; then
+    while [ ! -f /container_ready/tokens ]
+    do
+        echo "Waiting for the genesis tokens..."
+        sleep 2
+    done
+    WORKLOAD_TOKENS=$(cat /container_ready/tokens)
+fi
+
+write_tokens() {
+    if [ -n "${WORKLOAD_TOKENS}" ]; then
+        echo "[tokens]" >> "$1"
+        for token in ${WORKLOAD_TOKENS//,/ }; do
+            echo "${token%%=*} = \"${token#*=}\"" >> "$1"
+        done
+    fi
+}
+
 # Wait for the JSON RPC to come up for masp indexer
 json_rpc_ready=0
 while [ $json_rpc_ready != 200 ]
@@ -44,6 +64,7 @@ cosmos_base_dir = "${COSMOS_DIR}"
 namada_channel_id = "channel-0"
 cosmos_channel_id = "channel-0"
 EOF
+write_tokens config_init.toml
 
 /app/namada-chain-workload --config config_init.toml --seed ${TEST_SEED} --concurrency ${WORKLOAD_NUM} --test-time-sec 0 --init
 
@@ -70,6 +91,12 @@ namada_channel_id = "${namada_channel_id}"
 cosmos_channel_id = "${cosmos_channel_id}"
 EOF
 
+if [ -n "${WORKLOAD_PROFILE}" ]; then
+    echo "profile = \"${WORKLOAD_PROFILE}\"" >> config.toml
+fi
+
+write_tokens config.toml
+
 touch /container_ready/workload
 
 /app/namada-chain-workload --config config.toml --seed ${TEST_SEED} --concurrency ${WORKLOAD_NUM} --test-time-sec ${TEST_TIME_SEC}